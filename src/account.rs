@@ -5,6 +5,8 @@ use crate::errors::*;
 use std::collections::BTreeMap;
 use crate::api::API;
 use crate::api::Spot;
+use crate::history::{HistoryIter, HistoryQuery, DAY};
+use serde_json::Value;
 
 
 
@@ -16,32 +18,37 @@ pub struct Account {
     pub recv_window: u64,
 }
 
-struct OrderRequest {
+pub(crate) struct OrderRequest {
     pub symbol: String,
-    pub qty: f64,
-    pub price: f64,
-    pub stop_price: Option<f64>,
+    pub qty: Number,
+    pub price: Number,
+    pub stop_price: Option<Number>,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
     pub new_client_order_id: Option<String>,
-    pub iceberg_qty: Option<f64>
+    pub iceberg_qty: Option<Number>
 }
 
 struct OrderQuoteQuantityRequest {
     pub symbol: String,
-    pub quote_order_qty: f64,
-    pub price: f64,
+    pub quote_order_qty: Number,
+    pub price: Number,
     pub order_side: OrderSide,
     pub order_type: OrderType,
     pub time_in_force: TimeInForce,
     pub new_client_order_id: Option<String>,
 }
 
+#[derive(Clone)]
 pub enum OrderType {
     Limit,
     Market,
+    StopLoss,
     StopLossLimit,
+    TakeProfit,
+    TakeProfitLimit,
+    LimitMaker,
 }
 
 impl From<OrderType> for String {
@@ -49,7 +56,11 @@ impl From<OrderType> for String {
         match item {
             OrderType::Limit => String::from("LIMIT"),
             OrderType::Market => String::from("MARKET"),
+            OrderType::StopLoss => String::from("STOP_LOSS"),
             OrderType::StopLossLimit => String::from("STOP_LOSS_LIMIT"),
+            OrderType::TakeProfit => String::from("TAKE_PROFIT"),
+            OrderType::TakeProfitLimit => String::from("TAKE_PROFIT_LIMIT"),
+            OrderType::LimitMaker => String::from("LIMIT_MAKER"),
         }
     }
 }
@@ -87,6 +98,633 @@ impl From<TimeInForce> for String {
     }
 }
 
+/// Shape of the response to a new order, MARKET and LIMIT orders default
+/// to `Full` and the other types to `Ack`
+#[derive(Clone, Copy)]
+pub enum NewOrderResponseType {
+    Ack,
+    Result,
+    Full,
+}
+
+impl From<NewOrderResponseType> for String {
+    fn from(item: NewOrderResponseType) -> Self {
+        match item {
+            NewOrderResponseType::Ack => String::from("ACK"),
+            NewOrderResponseType::Result => String::from("RESULT"),
+            NewOrderResponseType::Full => String::from("FULL"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum SelfTradePreventionMode {
+    None,
+    ExpireTaker,
+    ExpireMaker,
+    ExpireBoth,
+    Decrement,
+}
+
+impl From<SelfTradePreventionMode> for String {
+    fn from(item: SelfTradePreventionMode) -> Self {
+        match item {
+            SelfTradePreventionMode::None => String::from("NONE"),
+            SelfTradePreventionMode::ExpireTaker => String::from("EXPIRE_TAKER"),
+            SelfTradePreventionMode::ExpireMaker => String::from("EXPIRE_MAKER"),
+            SelfTradePreventionMode::ExpireBoth => String::from("EXPIRE_BOTH"),
+            SelfTradePreventionMode::Decrement => String::from("DECREMENT"),
+        }
+    }
+}
+
+/// Price a pegged order at the best price of its own side (`PrimaryPeg`)
+/// or of the opposite side (`MarketPeg`) of the book
+#[derive(Clone)]
+pub enum PegPriceType {
+    PrimaryPeg,
+    MarketPeg,
+}
+
+impl From<PegPriceType> for String {
+    fn from(item: PegPriceType) -> Self {
+        match item {
+            PegPriceType::PrimaryPeg => String::from("PRIMARY_PEG"),
+            PegPriceType::MarketPeg => String::from("MARKET_PEG"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum CancelReplaceMode {
+    StopOnFailure,
+    AllowFailure,
+}
+
+impl From<CancelReplaceMode> for String {
+    fn from(item: CancelReplaceMode) -> Self {
+        match item {
+            CancelReplaceMode::StopOnFailure => String::from("STOP_ON_FAILURE"),
+            CancelReplaceMode::AllowFailure => String::from("ALLOW_FAILURE"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum CancelRestrictions {
+    OnlyNew,
+    OnlyPartiallyFilled,
+}
+
+impl From<CancelRestrictions> for String {
+    fn from(item: CancelRestrictions) -> Self {
+        match item {
+            CancelRestrictions::OnlyNew => String::from("ONLY_NEW"),
+            CancelRestrictions::OnlyPartiallyFilled => String::from("ONLY_PARTIALLY_FILLED"),
+        }
+    }
+}
+
+/// Spot order of any type, checked before it is sent.
+///
+///```no_run
+/// use binance::api::Binance;
+/// use binance::account::*;
+///
+/// fn main() {
+///     let account: Account = Binance::new(Some("api_key".into()), Some("secret_key".into()));
+///     let order = NewOrder::new("BNBUSDT", OrderSide::Sell, OrderType::TakeProfitLimit)
+///         .set_quantity("1".parse().unwrap())
+///         .set_price("650".parse().unwrap())
+///         .set_stop_price("640".parse().unwrap())
+///         .set_self_trade_prevention_mode(SelfTradePreventionMode::ExpireTaker)
+///         .set_new_order_resp_type(NewOrderResponseType::Result);
+///     let result = account.place_order(order);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct NewOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub quantity: Option<Number>,
+    pub quote_order_qty: Option<Number>,
+    pub price: Option<Number>,
+    pub stop_price: Option<Number>,
+    /// Trailing stop distance in basis points
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Number>,
+    pub new_client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    /// Values below 1000000 are reserved
+    pub strategy_type: Option<u64>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+    pub new_order_resp_type: Option<NewOrderResponseType>,
+    pub peg_price_type: Option<PegPriceType>,
+    /// Offset in price levels from the pegged price
+    pub peg_offset_value: Option<u32>,
+}
+
+impl NewOrder {
+    pub fn new<S>(symbol: S, side: OrderSide, order_type: OrderType) -> Self
+    where
+        S: Into<String>,
+    {
+        NewOrder {
+            symbol: symbol.into(),
+            side,
+            order_type,
+            time_in_force: None,
+            quantity: None,
+            quote_order_qty: None,
+            price: None,
+            stop_price: None,
+            trailing_delta: None,
+            iceberg_qty: None,
+            new_client_order_id: None,
+            strategy_id: None,
+            strategy_type: None,
+            self_trade_prevention_mode: None,
+            new_order_resp_type: None,
+            peg_price_type: None,
+            peg_offset_value: None,
+        }
+    }
+
+    /// GTC LIMIT order
+    pub fn limit<S>(symbol: S, side: OrderSide, quantity: Number, price: Number) -> Self
+    where
+        S: Into<String>,
+    {
+        NewOrder::new(symbol, side, OrderType::Limit)
+            .set_quantity(quantity)
+            .set_price(price)
+            .set_time_in_force(TimeInForce::GTC)
+    }
+
+    pub fn market<S>(symbol: S, side: OrderSide, quantity: Number) -> Self
+    where
+        S: Into<String>,
+    {
+        NewOrder::new(symbol, side, OrderType::Market).set_quantity(quantity)
+    }
+
+    pub fn set_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn set_quantity(mut self, quantity: Number) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    /// Amount of the quote asset to spend or receive, MARKET orders only
+    pub fn set_quote_order_qty(mut self, quote_order_qty: Number) -> Self {
+        self.quote_order_qty = Some(quote_order_qty);
+        self
+    }
+
+    pub fn set_price(mut self, price: Number) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn set_stop_price(mut self, stop_price: Number) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn set_trailing_delta(mut self, trailing_delta: u64) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
+
+    pub fn set_iceberg_qty(mut self, iceberg_qty: Number) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn set_new_client_order_id<S>(mut self, new_client_order_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.new_client_order_id = Some(new_client_order_id.into());
+        self
+    }
+
+    pub fn set_strategy(mut self, strategy_id: u64, strategy_type: u64) -> Self {
+        self.strategy_id = Some(strategy_id);
+        self.strategy_type = Some(strategy_type);
+        self
+    }
+
+    pub fn set_self_trade_prevention_mode(mut self, mode: SelfTradePreventionMode) -> Self {
+        self.self_trade_prevention_mode = Some(mode);
+        self
+    }
+
+    pub fn set_new_order_resp_type(mut self, new_order_resp_type: NewOrderResponseType) -> Self {
+        self.new_order_resp_type = Some(new_order_resp_type);
+        self
+    }
+
+    /// Peg the price of the order to the book, `offset` price levels away
+    pub fn set_peg(mut self, peg_price_type: PegPriceType, offset: Option<u32>) -> Self {
+        self.peg_price_type = Some(peg_price_type);
+        self.peg_offset_value = offset;
+        self
+    }
+
+    /// Response shape the exchange answers with
+    pub fn response_type(&self) -> NewOrderResponseType {
+        match (self.new_order_resp_type, &self.order_type) {
+            (Some(response_type), _) => response_type,
+            (None, OrderType::Limit) | (None, OrderType::Market) => NewOrderResponseType::Full,
+            (None, _) => NewOrderResponseType::Ack,
+        }
+    }
+
+    /// Check the parameters required, or refused, by the type of the order
+    pub fn validate(&self) -> Result<()> {
+        let order_type = String::from(self.order_type.clone());
+        let has_price = matches!(
+            self.order_type,
+            OrderType::Limit
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfitLimit
+                | OrderType::LimitMaker
+        );
+        let has_trigger = matches!(
+            self.order_type,
+            OrderType::StopLoss
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfit
+                | OrderType::TakeProfitLimit
+        );
+        let has_time_in_force = matches!(
+            self.order_type,
+            OrderType::Limit | OrderType::StopLossLimit | OrderType::TakeProfitLimit
+        );
+
+        match (self.quantity, self.quote_order_qty) {
+            (Some(_), Some(_)) => bail!("quantity and quote_order_qty are exclusive"),
+            (None, Some(_)) if !matches!(self.order_type, OrderType::Market) => {
+                bail!(format!("{} orders take no quote_order_qty", order_type))
+            }
+            (None, None) => bail!(format!("{} orders need a quantity", order_type)),
+            _ => (),
+        }
+
+        if has_price {
+            if self.price.is_none() && self.peg_price_type.is_none() {
+                bail!(format!("{} orders need a price", order_type));
+            }
+        } else if self.price.is_some() || self.peg_price_type.is_some() {
+            bail!(format!("{} orders take no price", order_type));
+        }
+
+        if has_trigger {
+            if self.stop_price.is_none() && self.trailing_delta.is_none() {
+                bail!(format!("{} orders need a stop_price or a trailing_delta", order_type));
+            }
+        } else if self.stop_price.is_some() || self.trailing_delta.is_some() {
+            bail!(format!("{} orders take no stop_price or trailing_delta", order_type));
+        }
+
+        if !has_time_in_force && self.time_in_force.is_some() {
+            bail!(format!("{} orders take no time_in_force", order_type));
+        }
+
+        if self.iceberg_qty.is_some() {
+            if !has_price {
+                bail!(format!("{} orders take no iceberg_qty", order_type));
+            }
+            if !matches!(self.time_in_force, None | Some(TimeInForce::GTC)) {
+                bail!("iceberg orders must be GTC");
+            }
+        }
+
+        if let Some(strategy_type) = self.strategy_type {
+            if strategy_type < 1_000_000 {
+                bail!("strategy_type must be at least 1000000");
+            }
+        }
+
+        if self.peg_offset_value.is_some() && self.peg_price_type.is_none() {
+            bail!("peg_offset_value needs a peg_price_type");
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_parameters(&self) -> Result<BTreeMap<String, String>> {
+        self.validate()?;
+
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), self.symbol.clone());
+        parameters.insert("side".into(), self.side.clone().into());
+        parameters.insert("type".into(), self.order_type.clone().into());
+
+        let time_in_force = match (&self.time_in_force, &self.order_type) {
+            (Some(time_in_force), _) => Some(time_in_force.clone()),
+            (None, OrderType::Limit)
+            | (None, OrderType::StopLossLimit)
+            | (None, OrderType::TakeProfitLimit) => Some(TimeInForce::GTC),
+            (None, _) => None,
+        };
+        if let Some(time_in_force) = time_in_force {
+            parameters.insert("timeInForce".into(), time_in_force.into());
+        }
+        if let Some(quantity) = self.quantity {
+            parameters.insert("quantity".into(), quantity.to_string());
+        }
+        if let Some(quote_order_qty) = self.quote_order_qty {
+            parameters.insert("quoteOrderQty".into(), quote_order_qty.to_string());
+        }
+        if let Some(price) = self.price {
+            parameters.insert("price".into(), price.to_string());
+        }
+        if let Some(stop_price) = self.stop_price {
+            parameters.insert("stopPrice".into(), stop_price.to_string());
+        }
+        if let Some(trailing_delta) = self.trailing_delta {
+            parameters.insert("trailingDelta".into(), trailing_delta.to_string());
+        }
+        if let Some(iceberg_qty) = self.iceberg_qty {
+            parameters.insert("icebergQty".into(), iceberg_qty.to_string());
+        }
+        if let Some(ref new_client_order_id) = self.new_client_order_id {
+            parameters.insert("newClientOrderId".into(), new_client_order_id.clone());
+        }
+        if let Some(strategy_id) = self.strategy_id {
+            parameters.insert("strategyId".into(), strategy_id.to_string());
+        }
+        if let Some(strategy_type) = self.strategy_type {
+            parameters.insert("strategyType".into(), strategy_type.to_string());
+        }
+        if let Some(ref mode) = self.self_trade_prevention_mode {
+            parameters.insert("selfTradePreventionMode".into(), mode.clone().into());
+        }
+        if let Some(new_order_resp_type) = self.new_order_resp_type {
+            parameters.insert("newOrderRespType".into(), new_order_resp_type.into());
+        }
+        if let Some(ref peg_price_type) = self.peg_price_type {
+            parameters.insert("pegPriceType".into(), peg_price_type.clone().into());
+        }
+        if let Some(peg_offset_value) = self.peg_offset_value {
+            parameters.insert("pegOffsetValue".into(), peg_offset_value.to_string());
+            parameters.insert("pegOffsetType".into(), "PRICE_LEVEL".into());
+        }
+
+        Ok(parameters)
+    }
+
+    // The SOR only takes LIMIT and MARKET orders, by quantity
+    pub(crate) fn to_sor_parameters(&self) -> Result<BTreeMap<String, String>> {
+        if !matches!(self.order_type, OrderType::Limit | OrderType::Market) {
+            bail!("SOR orders are LIMIT or MARKET orders");
+        }
+        if self.quote_order_qty.is_some() {
+            bail!("SOR orders take no quote_order_qty");
+        }
+        if self.peg_price_type.is_some() {
+            bail!("SOR orders can't be pegged");
+        }
+        self.to_parameters()
+    }
+}
+
+/// Order of an order list, its side and quantity are the ones of the list
+#[derive(Clone)]
+pub struct ListLeg {
+    pub order_type: OrderType,
+    pub time_in_force: Option<TimeInForce>,
+    pub price: Option<Number>,
+    pub stop_price: Option<Number>,
+    pub trailing_delta: Option<u64>,
+    pub iceberg_qty: Option<Number>,
+    pub client_order_id: Option<String>,
+    pub strategy_id: Option<u64>,
+    pub strategy_type: Option<u64>,
+}
+
+impl ListLeg {
+    pub fn new(order_type: OrderType) -> Self {
+        ListLeg {
+            order_type,
+            time_in_force: None,
+            price: None,
+            stop_price: None,
+            trailing_delta: None,
+            iceberg_qty: None,
+            client_order_id: None,
+            strategy_id: None,
+            strategy_type: None,
+        }
+    }
+
+    /// GTC LIMIT order
+    pub fn limit(price: Number) -> Self {
+        ListLeg::new(OrderType::Limit).set_price(price)
+    }
+
+    pub fn limit_maker(price: Number) -> Self {
+        ListLeg::new(OrderType::LimitMaker).set_price(price)
+    }
+
+    pub fn stop_loss(stop_price: Number) -> Self {
+        ListLeg::new(OrderType::StopLoss).set_stop_price(stop_price)
+    }
+
+    /// GTC STOP_LOSS_LIMIT order
+    pub fn stop_loss_limit(price: Number, stop_price: Number) -> Self {
+        ListLeg::new(OrderType::StopLossLimit)
+            .set_price(price)
+            .set_stop_price(stop_price)
+    }
+
+    pub fn take_profit(stop_price: Number) -> Self {
+        ListLeg::new(OrderType::TakeProfit).set_stop_price(stop_price)
+    }
+
+    /// GTC TAKE_PROFIT_LIMIT order
+    pub fn take_profit_limit(price: Number, stop_price: Number) -> Self {
+        ListLeg::new(OrderType::TakeProfitLimit)
+            .set_price(price)
+            .set_stop_price(stop_price)
+    }
+
+    pub fn set_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = Some(time_in_force);
+        self
+    }
+
+    pub fn set_price(mut self, price: Number) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn set_stop_price(mut self, stop_price: Number) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    pub fn set_trailing_delta(mut self, trailing_delta: u64) -> Self {
+        self.trailing_delta = Some(trailing_delta);
+        self
+    }
+
+    pub fn set_iceberg_qty(mut self, iceberg_qty: Number) -> Self {
+        self.iceberg_qty = Some(iceberg_qty);
+        self
+    }
+
+    pub fn set_client_order_id<S>(mut self, client_order_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.client_order_id = Some(client_order_id.into());
+        self
+    }
+
+    pub fn set_strategy(mut self, strategy_id: u64, strategy_type: u64) -> Self {
+        self.strategy_id = Some(strategy_id);
+        self.strategy_type = Some(strategy_type);
+        self
+    }
+
+    // The leg as a stand-alone order, to check it like one
+    fn to_order(&self, symbol: &str, side: OrderSide, quantity: Number) -> NewOrder {
+        NewOrder {
+            time_in_force: self.time_in_force.clone(),
+            quantity: Some(quantity),
+            price: self.price,
+            stop_price: self.stop_price,
+            trailing_delta: self.trailing_delta,
+            iceberg_qty: self.iceberg_qty,
+            new_client_order_id: self.client_order_id.clone(),
+            strategy_id: self.strategy_id,
+            strategy_type: self.strategy_type,
+            ..NewOrder::new(symbol, side, self.order_type.clone())
+        }
+    }
+}
+
+/// OCO placed through the deprecated `order/oco` endpoint: a LIMIT order with a
+/// STOP_LOSS order, or a STOP_LOSS_LIMIT one when `stop_limit_price` is set
+#[derive(Clone)]
+pub struct LegacyOcoOrder {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub quantity: Number,
+    pub price: Number,
+    pub stop_price: Number,
+    pub stop_limit_price: Option<Number>,
+    /// GTC when `stop_limit_price` is set and this is not
+    pub stop_limit_time_in_force: Option<TimeInForce>,
+    pub list_client_order_id: Option<String>,
+    pub limit_client_order_id: Option<String>,
+    pub stop_client_order_id: Option<String>,
+}
+
+/// One Cancels the Other: an order `above` and an order `below` the market price,
+/// the fill of one cancels the other
+#[derive(Clone)]
+pub struct OcoOrderList {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub quantity: Number,
+    /// STOP_LOSS(_LIMIT), TAKE_PROFIT(_LIMIT) or LIMIT_MAKER
+    pub above: ListLeg,
+    /// STOP_LOSS(_LIMIT), TAKE_PROFIT(_LIMIT) or LIMIT_MAKER
+    pub below: ListLeg,
+    pub list_client_order_id: Option<String>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+}
+
+/// One Triggers the Other: the `pending` order is placed once the `working` order is filled
+#[derive(Clone)]
+pub struct OtoOrderList {
+    pub symbol: String,
+    /// LIMIT or LIMIT_MAKER
+    pub working: ListLeg,
+    pub working_side: OrderSide,
+    pub working_quantity: Number,
+    pub pending: ListLeg,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Number,
+    pub list_client_order_id: Option<String>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+}
+
+/// One Triggers a One Cancels the Other: an OCO is placed once the `working` order is filled,
+/// `pending_below` is optional
+#[derive(Clone)]
+pub struct OtocoOrderList {
+    pub symbol: String,
+    /// LIMIT or LIMIT_MAKER
+    pub working: ListLeg,
+    pub working_side: OrderSide,
+    pub working_quantity: Number,
+    pub pending_side: OrderSide,
+    pub pending_quantity: Number,
+    pub pending_above: ListLeg,
+    pub pending_below: Option<ListLeg>,
+    pub list_client_order_id: Option<String>,
+    pub self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+}
+
+/// Order to cancel in a cancel-replace, identified by `cancel_order_id` or
+/// `cancel_orig_client_order_id`
+#[derive(Clone)]
+pub struct CancelReplaceOrder {
+    pub mode: CancelReplaceMode,
+    pub cancel_order_id: Option<u64>,
+    pub cancel_orig_client_order_id: Option<String>,
+    pub cancel_new_client_order_id: Option<String>,
+    pub cancel_restrictions: Option<CancelRestrictions>,
+}
+
+impl CancelReplaceOrder {
+    pub fn new(mode: CancelReplaceMode, cancel_order_id: u64) -> Self {
+        CancelReplaceOrder {
+            mode,
+            cancel_order_id: Some(cancel_order_id),
+            cancel_orig_client_order_id: None,
+            cancel_new_client_order_id: None,
+            cancel_restrictions: None,
+        }
+    }
+
+    pub fn with_client_id<S>(mode: CancelReplaceMode, cancel_orig_client_order_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        CancelReplaceOrder {
+            mode,
+            cancel_order_id: None,
+            cancel_orig_client_order_id: Some(cancel_orig_client_order_id.into()),
+            cancel_new_client_order_id: None,
+            cancel_restrictions: None,
+        }
+    }
+
+    pub fn set_cancel_new_client_order_id<S>(mut self, cancel_new_client_order_id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.cancel_new_client_order_id = Some(cancel_new_client_order_id.into());
+        self
+    }
+
+    pub fn set_cancel_restrictions(mut self, cancel_restrictions: CancelRestrictions) -> Self {
+        self.cancel_restrictions = Some(cancel_restrictions);
+        self
+    }
+}
+
 impl Account {
     // Account Information
     pub fn get_account(&self) -> Result<AccountInformation> {
@@ -114,6 +752,69 @@ impl Account {
         }
     }
 
+    // Commission rates of the account for ONE symbol
+    pub fn get_commission<S>(&self, symbol: S) -> Result<AccountCommission>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AccountCommission), Some(request))
+    }
+
+    // Unfilled order count of the account in every interval
+    pub fn get_order_rate_limits(&self) -> Result<Vec<RateLimitUsage>> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::RateLimitOrder), Some(request))
+    }
+
+    // Matches prevented by self-trade prevention, by `prevented_match_id` or by taker
+    // `order_id`, from `from_prevented_match_id`
+    pub fn get_prevented_matches<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, prevented_match_id: S2, order_id: S3, from_prevented_match_id: S4,
+        limit: S5,
+    ) -> Result<Vec<PreventedMatch>>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        match (prevented_match_id.into(), order_id.into()) {
+            (Some(prevented_match_id), _) => {
+                parameters.insert("preventedMatchId".into(), prevented_match_id.to_string());
+            }
+            (None, Some(order_id)) => {
+                parameters.insert("orderId".into(), order_id.to_string());
+                if let Some(from_id) = from_prevented_match_id.into() {
+                    parameters.insert("fromPreventedMatchId".into(), from_id.to_string());
+                }
+                if let Some(lt) = limit.into() {
+                    parameters.insert("limit".into(), format!("{}", lt));
+                }
+            }
+            (None, None) => bail!("Either prevented_match_id or order_id must be sent"),
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::MyPreventedMatches), Some(request))
+    }
+
+    // Allocations of the orders placed by the Smart Order Routing
+    pub fn get_allocations(&self, query: &HistoryQuery) -> Result<Vec<Allocation>> {
+        let request = build_signed_request(query.allocation_parameters(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::MyAllocations), Some(request))
+    }
+
     // Current open orders for ONE symbol
     pub fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
     where
@@ -165,7 +866,7 @@ impl Account {
 
 
     // Cancel all open orders for a single symbol
-    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<OrderCanceled>>
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<Vec<OpenOrderCanceled>>
     where
         S: Into<String>,
     {
@@ -208,10 +909,10 @@ impl Account {
     }
 
     // Place a LIMIT order - BUY
-    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub fn limit_buy<S, F>(&self, symbol: S, qty: F, price: Number) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -224,7 +925,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -232,10 +933,10 @@ impl Account {
     /// Place a test limit order - BUY
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub fn test_limit_buy<S, F>(&self, symbol: S, qty: F, price: Number) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -248,7 +949,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -256,10 +957,10 @@ impl Account {
     }
 
     // Place a LIMIT order - SELL
-    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<Transaction>
+    pub fn limit_sell<S, F>(&self, symbol: S, qty: F, price: Number) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -272,7 +973,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -280,10 +981,10 @@ impl Account {
     /// Place a test LIMIT order - SELL
     ///
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
-    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: f64) -> Result<()>
+    pub fn test_limit_sell<S, F>(&self, symbol: S, qty: F, price: Number) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -296,7 +997,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -307,12 +1008,12 @@ impl Account {
     pub fn market_buy<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Number::default(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
@@ -320,7 +1021,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -331,12 +1032,12 @@ impl Account {
     pub fn test_market_buy<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Number::default(),
             stop_price: None,
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
@@ -344,7 +1045,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -357,12 +1058,12 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Number::default(),
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -381,12 +1082,12 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let buy: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Number::default(),
             order_side: OrderSide::Buy,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -403,12 +1104,12 @@ impl Account {
     pub fn market_sell<S, F>(&self, symbol: S, qty: F) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Number::default(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
@@ -416,7 +1117,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -427,12 +1128,12 @@ impl Account {
     pub fn test_market_sell<S, F>(&self, symbol: S, qty: F) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
             qty: qty.into(),
-            price: 0.0,
+            price: Number::default(),
             stop_price: None,
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
@@ -440,7 +1141,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -453,12 +1154,12 @@ impl Account {
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Number::default(),
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -477,12 +1178,12 @@ impl Account {
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderQuoteQuantityRequest = OrderQuoteQuantityRequest {
             symbol: symbol.into(),
             quote_order_qty: quote_order_qty.into(),
-            price: 0.0,
+            price: Number::default(),
             order_side: OrderSide::Sell,
             order_type: OrderType::Market,
             time_in_force: TimeInForce::GTC,
@@ -506,15 +1207,17 @@ impl Account {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
     ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let price = "0.1".parse().unwrap();
+    ///     let stop_price = "0.09".parse().unwrap();
+    ///     let result = account.stop_limit_buy_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
     pub fn stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Number, stop_price: Number, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -527,7 +1230,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -545,15 +1248,17 @@ impl Account {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
     ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.test_stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let price = "0.1".parse().unwrap();
+    ///     let stop_price = "0.09".parse().unwrap();
+    ///     let result = account.test_stop_limit_buy_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
     pub fn test_stop_limit_buy_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Number, stop_price: Number, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -566,7 +1271,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -584,15 +1289,17 @@ impl Account {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
     ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let price = "0.1".parse().unwrap();
+    ///     let stop_price = "0.09".parse().unwrap();
+    ///     let result = account.stop_limit_sell_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
     pub fn stop_limit_sell_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Number, stop_price: Number, time_in_force: TimeInForce,
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -605,7 +1312,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -623,15 +1330,17 @@ impl Account {
     ///     let api_key = Some("api_key".into());
     ///     let secret_key = Some("secret_key".into());
     ///     let account: Account = Binance::new(api_key, secret_key);
-    ///     let result = account.test_stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC);
+    ///     let price = "0.1".parse().unwrap();
+    ///     let stop_price = "0.09".parse().unwrap();
+    ///     let result = account.test_stop_limit_sell_order("LTCBTC", 1, price, stop_price, TimeInForce::GTC);
     /// }
     /// ```
     pub fn test_stop_limit_sell_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: f64, time_in_force: TimeInForce,
+        &self, symbol: S, qty: F, price: Number, stop_price: Number, time_in_force: TimeInForce,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -644,7 +1353,7 @@ impl Account {
             new_client_order_id: None,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
@@ -654,7 +1363,7 @@ impl Account {
     /// Cancel - Replace a exist order
     #[allow(clippy::too_many_arguments)]
     pub fn cancel_replace<S>(
-        &self, symbol: S, qty: f64, price: f64,  order_side: OrderSide,
+        &self, symbol: S, qty: Number, price: Number,  order_side: OrderSide,
         order_type: OrderType, time_in_force: TimeInForce, new_client_order_id: S,
         cancel_order_id: S
     ) -> Result<CancelReplace>
@@ -668,7 +1377,7 @@ impl Account {
         params.insert("type".into(), order_type.into());
         params.insert("quantity".into(), qty.to_string());
 
-        if price != 0.0 {
+        if price != Number::default() {
             params.insert("price".into(), price.to_string());
             params.insert("timeInForce".into(), time_in_force.into());
         }
@@ -684,14 +1393,14 @@ impl Account {
     /// Place a custom order
     #[allow(clippy::too_many_arguments)]
     pub fn custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
-        order_type: OrderType, time_in_force: TimeInForce,  iceberg_qty: Option<f64>,
+        &self, symbol: S, qty: F, price: Number, stop_price: Option<Number>, order_side: OrderSide,
+        order_type: OrderType, time_in_force: TimeInForce,  iceberg_qty: Option<Number>,
         new_client_order_id: Option<String>,
 
     ) -> Result<Transaction>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -704,7 +1413,7 @@ impl Account {
             new_client_order_id,
             iceberg_qty,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client.post_signed(API::Spot(Spot::Order), request)
     }
@@ -714,12 +1423,12 @@ impl Account {
     /// This order is sandboxed: it is validated, but not sent to the matching engine.
     #[allow(clippy::too_many_arguments)]
     pub fn test_custom_order<S, F>(
-        &self, symbol: S, qty: F, price: f64, stop_price: Option<f64>, order_side: OrderSide,
+        &self, symbol: S, qty: F, price: Number, stop_price: Option<Number>, order_side: OrderSide,
         order_type: OrderType, time_in_force: TimeInForce, new_client_order_id: Option<String>,
     ) -> Result<()>
     where
         S: Into<String>,
-        F: Into<f64>,
+        F: Into<Number>,
     {
         let sell: OrderRequest = OrderRequest {
             symbol: symbol.into(),
@@ -732,13 +1441,81 @@ impl Account {
             new_client_order_id,
             iceberg_qty: None,
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
             .map(|_| ())
     }
 
+    /// Place an order of any type, the response matches the `NewOrderResponseType` of the order
+    pub fn place_order(&self, order: NewOrder) -> Result<OrderResponse> {
+        let response_type = order.response_type();
+        let request = build_signed_request(order.to_parameters()?, self.recv_window)?;
+        let response: Value = self.client.post_signed(API::Spot(Spot::Order), request)?;
+        order_response(response, response_type)
+    }
+
+    /// Place a test order of any type
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_place_order(&self, order: NewOrder) -> Result<()> {
+        let request = build_signed_request(order.to_parameters()?, self.recv_window)?;
+        self.client
+            .post_signed::<Empty>(API::Spot(Spot::OrderTest), request)
+            .map(|_| ())
+    }
+
+    /// Place a LIMIT or MARKET order through the Smart Order Routing
+    ///
+    /// The order may be filled on the order books of the other symbols with the same base
+    /// asset and an interchangeable quote asset, see `Transaction::used_sor` and the
+    /// `alloc_id` of the fills.
+    pub fn place_sor_order(&self, order: NewOrder) -> Result<OrderResponse> {
+        let response_type = order.response_type();
+        let request = build_signed_request(order.to_sor_parameters()?, self.recv_window)?;
+        let response: Value = self.client.post_signed(API::Spot(Spot::SorOrder), request)?;
+        order_response(response, response_type)
+    }
+
+    /// Place a test SOR order
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    pub fn test_sor_order(&self, order: NewOrder) -> Result<()> {
+        let request = build_signed_request(order.to_sor_parameters()?, self.recv_window)?;
+        self.client
+            .post_signed::<Empty>(API::Spot(Spot::SorOrderTest), request)
+            .map(|_| ())
+    }
+
+    /// Cancel an order and place `order` in one request
+    pub fn cancel_replace_order(
+        &self, cancel: CancelReplaceOrder, order: NewOrder,
+    ) -> Result<CancelReplace> {
+        let mut parameters = order.to_parameters()?;
+        parameters.insert("cancelReplaceMode".into(), cancel.mode.into());
+        match (cancel.cancel_order_id, cancel.cancel_orig_client_order_id) {
+            (Some(order_id), _) => {
+                parameters.insert("cancelOrderId".into(), order_id.to_string());
+            }
+            (None, Some(orig_client_order_id)) => {
+                parameters.insert("cancelOrigClientOrderId".into(), orig_client_order_id);
+            }
+            (None, None) => {
+                bail!("Either cancel_order_id or cancel_orig_client_order_id must be sent")
+            }
+        }
+        if let Some(cancel_new_client_order_id) = cancel.cancel_new_client_order_id {
+            parameters.insert("cancelNewClientOrderId".into(), cancel_new_client_order_id);
+        }
+        if let Some(cancel_restrictions) = cancel.cancel_restrictions {
+            parameters.insert("cancelRestrictions".into(), cancel_restrictions.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::CancelReplace), request)
+    }
+
     // Check an order's status
     pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<OrderCanceled>
     where
@@ -783,44 +1560,244 @@ impl Account {
             .map(|_| ())
     }
 
-    // Trade history
-    pub fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    /// Place an OCO through the deprecated `order/oco` endpoint
+    pub fn place_legacy_oco(&self, order: LegacyOcoOrder) -> Result<OrderList> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), order.symbol);
+        parameters.insert("side".into(), order.side.into());
+        parameters.insert("quantity".into(), order.quantity.to_string());
+        parameters.insert("price".into(), order.price.to_string());
+        parameters.insert("stopPrice".into(), order.stop_price.to_string());
+
+        match (order.stop_limit_price, order.stop_limit_time_in_force) {
+            (Some(stop_limit_price), time_in_force) => {
+                parameters.insert("stopLimitPrice".into(), stop_limit_price.to_string());
+                parameters.insert(
+                    "stopLimitTimeInForce".into(),
+                    time_in_force.unwrap_or(TimeInForce::GTC).into(),
+                );
+            }
+            (None, Some(_)) => bail!("stop_limit_time_in_force needs a stop_limit_price"),
+            (None, None) => (),
+        }
+        if let Some(list_client_order_id) = order.list_client_order_id {
+            parameters.insert("listClientOrderId".into(), list_client_order_id);
+        }
+        if let Some(limit_client_order_id) = order.limit_client_order_id {
+            parameters.insert("limitClientOrderId".into(), limit_client_order_id);
+        }
+        if let Some(stop_client_order_id) = order.stop_client_order_id {
+            parameters.insert("stopClientOrderId".into(), stop_client_order_id);
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::Oco), request)
+    }
+
+    /// Place an OCO order list
+    pub fn place_oco(&self, order: OcoOrderList) -> Result<OrderList> {
+        if !is_oco_leg(&order.above) || !is_oco_leg(&order.below) {
+            bail!("OCO orders are STOP_LOSS(_LIMIT), TAKE_PROFIT(_LIMIT) or LIMIT_MAKER");
+        }
+
+        let mut parameters = list_parameters(
+            order.symbol.clone(), order.list_client_order_id, order.self_trade_prevention_mode,
+        );
+        parameters.insert("side".into(), order.side.clone().into());
+        parameters.insert("quantity".into(), order.quantity.to_string());
+        insert_leg(
+            &mut parameters, "above", &order.above, &order.symbol, &order.side, order.quantity,
+            false,
+        )?;
+        insert_leg(
+            &mut parameters, "below", &order.below, &order.symbol, &order.side, order.quantity,
+            false,
+        )?;
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::OrderListOco), request)
+    }
+
+    /// Place an OTO order list
+    pub fn place_oto(&self, order: OtoOrderList) -> Result<OrderList> {
+        if !is_working_leg(&order.working) {
+            bail!("The working order of a list is LIMIT or LIMIT_MAKER");
+        }
+
+        let mut parameters = list_parameters(
+            order.symbol.clone(), order.list_client_order_id, order.self_trade_prevention_mode,
+        );
+        insert_leg(
+            &mut parameters, "working", &order.working, &order.symbol, &order.working_side,
+            order.working_quantity, true,
+        )?;
+        insert_leg(
+            &mut parameters, "pending", &order.pending, &order.symbol, &order.pending_side,
+            order.pending_quantity, true,
+        )?;
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::OrderListOto), request)
+    }
+
+    /// Place an OTOCO order list
+    pub fn place_otoco(&self, order: OtocoOrderList) -> Result<OrderList> {
+        if !is_working_leg(&order.working) {
+            bail!("The working order of a list is LIMIT or LIMIT_MAKER");
+        }
+        let pending_below_valid = match order.pending_below {
+            Some(ref pending_below) => is_oco_leg(pending_below),
+            None => true,
+        };
+        if !is_oco_leg(&order.pending_above) || !pending_below_valid {
+            bail!("OCO orders are STOP_LOSS(_LIMIT), TAKE_PROFIT(_LIMIT) or LIMIT_MAKER");
+        }
+
+        let mut parameters = list_parameters(
+            order.symbol.clone(), order.list_client_order_id, order.self_trade_prevention_mode,
+        );
+        insert_leg(
+            &mut parameters, "working", &order.working, &order.symbol, &order.working_side,
+            order.working_quantity, true,
+        )?;
+        parameters.insert("pendingSide".into(), order.pending_side.clone().into());
+        parameters.insert("pendingQuantity".into(), order.pending_quantity.to_string());
+        insert_leg(
+            &mut parameters, "pendingAbove", &order.pending_above, &order.symbol,
+            &order.pending_side, order.pending_quantity, false,
+        )?;
+        if let Some(ref pending_below) = order.pending_below {
+            insert_leg(
+                &mut parameters, "pendingBelow", pending_below, &order.symbol,
+                &order.pending_side, order.pending_quantity, false,
+            )?;
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client.post_signed(API::Spot(Spot::OrderListOtoco), request)
+    }
+
+    /// Cancel every order of a list
+    pub fn cancel_order_list<S>(&self, symbol: S, order_list_id: u64) -> Result<OrderList>
     where
         S: Into<String>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
         parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderListId".into(), order_list_id.to_string());
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
-            .get_signed(API::Spot(Spot::MyTrades), Some(request))
+            .delete_signed(API::Spot(Spot::OrderList), Some(request))
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
-        order_parameters.insert("symbol".into(), order.symbol);
-        order_parameters.insert("side".into(), order.order_side.into());
-        order_parameters.insert("type".into(), order.order_type.into());
-        order_parameters.insert("quantity".into(), order.qty.to_string());
+    pub fn cancel_order_list_with_client_id<S>(
+        &self, symbol: S, list_client_order_id: String,
+    ) -> Result<OrderList>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("listClientOrderId".into(), list_client_order_id);
 
-        if let Some(stop_price) = order.stop_price {
-            order_parameters.insert("stopPrice".into(), stop_price.to_string());
-        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Spot(Spot::OrderList), Some(request))
+    }
 
-        if order.price != 0.0 {
-            order_parameters.insert("price".into(), order.price.to_string());
-            order_parameters.insert("timeInForce".into(), order.time_in_force.into());
-        }
+    pub fn get_order_list(&self, order_list_id: u64) -> Result<OrderList> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("orderListId".into(), order_list_id.to_string());
 
-        if let Some(client_order_id) = order.new_client_order_id {
-            order_parameters.insert("newClientOrderId".into(), client_order_id);
-        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OrderList), Some(request))
+    }
 
-        if let Some(iceberg_qty) = order.iceberg_qty {
-            order_parameters.insert("icebergQty".into(), iceberg_qty.to_string());
+    pub fn get_order_list_with_client_id(&self, orig_client_order_id: String) -> Result<OrderList> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("origClientOrderId".into(), orig_client_order_id);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OrderList), Some(request))
+    }
+
+    // Order lists of every status, from `from_id` or within `start_time` and `end_time`
+    pub fn get_all_order_lists<S1, S2, S3, S4>(
+        &self, from_id: S1, start_time: S2, end_time: S3, limit: S4,
+    ) -> Result<Vec<OrderList>>
+    where
+        S1: Into<Option<u64>>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(from_id) = from_id.into() {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
         }
 
-        order_parameters
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrderList), Some(request))
+    }
+
+    pub fn get_open_order_lists(&self) -> Result<Vec<OrderList>> {
+        let request = build_signed_request(BTreeMap::new(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::OpenOrderList), Some(request))
+    }
+
+    // Trade history
+    pub fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::MyTrades), Some(request))
+    }
+
+    // Trade history by order, trade id or time, at most 24 hours between start and end time
+    pub fn trade_history_query(&self, query: &HistoryQuery) -> Result<Vec<TradeHistory>> {
+        let request = build_signed_request(query.trade_parameters(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::MyTrades), Some(request))
+    }
+
+    // Every trade of the query, fetched by pages of up to 1000 trades
+    pub fn trade_history_iter(&self, query: HistoryQuery) -> HistoryIter<TradeHistory> {
+        let account = self.clone();
+        HistoryIter::new(query, 1000, DAY, move |query| {
+            account.trade_history_query(query)
+        })
+    }
+
+    // Orders of every status from an order id or within a time range of at most 24 hours
+    pub fn all_orders_query(&self, query: &HistoryQuery) -> Result<Vec<Order>> {
+        let request = build_signed_request(query.order_parameters(), self.recv_window)?;
+        self.client
+            .get_signed(API::Spot(Spot::AllOrders), Some(request))
+    }
+
+    // Every order of the query, fetched by pages of up to 1000 orders
+    pub fn all_orders_iter(&self, query: HistoryQuery) -> HistoryIter<Order> {
+        let account = self.clone();
+        HistoryIter::new(query, 1000, DAY, move |query| account.all_orders_query(query))
     }
 
     fn build_quote_quantity_order(
@@ -833,7 +1810,7 @@ impl Account {
         order_parameters.insert("type".into(), order.order_type.into());
         order_parameters.insert("quoteOrderQty".into(), order.quote_order_qty.to_string());
 
-        if order.price != 0.0 {
+        if order.price != Number::default() {
             order_parameters.insert("price".into(), order.price.to_string());
             order_parameters.insert("timeInForce".into(), order.time_in_force.into());
         }
@@ -846,3 +1823,81 @@ impl Account {
     }
 }
 
+fn is_oco_leg(leg: &ListLeg) -> bool {
+    !matches!(leg.order_type, OrderType::Limit | OrderType::Market)
+}
+
+fn is_working_leg(leg: &ListLeg) -> bool {
+    matches!(leg.order_type, OrderType::Limit | OrderType::LimitMaker)
+}
+
+fn list_parameters(
+    symbol: String, list_client_order_id: Option<String>,
+    self_trade_prevention_mode: Option<SelfTradePreventionMode>,
+) -> BTreeMap<String, String> {
+    let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+    parameters.insert("symbol".into(), symbol);
+    if let Some(list_client_order_id) = list_client_order_id {
+        parameters.insert("listClientOrderId".into(), list_client_order_id);
+    }
+    if let Some(mode) = self_trade_prevention_mode {
+        parameters.insert("selfTradePreventionMode".into(), mode.into());
+    }
+    parameters
+}
+
+// The parameters of a leg are the ones of an order prefixed by the leg name,
+// "price" of the "above" leg is sent as "abovePrice"
+fn insert_leg(
+    parameters: &mut BTreeMap<String, String>, prefix: &str, leg: &ListLeg, symbol: &str,
+    side: &OrderSide, quantity: Number, with_side_and_quantity: bool,
+) -> Result<()> {
+    let order = leg.to_order(symbol, side.clone(), quantity);
+    for (name, value) in order.to_parameters()? {
+        let name = match name.as_str() {
+            "symbol" => continue,
+            "side" | "quantity" if !with_side_and_quantity => continue,
+            "newClientOrderId" => String::from("ClientOrderId"),
+            _ => name[..1].to_uppercase() + &name[1..],
+        };
+        parameters.insert(format!("{}{}", prefix, name), value);
+    }
+    Ok(())
+}
+
+pub(crate) fn order_response(
+    response: Value, response_type: NewOrderResponseType,
+) -> Result<OrderResponse> {
+    Ok(match response_type {
+        NewOrderResponseType::Ack => OrderResponse::Ack(serde_json::from_value(response)?),
+        NewOrderResponseType::Result => OrderResponse::Result(serde_json::from_value(response)?),
+        NewOrderResponseType::Full => OrderResponse::Full(serde_json::from_value(response)?),
+    })
+}
+
+pub(crate) fn build_order(order: OrderRequest) -> BTreeMap<String, String> {
+    let mut order_parameters: BTreeMap<String, String> = BTreeMap::new();
+    order_parameters.insert("symbol".into(), order.symbol);
+    order_parameters.insert("side".into(), order.order_side.into());
+    order_parameters.insert("type".into(), order.order_type.into());
+    order_parameters.insert("quantity".into(), order.qty.to_string());
+
+    if let Some(stop_price) = order.stop_price {
+        order_parameters.insert("stopPrice".into(), stop_price.to_string());
+    }
+
+    if order.price != Number::default() {
+        order_parameters.insert("price".into(), order.price.to_string());
+        order_parameters.insert("timeInForce".into(), order.time_in_force.into());
+    }
+
+    if let Some(client_order_id) = order.new_client_order_id {
+        order_parameters.insert("newClientOrderId".into(), client_order_id);
+    }
+
+    if let Some(iceberg_qty) = order.iceberg_qty {
+        order_parameters.insert("icebergQty".into(), iceberg_qty.to_string());
+    }
+
+    order_parameters
+}
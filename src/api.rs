@@ -1,6 +1,10 @@
 use crate::account::*;
 use crate::client::*;
 use crate::config::*;
+use crate::delivery::account::DeliveryAccount;
+use crate::delivery::general::DeliveryGeneral;
+use crate::delivery::market::DeliveryMarket;
+use crate::delivery::userstream::DeliveryUserStream;
 use crate::futures::account::FuturesAccount;
 use crate::futures::general::*;
 use crate::futures::market::*;
@@ -8,6 +12,10 @@ use crate::futures::userstream::*;
 use crate::general::*;
 use crate::margin::account::MarginAccount;
 use crate::market::*;
+use crate::options::account::OptionsAccount;
+use crate::options::general::OptionsGeneral;
+use crate::options::market::OptionsMarket;
+use crate::options::userstream::OptionsUserStream;
 use crate::userstream::*;
 use crate::savings::*;
 
@@ -17,6 +25,8 @@ pub enum API {
     Spot(Spot),
     Savings(Sapi),
     Futures(Futures),
+    Delivery(Delivery),
+    Options(Options),
     Margin(Margin)
 }
 
@@ -45,10 +55,19 @@ pub enum Spot {
     OrderList,
     AllOrderList,
     OpenOrderList,
+    OrderListOco,
+    OrderListOto,
+    OrderListOtoco,
     Account,
+    AccountCommission,
+    RateLimitOrder,
     MyTrades,
+    MyPreventedMatches,
+    MyAllocations,
     UserDataStream,
-    CancelReplace
+    CancelReplace,
+    SorOrder,
+    SorOrderTest
 }
 
 #[derive(Clone)]
@@ -95,7 +114,73 @@ pub enum Futures {
     Account,
     OpenOrders,
     UserDataStream,
-    ModifyOrder
+    ModifyOrder,
+    BatchOrders,
+    MarginType,
+    PositionMargin,
+    LeverageBracket,
+    Income,
+    CommissionRate,
+    AdlQuantile,
+    ForceOrders,
+    MultiAssetsMargin,
+    CountdownCancelAll,
+    OrderAmendment,
+    UserTrades,
+}
+
+/// COIN-M futures (`/dapi`) routes.
+#[derive(Clone)]
+pub enum Delivery {
+    Ping,
+    Time,
+    ExchangeInfo,
+    Depth,
+    Trades,
+    HistoricalTrades,
+    AggTrades,
+    PremiumIndex,
+    FundingRate,
+    Klines,
+    ContinuousKlines,
+    IndexPriceKlines,
+    MarkPriceKlines,
+    Ticker24hr,
+    TickerPrice,
+    BookTicker,
+    OpenInterest,
+    Order,
+    AllOpenOrders,
+    OpenOrders,
+    AllOrders,
+    Account,
+    Balance,
+    PositionRisk,
+    ChangeInitialLeverage,
+    PositionSide,
+    UserTrades,
+    UserDataStream,
+}
+
+/// European options (`/eapi`) routes.
+#[derive(Clone)]
+pub enum Options {
+    Ping,
+    Time,
+    ExchangeInfo,
+    Depth,
+    Trades,
+    Klines,
+    Mark,
+    Ticker,
+    Index,
+    OpenInterest,
+    Order,
+    AllOpenOrders,
+    OpenOrders,
+    MarginAccount,
+    Position,
+    UserDataStream,
 }
 
 #[derive(Clone)]
@@ -106,7 +191,8 @@ pub enum Margin{
     UserDataStream,
     UserDataStreamIsolated,
     Account,
-    AccountIsolated
+    AccountIsolated,
+    MyTrades
 }
 
 impl From<API> for String {
@@ -133,10 +219,19 @@ impl From<API> for String {
                 Spot::OrderList => "/api/v3/orderList",
                 Spot::AllOrderList => "/api/v3/allOrderList",
                 Spot::OpenOrderList => "/api/v3/openOrderList",
+                Spot::OrderListOco => "/api/v3/orderList/oco",
+                Spot::OrderListOto => "/api/v3/orderList/oto",
+                Spot::OrderListOtoco => "/api/v3/orderList/otoco",
                 Spot::Account => "/api/v3/account",
+                Spot::AccountCommission => "/api/v3/account/commission",
+                Spot::RateLimitOrder => "/api/v3/rateLimit/order",
                 Spot::MyTrades => "/api/v3/myTrades",
+                Spot::MyPreventedMatches => "/api/v3/myPreventedMatches",
+                Spot::MyAllocations => "/api/v3/myAllocations",
                 Spot::UserDataStream => "/api/v3/userDataStream",
-                Spot::CancelReplace => "/api/v3/order/cancelReplace"
+                Spot::CancelReplace => "/api/v3/order/cancelReplace",
+                Spot::SorOrder => "/api/v3/sor/order",
+                Spot::SorOrderTest => "/api/v3/sor/order/test"
 
             },
             API::Savings(route) => match route {
@@ -181,6 +276,66 @@ impl From<API> for String {
                 Futures::OpenOrders => "/fapi/v1/openOrders",
                 Futures::UserDataStream => "/fapi/v1/listenKey",
                 Futures::ModifyOrder => "/fapi/v1/order",
+                Futures::BatchOrders => "/fapi/v1/batchOrders",
+                Futures::MarginType => "/fapi/v1/marginType",
+                Futures::PositionMargin => "/fapi/v1/positionMargin",
+                Futures::LeverageBracket => "/fapi/v1/leverageBracket",
+                Futures::Income => "/fapi/v1/income",
+                Futures::CommissionRate => "/fapi/v1/commissionRate",
+                Futures::AdlQuantile => "/fapi/v1/adlQuantile",
+                Futures::ForceOrders => "/fapi/v1/forceOrders",
+                Futures::MultiAssetsMargin => "/fapi/v1/multiAssetsMargin",
+                Futures::CountdownCancelAll => "/fapi/v1/countdownCancelAll",
+                Futures::OrderAmendment => "/fapi/v1/orderAmendment",
+                Futures::UserTrades => "/fapi/v1/userTrades",
+            },
+            API::Delivery(route) => match route {
+                Delivery::Ping => "/dapi/v1/ping",
+                Delivery::Time => "/dapi/v1/time",
+                Delivery::ExchangeInfo => "/dapi/v1/exchangeInfo",
+                Delivery::Depth => "/dapi/v1/depth",
+                Delivery::Trades => "/dapi/v1/trades",
+                Delivery::HistoricalTrades => "/dapi/v1/historicalTrades",
+                Delivery::AggTrades => "/dapi/v1/aggTrades",
+                Delivery::PremiumIndex => "/dapi/v1/premiumIndex",
+                Delivery::FundingRate => "/dapi/v1/fundingRate",
+                Delivery::Klines => "/dapi/v1/klines",
+                Delivery::ContinuousKlines => "/dapi/v1/continuousKlines",
+                Delivery::IndexPriceKlines => "/dapi/v1/indexPriceKlines",
+                Delivery::MarkPriceKlines => "/dapi/v1/markPriceKlines",
+                Delivery::Ticker24hr => "/dapi/v1/ticker/24hr",
+                Delivery::TickerPrice => "/dapi/v1/ticker/price",
+                Delivery::BookTicker => "/dapi/v1/ticker/bookTicker",
+                Delivery::OpenInterest => "/dapi/v1/openInterest",
+                Delivery::Order => "/dapi/v1/order",
+                Delivery::AllOpenOrders => "/dapi/v1/allOpenOrders",
+                Delivery::OpenOrders => "/dapi/v1/openOrders",
+                Delivery::AllOrders => "/dapi/v1/allOrders",
+                Delivery::Account => "/dapi/v1/account",
+                Delivery::Balance => "/dapi/v1/balance",
+                Delivery::PositionRisk => "/dapi/v1/positionRisk",
+                Delivery::ChangeInitialLeverage => "/dapi/v1/leverage",
+                Delivery::PositionSide => "/dapi/v1/positionSide/dual",
+                Delivery::UserTrades => "/dapi/v1/userTrades",
+                Delivery::UserDataStream => "/dapi/v1/listenKey",
+            },
+            API::Options(route) => match route {
+                Options::Ping => "/eapi/v1/ping",
+                Options::Time => "/eapi/v1/time",
+                Options::ExchangeInfo => "/eapi/v1/exchangeInfo",
+                Options::Depth => "/eapi/v1/depth",
+                Options::Trades => "/eapi/v1/trades",
+                Options::Klines => "/eapi/v1/klines",
+                Options::Mark => "/eapi/v1/mark",
+                Options::Ticker => "/eapi/v1/ticker",
+                Options::Index => "/eapi/v1/index",
+                Options::OpenInterest => "/eapi/v1/openInterest",
+                Options::Order => "/eapi/v1/order",
+                Options::AllOpenOrders => "/eapi/v1/allOpenOrders",
+                Options::OpenOrders => "/eapi/v1/openOrders",
+                Options::MarginAccount => "/eapi/v1/marginAccount",
+                Options::Position => "/eapi/v1/position",
+                Options::UserDataStream => "/eapi/v1/listenKey",
             },
             API::Margin(route) => match route{
                 Margin::OpenOrders => "/sapi/v1/margin/openOrders",
@@ -189,7 +344,8 @@ impl From<API> for String {
                 Margin::UserDataStream => "/sapi/v1/userDataStream",
                 Margin::UserDataStreamIsolated => "/sapi/v1/userDataStream/isolated",
                 Margin::Account => "/sapi/v1/margin/account",
-                Margin::AccountIsolated => "/sapi/v1/margin/isolated/account"
+                Margin::AccountIsolated => "/sapi/v1/margin/isolated/account",
+                Margin::MyTrades => "/sapi/v1/margin/myTrades"
             }
         })
     }
@@ -211,7 +367,7 @@ impl Binance for General {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> General {
         General {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
         }
     }
 }
@@ -225,7 +381,7 @@ impl Binance for Account {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> Account {
         Account {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
             recv_window: config.recv_window,
         }
     }
@@ -240,7 +396,7 @@ impl Binance for Savings {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> Self {
         Self {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
             recv_window: config.recv_window,
         }
     }
@@ -255,7 +411,7 @@ impl Binance for Market {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> Market {
         Market {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
             recv_window: config.recv_window,
         }
     }
@@ -278,7 +434,7 @@ impl Binance for UserStream {
         }
 
         UserStream {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
             recv_window: config.recv_window,
             endpoint,
         }
@@ -298,7 +454,7 @@ impl Binance for MarginAccount {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> MarginAccount {
         MarginAccount {
-            client: Client::new(api_key, secret_key, config.rest_api_endpoint.clone()),
+            client: Client::new_with_config(api_key, secret_key, config.rest_api_endpoint.clone(), config),
             is_isolated: config.isolated,
             recv_window: config.recv_window,
         }
@@ -319,10 +475,11 @@ impl Binance for FuturesGeneral {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> FuturesGeneral {
         FuturesGeneral {
-            client: Client::new(
+            client: Client::new_with_config(
                 api_key,
                 secret_key,
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
         }
     }
@@ -337,10 +494,11 @@ impl Binance for FuturesMarket {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> FuturesMarket {
         FuturesMarket {
-            client: Client::new(
+            client: Client::new_with_config(
                 api_key,
                 secret_key,
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
@@ -356,10 +514,11 @@ impl Binance for FuturesAccount {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> Self {
         Self {
-            client: Client::new(
+            client: Client::new_with_config(
                 api_key,
                 secret_key,
                 config.futures_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
@@ -375,10 +534,173 @@ impl Binance for FuturesUserStream {
         api_key: Option<String>, secret_key: Option<String>, config: &Config,
     ) -> FuturesUserStream {
         FuturesUserStream {
-            client: Client::new(
+            client: Client::new_with_config(
                 api_key,
                 secret_key,
                 config.futures_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+// *****************************************************
+//              Binance COIN-M Futures API
+// *****************************************************
+
+impl Binance for DeliveryGeneral {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> DeliveryGeneral {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> DeliveryGeneral {
+        DeliveryGeneral {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.delivery_rest_api_endpoint.clone(),
+                config,
+            ),
+        }
+    }
+}
+
+impl Binance for DeliveryMarket {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> DeliveryMarket {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> DeliveryMarket {
+        DeliveryMarket {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.delivery_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for DeliveryAccount {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> Self {
+        Self {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.delivery_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for DeliveryUserStream {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> DeliveryUserStream {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> DeliveryUserStream {
+        DeliveryUserStream {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.delivery_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for OptionsGeneral {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> OptionsGeneral {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> OptionsGeneral {
+        OptionsGeneral {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.options_rest_api_endpoint.clone(),
+                config,
+            ),
+        }
+    }
+}
+
+impl Binance for OptionsMarket {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> OptionsMarket {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> OptionsMarket {
+        OptionsMarket {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.options_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for OptionsAccount {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> OptionsAccount {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> OptionsAccount {
+        OptionsAccount {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.options_rest_api_endpoint.clone(),
+                config,
+            ),
+            recv_window: config.recv_window,
+        }
+    }
+}
+
+impl Binance for OptionsUserStream {
+    fn new(api_key: Option<String>, secret_key: Option<String>) -> OptionsUserStream {
+        Self::new_with_config(api_key, secret_key, &Config::default())
+    }
+
+    fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> OptionsUserStream {
+        OptionsUserStream {
+            client: Client::new_with_config(
+                api_key,
+                secret_key,
+                config.options_rest_api_endpoint.clone(),
+                config,
             ),
             recv_window: config.recv_window,
         }
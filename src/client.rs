@@ -3,33 +3,71 @@ use hex::encode as hex_encode;
 use hmac::{Hmac, Mac, NewMac};
 use crate::errors::*;
 use reqwest::StatusCode;
-use reqwest::blocking::Response;
+use reqwest::Method;
+use reqwest::blocking::{RequestBuilder, Response};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE};
 use sha2::Sha256;
 use serde::de::DeserializeOwned;
 use crate::api::API;
+use crate::config::Config;
 
 #[derive(Clone)]
 pub struct Client {
     api_key: String,
     secret_key: String,
     host: String,
+    user_agent: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
     inner_client: reqwest::blocking::Client,
 }
 
 impl Client {
     pub fn new(api_key: Option<String>, secret_key: Option<String>, host: String) -> Self {
+        Self::new_with_config(api_key, secret_key, host, &Config::default())
+    }
+
+    pub fn new_with_config(
+        api_key: Option<String>, secret_key: Option<String>, host: String, config: &Config,
+    ) -> Self {
+        let mut builder = reqwest::blocking::Client::builder()
+            .pool_idle_timeout(config.pool_idle_timeout)
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .tcp_nodelay(config.tcp_nodelay)
+            .tcp_keepalive(config.tcp_keepalive)
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout);
+
+        if let Some(ref proxy) = config.proxy {
+            let proxy = proxy.clone();
+            let proxy_url = proxy.to_url();
+            builder = builder.proxy(reqwest::Proxy::custom(move |url| {
+                match url.host_str() {
+                    Some(host) if proxy.bypasses(host) => None,
+                    _ => Some(proxy_url.clone()),
+                }
+            }));
+        }
+
         Client {
             api_key: api_key.unwrap_or_else(|| "".into()),
             secret_key: secret_key.unwrap_or_else(|| "".into()),
             host,
-            inner_client: reqwest::blocking::Client::builder()
-                .pool_idle_timeout(Some(Duration::from_secs(30)))  // Разумный таймаут для бездействующих соединений
-                .tcp_keepalive(Some(Duration::from_secs(5)))  // Уменьшенный keepalive для быстрой проверки активности соединения
-                .pool_max_idle_per_host(20)  // Увеличенный пул соединений для уменьшения времени открытия новых соединений
-                .timeout(Duration::from_millis(1000))  // Короткий таймаут для запроса (можно настроить на основании тестирования)
-                .build()
-                .unwrap(),
+            user_agent: config.user_agent.clone(),
+            headers: config.headers.clone(),
+            timeout: None,
+            inner_client: builder.build().unwrap(),
+        }
+    }
+
+    /// Same client, with `timeout` instead of the configured timeout for its requests.
+    ///
+    /// Useful for large responses like `exchangeInfo` or `allOrders`:
+    /// `general.client = general.client.with_timeout(Duration::from_secs(10))`.
+    pub fn with_timeout(&self, timeout: Duration) -> Client {
+        Client {
+            timeout: Some(timeout),
+            ..self.clone()
         }
     }
 
@@ -37,9 +75,8 @@ impl Client {
         &self, endpoint: API, request: Option<String>,
     ) -> Result<T> {
         let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .get(url.as_str())
+        let response = self
+            .request(Method::GET, url.as_str())
             .headers(self.build_headers(true)?)
             .send()?;
 
@@ -48,9 +85,8 @@ impl Client {
 
     pub fn post_signed<T: DeserializeOwned>(&self, endpoint: API, request: String) -> Result<T> {
         let url = self.sign_request(endpoint, Some(request));
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
+        let response = self
+            .request(Method::POST, url.as_str())
             .headers(self.build_headers(true)?)
             .send()?;
 
@@ -60,9 +96,8 @@ impl Client {
 
     pub fn put_signed<T: DeserializeOwned>(&self, endpoint: API, request: String) -> Result<T> {
         let url = self.sign_request(endpoint, Some(request));
-        let client = &self.inner_client;
-        let response = client
-            .put(url.as_str())
+        let response = self
+            .request(Method::PUT, url.as_str())
             .headers(self.build_headers(true)?)
             .send()?;
 
@@ -73,9 +108,8 @@ impl Client {
         &self, endpoint: API, request: Option<String>,
     ) -> Result<T> {
         let url = self.sign_request(endpoint, request);
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
+        let response = self
+            .request(Method::DELETE, url.as_str())
             .headers(self.build_headers(true)?)
             .send()?;
 
@@ -90,17 +124,18 @@ impl Client {
             }
         }
 
-        let client = &self.inner_client;
-        let response = client.get(url.as_str()).send()?;
+        let response = self
+            .request(Method::GET, url.as_str())
+            .headers(self.default_headers()?)
+            .send()?;
 
         self.handler(response)
     }
 
     pub fn post<T: DeserializeOwned>(&self, endpoint: API) -> Result<T> {
         let url: String = format!("{}{}", self.host, String::from(endpoint));
-        let client = &self.inner_client;
-        let response = client
-            .post(url.as_str())
+        let response = self
+            .request(Method::POST, url.as_str())
             .headers(self.build_headers(false)?)
             .send()?;
 
@@ -115,9 +150,8 @@ impl Client {
             url = format!("{url}?{data}");
         }
 
-        let client = &self.inner_client;
-        let response = client
-            .put(url)
+        let response = self
+            .request(Method::PUT, url.as_str())
             .headers(self.build_headers(false)?)
             .body(data)
             .send()?;
@@ -133,9 +167,8 @@ impl Client {
             url = format!("{url}?{data}");
         }
 
-        let client = &self.inner_client;
-        let response = client
-            .delete(url.as_str())
+        let response = self
+            .request(Method::DELETE, url.as_str())
             .headers(self.build_headers(false)?)
             .body(data)
             .send()?;
@@ -164,10 +197,32 @@ impl Client {
         }
     }
 
-    fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
+    fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let request = self.inner_client.request(method, url);
+        match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        }
+    }
+
+    // User agent and the extra headers of the `Config`
+    fn default_headers(&self) -> Result<HeaderMap> {
         let mut custom_headers = HeaderMap::new();
 
-        custom_headers.insert(USER_AGENT, HeaderValue::from_static("binance-rs"));
+        custom_headers.insert(USER_AGENT, HeaderValue::from_str(&self.user_agent)?);
+        for (name, value) in &self.headers {
+            custom_headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        Ok(custom_headers)
+    }
+
+    fn build_headers(&self, content_type: bool) -> Result<HeaderMap> {
+        let mut custom_headers = self.default_headers()?;
+
         if content_type {
             custom_headers.insert(
                 CONTENT_TYPE,
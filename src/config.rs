@@ -1,15 +1,38 @@
+use std::time::Duration;
+
+use crate::proxy::Proxy;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     pub rest_api_endpoint: String,
     pub ws_endpoint: String,
+    pub ws_api_endpoint: String,
 
     pub futures_rest_api_endpoint: String,
     pub futures_ws_endpoint: String,
 
+    pub delivery_rest_api_endpoint: String,
+
+    pub options_rest_api_endpoint: String,
+    pub options_ws_endpoint: String,
+
     pub margin: bool,
     pub isolated: bool,
 
     pub recv_window: u64,
+
+    pub proxy: Option<Proxy>,
+
+    /// Total time allowed for one REST request, `None` waits forever.
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: usize,
+    pub tcp_nodelay: bool,
+    pub tcp_keepalive: Option<Duration>,
+    pub user_agent: String,
+    /// Extra headers sent with every REST request.
+    pub headers: Vec<(String, String)>,
 }
 
 impl Config {
@@ -17,13 +40,30 @@ impl Config {
         Config {
             rest_api_endpoint: "https://api.binance.com".into(),
             ws_endpoint: "wss://stream.binance.com:9443/ws".into(),
+            ws_api_endpoint: "wss://ws-api.binance.com:443/ws-api/v3".into(),
 
             futures_rest_api_endpoint: "https://fapi.binance.com".into(),
             futures_ws_endpoint: "wss://fstream.binance.com/ws".into(),
 
+            delivery_rest_api_endpoint: "https://dapi.binance.com".into(),
+
+            options_rest_api_endpoint: "https://eapi.binance.com".into(),
+            options_ws_endpoint: "wss://nbstream.binance.com/eoptions".into(),
+
             margin: false,
             isolated: false,
             recv_window: 5000,
+
+            proxy: None,
+
+            timeout: Some(Duration::from_millis(1000)),
+            connect_timeout: None,
+            pool_idle_timeout: Some(Duration::from_secs(30)),
+            pool_max_idle_per_host: 20,
+            tcp_nodelay: true,
+            tcp_keepalive: Some(Duration::from_secs(5)),
+            user_agent: "binance-rs".into(),
+            headers: Vec::new(),
         }
     }
 
@@ -31,8 +71,10 @@ impl Config {
         Config::default()
             .set_rest_api_endpoint("https://testnet.binance.vision")
             .set_ws_endpoint("wss://testnet.binance.vision/ws")
+            .set_ws_api_endpoint("wss://testnet.binance.vision/ws-api/v3")
             .set_futures_rest_api_endpoint("https://testnet.binancefuture.com")
             .set_futures_ws_endpoint("https://testnet.binancefuture.com/ws")
+            .set_delivery_rest_api_endpoint("https://testnet.binancefuture.com")
     }
 
     pub fn set_rest_api_endpoint<T: Into<String>>(mut self, rest_api_endpoint: T) -> Self {
@@ -44,6 +86,11 @@ impl Config {
         self.ws_endpoint = ws_endpoint.into();
         self
     }
+    pub fn set_ws_api_endpoint<T: Into<String>>(mut self, ws_api_endpoint: T) -> Self {
+        self.ws_api_endpoint = ws_api_endpoint.into();
+        self
+    }
+
     pub fn set_futures_rest_api_endpoint<T: Into<String>>(
         mut self, futures_rest_api_endpoint: T,
     ) -> Self {
@@ -56,11 +103,76 @@ impl Config {
         self
     }
 
+    pub fn set_delivery_rest_api_endpoint<T: Into<String>>(
+        mut self, delivery_rest_api_endpoint: T,
+    ) -> Self {
+        self.delivery_rest_api_endpoint = delivery_rest_api_endpoint.into();
+        self
+    }
+
+    pub fn set_options_rest_api_endpoint<T: Into<String>>(
+        mut self, options_rest_api_endpoint: T,
+    ) -> Self {
+        self.options_rest_api_endpoint = options_rest_api_endpoint.into();
+        self
+    }
+
+    pub fn set_options_ws_endpoint<T: Into<String>>(mut self, options_ws_endpoint: T) -> Self {
+        self.options_ws_endpoint = options_ws_endpoint.into();
+        self
+    }
+
     pub fn set_recv_window(mut self, recv_window: u64) -> Self {
         self.recv_window = recv_window;
         self
     }
 
+    /// Route REST requests and websocket connections through `proxy`.
+    pub fn set_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn set_timeout<T: Into<Option<Duration>>>(mut self, timeout: T) -> Self {
+        self.timeout = timeout.into();
+        self
+    }
+
+    pub fn set_connect_timeout<T: Into<Option<Duration>>>(mut self, connect_timeout: T) -> Self {
+        self.connect_timeout = connect_timeout.into();
+        self
+    }
+
+    pub fn set_pool_idle_timeout<T: Into<Option<Duration>>>(mut self, pool_idle_timeout: T) -> Self {
+        self.pool_idle_timeout = pool_idle_timeout.into();
+        self
+    }
+
+    pub fn set_pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = pool_max_idle_per_host;
+        self
+    }
+
+    pub fn set_tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    pub fn set_tcp_keepalive<T: Into<Option<Duration>>>(mut self, tcp_keepalive: T) -> Self {
+        self.tcp_keepalive = tcp_keepalive.into();
+        self
+    }
+
+    pub fn set_user_agent<T: Into<String>>(mut self, user_agent: T) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn add_header<K: Into<String>, V: Into<String>>(mut self, name: K, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
     pub fn set_margin(mut self, margin: bool) -> Self{
         self.margin = margin;
         self
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+
+use crate::util::*;
+use crate::errors::*;
+use crate::client::Client;
+use crate::api::{API, Delivery};
+use crate::model::Empty;
+use crate::account::{OrderSide, TimeInForce};
+use crate::futures::account::{build_order, CustomOrderRequest, OrderRequest, OrderType};
+use super::model::{
+    AccountBalance, AccountInformation, ChangeLeverageResponse, Order, PositionRisk, TradeHistory,
+};
+
+/// COIN-M (delivery) account and orders, quantities are in contracts.
+#[derive(Clone)]
+pub struct DeliveryAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl DeliveryAccount {
+    pub fn limit_buy(
+        &self, symbol: impl Into<String>, contracts: impl Into<f64>, price: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<Order> {
+        self.place(simple_order(
+            symbol.into(), OrderSide::Buy, OrderType::Limit, Some(time_in_force),
+            contracts.into(), Some(price),
+        ))
+    }
+
+    pub fn limit_sell(
+        &self, symbol: impl Into<String>, contracts: impl Into<f64>, price: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<Order> {
+        self.place(simple_order(
+            symbol.into(), OrderSide::Sell, OrderType::Limit, Some(time_in_force),
+            contracts.into(), Some(price),
+        ))
+    }
+
+    // Place a MARKET order - BUY
+    pub fn market_buy<S, F>(&self, symbol: S, contracts: F) -> Result<Order>
+    where
+        S: Into<String>,
+        F: Into<f64>,
+    {
+        self.place(simple_order(
+            symbol.into(), OrderSide::Buy, OrderType::Market, None, contracts.into(), None,
+        ))
+    }
+
+    // Place a MARKET order - SELL
+    pub fn market_sell<S, F>(&self, symbol: S, contracts: F) -> Result<Order>
+    where
+        S: Into<String>,
+        F: Into<f64>,
+    {
+        self.place(simple_order(
+            symbol.into(), OrderSide::Sell, OrderType::Market, None, contracts.into(), None,
+        ))
+    }
+
+    // Custom order for for professional traders, `qty` is in contracts
+    pub fn custom_order(&self, order_request: CustomOrderRequest) -> Result<Order> {
+        self.place(order_request.into())
+    }
+
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Delivery(Delivery::Order), Some(request))
+    }
+
+    pub fn cancel_order_with_client_id<S>(
+        &self, symbol: S, orig_client_order_id: String,
+    ) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Delivery(Delivery::Order), Some(request))
+    }
+
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed::<Empty>(API::Delivery(Delivery::AllOpenOrders), Some(request))
+            .map(|_| ())
+    }
+
+    pub fn get_all_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::OpenOrders), Some(request))
+    }
+
+    pub fn get_all_orders<S>(&self, symbol: S, limit: usize) -> Result<Vec<Order>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), limit.to_string());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::AllOrders), Some(request))
+    }
+
+    pub fn get_order_status<S>(&self, symbol: S, client_order_id: S) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), client_order_id.into());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::Order), Some(request))
+    }
+
+    // Positions of every symbol of ONE pair ("BTCUSD")
+    pub fn position_information<S>(&self, pair: S) -> Result<Vec<PositionRisk>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::PositionRisk), Some(request))
+    }
+
+    pub fn account_information(&self) -> Result<AccountInformation> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::Account), Some(request))
+    }
+
+    pub fn account_balance(&self) -> Result<Vec<AccountBalance>> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::Balance), Some(request))
+    }
+
+    pub fn change_initial_leverage<S>(
+        &self, symbol: S, leverage: u8,
+    ) -> Result<ChangeLeverageResponse>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("leverage".into(), leverage.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Delivery(Delivery::ChangeInitialLeverage), request)
+    }
+
+    pub fn change_position_mode(&self, dual_side_position: bool) -> Result<()> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let dual_side = if dual_side_position { "true" } else { "false" };
+        parameters.insert("dualSidePosition".into(), dual_side.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed::<Empty>(API::Delivery(Delivery::PositionSide), request)
+            .map(|_| ())
+    }
+
+    // Trade history
+    pub fn trade_history<S>(&self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::UserTrades), Some(request))
+    }
+
+    fn place(&self, order: OrderRequest) -> Result<Order> {
+        let order = build_order(order);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Delivery(Delivery::Order), request)
+    }
+}
+
+fn simple_order(
+    symbol: String, side: OrderSide, order_type: OrderType, time_in_force: Option<TimeInForce>,
+    contracts: f64, price: Option<f64>,
+) -> OrderRequest {
+    OrderRequest {
+        symbol,
+        side,
+        position_side: None,
+        order_type,
+        time_in_force,
+        qty: Some(contracts),
+        reduce_only: None,
+        price,
+        stop_price: None,
+        close_position: None,
+        activation_price: None,
+        callback_rate: None,
+        working_type: None,
+        price_protect: None,
+        order_id: None,
+    }
+}
@@ -0,0 +1,48 @@
+use crate::delivery::model::*;
+use crate::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Delivery;
+
+#[derive(Clone)]
+pub struct DeliveryGeneral {
+    pub client: Client,
+}
+
+impl DeliveryGeneral {
+    // Test connectivity
+    pub fn ping(&self) -> Result<String> {
+        self.client.get::<crate::model::Empty>(API::Delivery(Delivery::Ping), None)?;
+        Ok("pong".into())
+    }
+
+    // Check server time
+    pub fn get_server_time(&self) -> Result<ServerTime> {
+        self.client.get(API::Delivery(Delivery::Time), None)
+    }
+
+    // Obtain exchange information
+    // - Current exchange trading rules and contract information
+    pub fn exchange_info(&self) -> Result<ExchangeInformation> {
+        self.client.get(API::Delivery(Delivery::ExchangeInfo), None)
+    }
+
+    // Get Symbol information
+    pub fn get_symbol_info<S>(&self, symbol: S) -> Result<Symbol>
+    where
+        S: Into<String>,
+    {
+        let upper_symbol = symbol.into().to_uppercase();
+        match self.exchange_info() {
+            Ok(info) => {
+                for item in info.symbols {
+                    if item.symbol == upper_symbol {
+                        return Ok(item);
+                    }
+                }
+                bail!("Symbol not found")
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+/*!
+COIN-M (delivery) market data, quantities are in contracts.
+*/
+
+use crate::util::*;
+use crate::delivery::model::*;
+use crate::client::*;
+use crate::errors::*;
+use std::collections::BTreeMap;
+use serde_json::Value;
+use crate::api::API;
+use crate::api::Delivery;
+use std::convert::TryInto;
+
+#[derive(Clone)]
+pub struct DeliveryMarket {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl DeliveryMarket {
+    // Order book (Default 500; max 1000)
+    pub fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client.get(API::Delivery(Delivery::Depth), Some(request))
+    }
+
+    // Order book at a custom depth. Currently supported values
+    // are 5, 10, 20, 50, 100, 500, 1000
+    pub fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), depth.to_string());
+        let request = build_request(parameters);
+        self.client.get(API::Delivery(Delivery::Depth), Some(request))
+    }
+
+    pub fn get_trades<S>(&self, symbol: S) -> Result<Trades>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::Trades), Some(request))
+    }
+
+    pub fn get_historical_trades<S1, S2, S3>(
+        &self, symbol: S1, from_id: S2, limit: S3,
+    ) -> Result<Trades>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Delivery(Delivery::HistoricalTrades), Some(request))
+    }
+
+    pub fn get_agg_trades<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, from_id: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<AggTrades>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(fi) = from_id.into() {
+            parameters.insert("fromId".into(), format!("{}", fi));
+        }
+
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::AggTrades), Some(request))
+    }
+
+    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    // The `quote_asset_volume` of a COIN-M kline is the base asset volume
+    pub fn get_klines<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.klines(Delivery::Klines, parameters, interval, limit, start_time, end_time)
+    }
+
+    // Klines of a contract type ("PERPETUAL", "CURRENT_QUARTER", "NEXT_QUARTER") of a pair
+    pub fn get_continuous_klines<S1, S2, S3, S4, S5, S6>(
+        &self, pair: S1, contract_type: S2, interval: S3, limit: S4, start_time: S5,
+        end_time: S6,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+        S4: Into<Option<u16>>,
+        S5: Into<Option<u64>>,
+        S6: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+        parameters.insert("contractType".into(), contract_type.into());
+        self.klines(Delivery::ContinuousKlines, parameters, interval, limit, start_time, end_time)
+    }
+
+    pub fn get_index_price_klines<S1, S2, S3, S4, S5>(
+        &self, pair: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+        self.klines(Delivery::IndexPriceKlines, parameters, interval, limit, start_time, end_time)
+    }
+
+    pub fn get_mark_price_klines<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.klines(Delivery::MarkPriceKlines, parameters, interval, limit, start_time, end_time)
+    }
+
+    // 24hr ticker price change statistics, for ONE symbol or every symbol of a pair
+    pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<Vec<PriceStats>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::Ticker24hr), Some(request))
+    }
+
+    // 24hr ticker price change statistics for all symbols
+    pub fn get_all_24h_price_stats(&self) -> Result<Vec<PriceStats>> {
+        self.client.get(API::Delivery(Delivery::Ticker24hr), None)
+    }
+
+    // Latest price for ONE symbol
+    pub fn get_price<S>(&self, symbol: S) -> Result<Vec<SymbolPrice>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::TickerPrice), Some(request))
+    }
+
+    // Latest price for all symbols
+    pub fn get_all_prices(&self) -> Result<Vec<SymbolPrice>> {
+        self.client.get(API::Delivery(Delivery::TickerPrice), None)
+    }
+
+    // -> Best price/qty on the order book for ALL symbols
+    pub fn get_all_book_tickers(&self) -> Result<BookTickers> {
+        self.client.get(API::Delivery(Delivery::BookTicker), None)
+    }
+
+    // -> Best price/qty on the order book for ONE symbol
+    pub fn get_book_ticker<S>(&self, symbol: S) -> Result<BookTickers>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::BookTicker), Some(request))
+    }
+
+    // Mark and index price of every symbol
+    pub fn get_mark_prices(&self) -> Result<MarkPrices> {
+        self.client.get(API::Delivery(Delivery::PremiumIndex), None)
+    }
+
+    // Mark and index price of the symbols of ONE pair ("BTCUSD")
+    pub fn get_pair_mark_prices<S>(&self, pair: S) -> Result<MarkPrices>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::PremiumIndex), Some(request))
+    }
+
+    pub fn get_funding_rate<S1, S2, S3, S4>(
+        &self, symbol: S1, limit: S2, start_time: S3, end_time: S4,
+    ) -> Result<Vec<FundingRate>>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::FundingRate), Some(request))
+    }
+
+    pub fn open_interest<S>(&self, symbol: S) -> Result<OpenInterest>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Delivery(Delivery::OpenInterest), Some(request))
+    }
+
+    fn klines<S1, S2, S3, S4>(
+        &self, route: Delivery, mut parameters: BTreeMap<String, String>, interval: S1,
+        limit: S2, start_time: S3, end_time: S4,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        parameters.insert("interval".into(), interval.into());
+
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+
+        let request = build_request(parameters);
+        let data: Vec<Vec<Value>> = self.client.get(API::Delivery(route), Some(request))?;
+
+        let klines = KlineSummaries::AllKlineSummaries(
+            data.iter()
+                .map(|row| row.try_into())
+                .collect::<Result<Vec<KlineSummary>>>()?,
+        );
+
+        Ok(klines)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod account;
+pub mod general;
+pub mod market;
+pub mod model;
+pub mod userstream;
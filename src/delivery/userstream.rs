@@ -0,0 +1,28 @@
+use crate::model::*;
+use crate::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Delivery;
+
+#[derive(Clone)]
+pub struct DeliveryUserStream {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl DeliveryUserStream {
+    // User Stream
+    pub fn start(&self) -> Result<UserDataStream> {
+        self.client.post(API::Delivery(Delivery::UserDataStream))
+    }
+
+    pub fn keep_alive(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .put(API::Delivery(Delivery::UserDataStream), listen_key)
+    }
+
+    pub fn close(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .delete(API::Delivery(Delivery::UserDataStream), listen_key)
+    }
+}
@@ -3,7 +3,8 @@ use std::net::TcpStream;
 use serde::Deserialize;
 use crate::errors;
 use tungstenite::ClientHandshake;
-use native_tls::TlsStream;
+use tungstenite::stream::MaybeTlsStream;
+use crate::filters::FilterViolation;
 use crate::model::CancelReplace;
 
 
@@ -32,6 +33,11 @@ error_chain! {
 
         BinanceError(response: BinanceContentError)
 
+        FilterViolation(violation: FilterViolation) {
+            description("order breaks a symbol filter"),
+            display("Order breaks {}", violation),
+        }
+
         KlineValueMissingError(index: usize, name: &'static str) {
             description("invalid Vec for Kline"),
             display("{} at {} is missing", name, index),
@@ -45,7 +51,7 @@ error_chain! {
             description("TLS handshake error"),
             display("TLS handshake error: {}", error),
         }
-         WebSocketHandshakeError(error: tungstenite::HandshakeError<ClientHandshake<TlsStream<TcpStream>>>) {
+         WebSocketHandshakeError(error: tungstenite::HandshakeError<ClientHandshake<MaybeTlsStream<TcpStream>>>) {
             description("WebSocket handshake error"),
             display("WebSocket handshake error: {}", error),
         }
@@ -54,6 +60,7 @@ error_chain! {
     foreign_links {
         ReqError(reqwest::Error);
         InvalidHeaderError(reqwest::header::InvalidHeaderValue);
+        InvalidHeaderNameError(reqwest::header::InvalidHeaderName);
         IoError(std::io::Error);
         ParseFloatError(std::num::ParseFloatError);
         UrlParserError(url::ParseError);
@@ -75,8 +82,8 @@ impl From<native_tls::HandshakeError<TcpStream>> for errors::Error {
     }
 }
 
-impl From<tungstenite::HandshakeError<tungstenite::ClientHandshake<native_tls::TlsStream<std::net::TcpStream>>>> for errors::Error {
-    fn from(err: tungstenite::HandshakeError<tungstenite::ClientHandshake<native_tls::TlsStream<std::net::TcpStream>>>) -> errors::Error {
+impl From<tungstenite::HandshakeError<tungstenite::ClientHandshake<MaybeTlsStream<std::net::TcpStream>>>> for errors::Error {
+    fn from(err: tungstenite::HandshakeError<tungstenite::ClientHandshake<MaybeTlsStream<std::net::TcpStream>>>) -> errors::Error {
         errors::Error::from(errors::ErrorKind::WebSocketHandshakeError(err))
     }
 }
@@ -88,4 +95,11 @@ impl Error {
             _ => None,
         }
     }
+
+    pub fn filter_violation(&self) -> Option<&FilterViolation> {
+        match &self.0 {
+            ErrorKind::FilterViolation(violation) => Some(violation),
+            _ => None,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,702 @@
+/*!
+Pre-trade checks of spot orders against the exchange filters of their symbol.
+
+`SymbolFilters` reads the filters of a `Symbol` from `exchangeInfo` once, then
+reports which filter an order breaks before it is sent instead of learning it
+from a `-1013` error, and rounds prices and quantities to the tick and step
+size of the symbol. Percent price and market notional checks need a reference
+price, usually the average price of the symbol (`Market::get_average_price`).
+The checks and the rounding are done on `Number`, exact with the `decimal` feature.
+*/
+
+use std::fmt;
+
+use crate::account::{NewOrder, OrderSide, OrderType};
+use crate::errors::*;
+use crate::model::string_or_float::FromStringOrFloat;
+use crate::model::{number_to_f64, Filters, Number, Symbol};
+
+/// Which side of a filter range a value is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    BelowMin,
+    AboveMax,
+    /// Not a multiple of the tick or step size
+    NotOnStep,
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bound::BelowMin => write!(f, "below the minimum"),
+            Bound::AboveMax => write!(f, "above the maximum"),
+            Bound::NotOnStep => write!(f, "not a multiple of the step"),
+        }
+    }
+}
+
+/// Filter broken by an order, `limit` is the minimum, maximum or step the value broke.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterViolation {
+    /// PRICE_FILTER, `parameter` is "price" or "stopPrice"
+    Price {
+        parameter: &'static str,
+        value: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    LotSize {
+        value: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    MarketLotSize {
+        value: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    MinNotional {
+        notional: Number,
+        limit: Number,
+    },
+    Notional {
+        notional: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    PercentPrice {
+        price: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    PercentPriceBySide {
+        price: Number,
+        bound: Bound,
+        limit: Number,
+    },
+    IcebergParts {
+        parts: u64,
+        limit: u64,
+    },
+    /// TRAILING_DELTA, in basis points
+    TrailingDelta {
+        delta: u64,
+        bound: Bound,
+        limit: u64,
+    },
+}
+
+impl FilterViolation {
+    /// Name of the filter in `exchangeInfo`.
+    pub fn filter_type(&self) -> &'static str {
+        match self {
+            FilterViolation::Price { .. } => "PRICE_FILTER",
+            FilterViolation::LotSize { .. } => "LOT_SIZE",
+            FilterViolation::MarketLotSize { .. } => "MARKET_LOT_SIZE",
+            FilterViolation::MinNotional { .. } => "MIN_NOTIONAL",
+            FilterViolation::Notional { .. } => "NOTIONAL",
+            FilterViolation::PercentPrice { .. } => "PERCENT_PRICE",
+            FilterViolation::PercentPriceBySide { .. } => "PERCENT_PRICE_BY_SIDE",
+            FilterViolation::IcebergParts { .. } => "ICEBERG_PARTS",
+            FilterViolation::TrailingDelta { .. } => "TRAILING_DELTA",
+        }
+    }
+}
+
+impl fmt::Display for FilterViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterViolation::Price {
+                parameter,
+                value,
+                bound,
+                limit,
+            } => write!(
+                f,
+                "{}: {} {} is {} {}",
+                self.filter_type(),
+                parameter,
+                value,
+                bound,
+                limit
+            ),
+            FilterViolation::LotSize {
+                value,
+                bound,
+                limit,
+            }
+            | FilterViolation::MarketLotSize {
+                value,
+                bound,
+                limit,
+            } => write!(
+                f,
+                "{}: quantity {} is {} {}",
+                self.filter_type(),
+                value,
+                bound,
+                limit
+            ),
+            FilterViolation::MinNotional { notional, limit } => write!(
+                f,
+                "{}: notional {} is {} {}",
+                self.filter_type(),
+                notional,
+                Bound::BelowMin,
+                limit
+            ),
+            FilterViolation::Notional {
+                notional,
+                bound,
+                limit,
+            } => write!(
+                f,
+                "{}: notional {} is {} {}",
+                self.filter_type(),
+                notional,
+                bound,
+                limit
+            ),
+            FilterViolation::PercentPrice {
+                price,
+                bound,
+                limit,
+            }
+            | FilterViolation::PercentPriceBySide {
+                price,
+                bound,
+                limit,
+            } => write!(
+                f,
+                "{}: price {} is {} {}",
+                self.filter_type(),
+                price,
+                bound,
+                limit
+            ),
+            FilterViolation::IcebergParts { parts, limit } => write!(
+                f,
+                "{}: {} iceberg parts, at most {} allowed",
+                self.filter_type(),
+                parts,
+                limit
+            ),
+            FilterViolation::TrailingDelta {
+                delta,
+                bound,
+                limit,
+            } => write!(
+                f,
+                "{}: trailing delta {} is {} {}",
+                self.filter_type(),
+                delta,
+                bound,
+                limit
+            ),
+        }
+    }
+}
+
+// Range and step of a price or quantity, 0 disables a bound
+#[derive(Debug, Clone)]
+struct Range {
+    min: Number,
+    max: Number,
+    step: Number,
+    // Decimals of the step, None when there is no step
+    precision: Option<usize>,
+}
+
+impl Range {
+    fn new(min: &str, max: &str, step: &str) -> Result<Self> {
+        let step_value = parse_number(step)?;
+        Ok(Range {
+            min: parse_number(min)?,
+            max: parse_number(max)?,
+            step: step_value,
+            precision: if is_set(step_value) {
+                Some(decimals(step))
+            } else {
+                None
+            },
+        })
+    }
+
+    fn check(&self, value: Number) -> Option<(Bound, Number)> {
+        if is_set(self.min) && value < self.min {
+            return Some((Bound::BelowMin, self.min));
+        }
+        if is_set(self.max) && value > self.max {
+            return Some((Bound::AboveMax, self.max));
+        }
+        if is_set(self.step) {
+            let steps = (value - self.min) / self.step;
+            if (steps - round_half_away(steps)).abs() > STEP_TOLERANCE {
+                return Some((Bound::NotOnStep, self.step));
+            }
+        }
+        None
+    }
+
+    fn round(&self, value: Number) -> Number {
+        if !is_set(self.step) {
+            return value;
+        }
+        self.clean(round_half_away(value / self.step) * self.step)
+    }
+
+    fn floor(&self, value: Number) -> Number {
+        if !is_set(self.step) {
+            return value;
+        }
+        self.clean((value / self.step + STEP_TOLERANCE).floor() * self.step)
+    }
+
+    fn format(&self, value: Number) -> String {
+        match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        }
+    }
+
+    // Drops the float noise of the step multiplication (0.1 * 3 = 0.30000000000000004)
+    fn clean(&self, value: Number) -> Number {
+        self.format(value).parse().unwrap_or(value)
+    }
+}
+
+// Float division leaves ratios like 2.9999999999999996 on exact steps
+#[cfg(not(feature = "decimal"))]
+const STEP_TOLERANCE: Number = 1e-8;
+#[cfg(feature = "decimal")]
+const STEP_TOLERANCE: Number = Number::ZERO;
+
+// Half away from zero like `f64::round`, `Decimal::round` rounds half to even
+#[cfg(not(feature = "decimal"))]
+fn round_half_away(value: Number) -> Number {
+    value.round()
+}
+
+#[cfg(feature = "decimal")]
+fn round_half_away(value: Number) -> Number {
+    value.round_dp_with_strategy(0, rust_decimal::RoundingStrategy::MidpointAwayFromZero)
+}
+
+// 0 disables a bound or a step
+fn is_set(value: Number) -> bool {
+    value > Number::default()
+}
+
+fn parse_number(value: &str) -> Result<Number> {
+    Ok(Number::from_string(value)?)
+}
+
+// Significant decimals of "0.00100000" is 3
+fn decimals(step: &str) -> usize {
+    match step.split_once('.') {
+        Some((_, fraction)) => fraction.trim_end_matches('0').len(),
+        None => 0,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Multipliers {
+    up: Number,
+    down: Number,
+}
+
+#[derive(Debug, Clone)]
+struct Notional {
+    min: Number,
+    apply_min_to_market: bool,
+    max: Number,
+    apply_max_to_market: bool,
+}
+
+#[derive(Debug, Clone)]
+struct TrailingDelta {
+    min_above: u64,
+    max_above: u64,
+    min_below: u64,
+    max_below: u64,
+}
+
+/// Filters of one spot symbol, parsed from `exchangeInfo`.
+#[derive(Debug, Clone)]
+pub struct SymbolFilters {
+    pub symbol: String,
+    price: Option<Range>,
+    lot_size: Option<Range>,
+    market_lot_size: Option<Range>,
+    min_notional: Option<Notional>,
+    notional: Option<Notional>,
+    percent_price: Option<Multipliers>,
+    // Bid then ask multipliers
+    percent_price_by_side: Option<(Multipliers, Multipliers)>,
+    iceberg_parts: Option<u64>,
+    trailing_delta: Option<TrailingDelta>,
+}
+
+impl SymbolFilters {
+    pub fn new(symbol: &Symbol) -> Result<Self> {
+        let mut filters = SymbolFilters {
+            symbol: symbol.symbol.clone(),
+            price: None,
+            lot_size: None,
+            market_lot_size: None,
+            min_notional: None,
+            notional: None,
+            percent_price: None,
+            percent_price_by_side: None,
+            iceberg_parts: None,
+            trailing_delta: None,
+        };
+
+        for filter in &symbol.filters {
+            match filter {
+                Filters::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => filters.price = Some(Range::new(min_price, max_price, tick_size)?),
+                Filters::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => filters.lot_size = Some(Range::new(min_qty, max_qty, step_size)?),
+                Filters::MarketLotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => filters.market_lot_size = Some(Range::new(min_qty, max_qty, step_size)?),
+                Filters::MinNotional {
+                    min_notional,
+                    apply_to_market,
+                    ..
+                } => {
+                    filters.min_notional = Some(Notional {
+                        min: parse_optional(min_notional)?,
+                        apply_min_to_market: apply_to_market.unwrap_or(true),
+                        max: Number::default(),
+                        apply_max_to_market: false,
+                    })
+                }
+                Filters::Notional {
+                    min_notional,
+                    apply_min_to_market,
+                    max_notional,
+                    apply_max_to_market,
+                    ..
+                } => {
+                    filters.notional = Some(Notional {
+                        min: parse_optional(min_notional)?,
+                        apply_min_to_market: apply_min_to_market.unwrap_or(true),
+                        max: parse_optional(max_notional)?,
+                        apply_max_to_market: apply_max_to_market.unwrap_or(false),
+                    })
+                }
+                Filters::PercentPrice {
+                    multiplier_up,
+                    multiplier_down,
+                    ..
+                } => {
+                    filters.percent_price = Some(Multipliers {
+                        up: parse_number(multiplier_up)?,
+                        down: parse_number(multiplier_down)?,
+                    })
+                }
+                Filters::PercentPriceBySide {
+                    bid_multiplier_up,
+                    bid_multiplier_down,
+                    ask_multiplier_up,
+                    ask_multiplier_down,
+                    ..
+                } => {
+                    filters.percent_price_by_side = Some((
+                        Multipliers {
+                            up: parse_number(bid_multiplier_up)?,
+                            down: parse_number(bid_multiplier_down)?,
+                        },
+                        Multipliers {
+                            up: parse_number(ask_multiplier_up)?,
+                            down: parse_number(ask_multiplier_down)?,
+                        },
+                    ))
+                }
+                Filters::IcebergParts { limit } => filters.iceberg_parts = limit.map(u64::from),
+                Filters::TrailingData {
+                    min_trailing_above_delta,
+                    max_trailing_above_delta,
+                    min_trailing_below_delta,
+                    max_trailing_below_delta,
+                } => {
+                    filters.trailing_delta = Some(TrailingDelta {
+                        min_above: min_trailing_above_delta.unwrap_or(0).into(),
+                        max_above: max_trailing_above_delta.unwrap_or(0).into(),
+                        min_below: min_trailing_below_delta.unwrap_or(0).into(),
+                        max_below: max_trailing_below_delta.unwrap_or(0).into(),
+                    })
+                }
+                _ => (),
+            }
+        }
+
+        Ok(filters)
+    }
+
+    pub fn tick_size(&self) -> Option<Number> {
+        self.price.as_ref().map(|price| price.step)
+    }
+
+    pub fn step_size(&self) -> Option<Number> {
+        self.lot_size.as_ref().map(|lot_size| lot_size.step)
+    }
+
+    /// Decimals of the tick size.
+    pub fn price_precision(&self) -> Option<usize> {
+        self.price.as_ref().and_then(|price| price.precision)
+    }
+
+    /// Decimals of the LOT_SIZE step.
+    pub fn quantity_precision(&self) -> Option<usize> {
+        self.lot_size
+            .as_ref()
+            .and_then(|lot_size| lot_size.precision)
+    }
+
+    /// Price rounded to the nearest tick.
+    pub fn round_price(&self, price: Number) -> Number {
+        match self.price {
+            Some(ref range) => range.round(price),
+            None => price,
+        }
+    }
+
+    /// Quantity rounded down to the LOT_SIZE step.
+    pub fn round_quantity(&self, quantity: Number) -> Number {
+        match self.lot_size {
+            Some(ref range) => range.floor(quantity),
+            None => quantity,
+        }
+    }
+
+    /// Quantity rounded down to the MARKET_LOT_SIZE step, or the LOT_SIZE step without one.
+    pub fn round_market_quantity(&self, quantity: Number) -> Number {
+        match self.market_lot_size {
+            Some(ref range) if is_set(range.step) => range.floor(quantity),
+            _ => self.round_quantity(quantity),
+        }
+    }
+
+    /// Price rounded to the nearest tick, with the decimals of the tick size.
+    pub fn format_price(&self, price: Number) -> String {
+        match self.price {
+            Some(ref range) => range.format(range.round(price)),
+            None => price.to_string(),
+        }
+    }
+
+    /// Quantity rounded down to the step, with the decimals of the step size.
+    pub fn format_quantity(&self, quantity: Number) -> String {
+        match self.lot_size {
+            Some(ref range) => range.format(range.floor(quantity)),
+            None => quantity.to_string(),
+        }
+    }
+
+    /// Order with its prices on the tick and its quantities rounded down to the step.
+    pub fn round_order(&self, mut order: NewOrder) -> NewOrder {
+        order.price = order.price.map(|price| self.round_price(price));
+        order.stop_price = order.stop_price.map(|price| self.round_price(price));
+        order.iceberg_qty = order.iceberg_qty.map(|qty| self.round_quantity(qty));
+        order.quantity = match order.order_type {
+            OrderType::Market => order.quantity.map(|qty| self.round_market_quantity(qty)),
+            _ => order.quantity.map(|qty| self.round_quantity(qty)),
+        };
+        order
+    }
+
+    /// Fails with `ErrorKind::FilterViolation` on the first filter the order breaks.
+    pub fn validate(&self, order: &NewOrder, reference_price: Number) -> Result<()> {
+        match self.violations(order, reference_price).into_iter().next() {
+            Some(violation) => Err(ErrorKind::FilterViolation(violation).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Every filter the order breaks. `reference_price` is the average price of the
+    /// symbol, used by the percent price filters and for the notional of orders
+    /// without a price.
+    pub fn violations(&self, order: &NewOrder, reference_price: Number) -> Vec<FilterViolation> {
+        let mut violations = Vec::new();
+        let is_market = matches!(order.order_type, OrderType::Market);
+        let price = order.price;
+        let quantity = order.quantity;
+
+        if let Some(ref range) = self.price {
+            for (parameter, value) in [("price", price), ("stopPrice", order.stop_price)] {
+                if let Some(value) = value {
+                    if let Some((bound, limit)) = range.check(value) {
+                        violations.push(FilterViolation::Price {
+                            parameter,
+                            value,
+                            bound,
+                            limit,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(quantity) = quantity {
+            if let Some((bound, limit)) = self.lot_size.as_ref().and_then(|r| r.check(quantity)) {
+                violations.push(FilterViolation::LotSize {
+                    value: quantity,
+                    bound,
+                    limit,
+                });
+            }
+            if is_market {
+                if let Some((bound, limit)) = self
+                    .market_lot_size
+                    .as_ref()
+                    .and_then(|r| r.check(quantity))
+                {
+                    violations.push(FilterViolation::MarketLotSize {
+                        value: quantity,
+                        bound,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        let notional = match (order.quote_order_qty, quantity) {
+            (Some(quote_order_qty), _) => Some(quote_order_qty),
+            (None, Some(quantity)) => Some(quantity * price.unwrap_or(reference_price)),
+            (None, None) => None,
+        };
+        if let Some(notional) = notional {
+            if let Some(ref filter) = self.min_notional {
+                if is_set(filter.min)
+                    && notional < filter.min
+                    && (!is_market || filter.apply_min_to_market)
+                {
+                    violations.push(FilterViolation::MinNotional {
+                        notional,
+                        limit: filter.min,
+                    });
+                }
+            }
+            if let Some(ref filter) = self.notional {
+                if is_set(filter.min)
+                    && notional < filter.min
+                    && (!is_market || filter.apply_min_to_market)
+                {
+                    violations.push(FilterViolation::Notional {
+                        notional,
+                        bound: Bound::BelowMin,
+                        limit: filter.min,
+                    });
+                } else if is_set(filter.max)
+                    && notional > filter.max
+                    && (!is_market || filter.apply_max_to_market)
+                {
+                    violations.push(FilterViolation::Notional {
+                        notional,
+                        bound: Bound::AboveMax,
+                        limit: filter.max,
+                    });
+                }
+            }
+        }
+
+        if let (Some(price), true) = (price, is_set(reference_price)) {
+            if let Some(ref multipliers) = self.percent_price {
+                if let Some((bound, limit)) = multipliers.check(price, reference_price) {
+                    violations.push(FilterViolation::PercentPrice {
+                        price,
+                        bound,
+                        limit,
+                    });
+                }
+            }
+            if let Some((ref bid, ref ask)) = self.percent_price_by_side {
+                let multipliers = match order.side {
+                    OrderSide::Buy => bid,
+                    OrderSide::Sell => ask,
+                };
+                if let Some((bound, limit)) = multipliers.check(price, reference_price) {
+                    violations.push(FilterViolation::PercentPriceBySide {
+                        price,
+                        bound,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        if let (Some(limit), Some(iceberg_qty), Some(quantity)) =
+            (self.iceberg_parts, order.iceberg_qty, quantity)
+        {
+            if is_set(iceberg_qty) {
+                let parts = (quantity / iceberg_qty - STEP_TOLERANCE).ceil();
+                let parts = number_to_f64(parts) as u64;
+                if limit > 0 && parts > limit {
+                    violations.push(FilterViolation::IcebergParts { parts, limit });
+                }
+            }
+        }
+
+        if let (Some(ref filter), Some(delta)) = (&self.trailing_delta, order.trailing_delta) {
+            // Above the market for buy stops and sell take profits, below otherwise
+            let above = matches!(
+                (&order.side, &order.order_type),
+                (OrderSide::Buy, OrderType::StopLoss)
+                    | (OrderSide::Buy, OrderType::StopLossLimit)
+                    | (OrderSide::Sell, OrderType::TakeProfit)
+                    | (OrderSide::Sell, OrderType::TakeProfitLimit)
+            );
+            let (min, max) = if above {
+                (filter.min_above, filter.max_above)
+            } else {
+                (filter.min_below, filter.max_below)
+            };
+            if min > 0 && delta < min {
+                violations.push(FilterViolation::TrailingDelta {
+                    delta,
+                    bound: Bound::BelowMin,
+                    limit: min,
+                });
+            } else if max > 0 && delta > max {
+                violations.push(FilterViolation::TrailingDelta {
+                    delta,
+                    bound: Bound::AboveMax,
+                    limit: max,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+impl Multipliers {
+    fn check(&self, price: Number, reference_price: Number) -> Option<(Bound, Number)> {
+        let (low, high) = (reference_price * self.down, reference_price * self.up);
+        if is_set(self.down) && price < low {
+            Some((Bound::BelowMin, low))
+        } else if is_set(self.up) && price > high {
+            Some((Bound::AboveMax, high))
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_optional(value: &Option<String>) -> Result<Number> {
+    match value {
+        Some(value) => parse_number(value),
+        None => Ok(Number::default()),
+    }
+}
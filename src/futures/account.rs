@@ -8,8 +8,16 @@ use crate::model::Empty;
 use crate::account::{OrderSide, TimeInForce};
 use super::model::{
     ChangeLeverageResponse, Transaction, CanceledOrder, PositionRisk, AccountBalance,
-    AccountInformation,
+    AccountInformation, BatchOrderResponse, BatchCancelResponse, PositionMarginResponse,
+    LeverageBracket, Income, CommissionRate, AdlQuantile, Order, PositionMode, MultiAssetsMargin,
+    CountdownCancelAll, ModifyOrderResponse, AmendRejectReason, OrderAmendment, TradeHistory,
 };
+use crate::history::{HistoryIter, HistoryQuery, DAY};
+use serde_json::Value;
+
+// Maximum number of orders of a batchOrders request
+const MAX_BATCH_ORDERS: usize = 5;
+const MAX_BATCH_CANCELS: usize = 10;
 
 #[derive(Clone)]
 pub struct FuturesAccount {
@@ -92,7 +100,115 @@ impl From<WorkingType> for String {
     }
 }
 
-struct OrderRequest {
+/// Price an order at the opposite (`Opponent`) or same (`Queue`) side of the book,
+/// the number is the level of the book
+#[derive(Clone)]
+pub enum PriceMatch {
+    Opponent,
+    Opponent5,
+    Opponent10,
+    Opponent20,
+    Queue,
+    Queue5,
+    Queue10,
+    Queue20,
+}
+
+impl From<PriceMatch> for String {
+    fn from(item: PriceMatch) -> Self {
+        match item {
+            PriceMatch::Opponent => String::from("OPPONENT"),
+            PriceMatch::Opponent5 => String::from("OPPONENT_5"),
+            PriceMatch::Opponent10 => String::from("OPPONENT_10"),
+            PriceMatch::Opponent20 => String::from("OPPONENT_20"),
+            PriceMatch::Queue => String::from("QUEUE"),
+            PriceMatch::Queue5 => String::from("QUEUE_5"),
+            PriceMatch::Queue10 => String::from("QUEUE_10"),
+            PriceMatch::Queue20 => String::from("QUEUE_20"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum MarginType {
+    Isolated,
+    Crossed,
+}
+
+impl From<MarginType> for String {
+    fn from(item: MarginType) -> Self {
+        match item {
+            MarginType::Isolated => String::from("ISOLATED"),
+            MarginType::Crossed => String::from("CROSSED"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum IncomeType {
+    Transfer,
+    WelcomeBonus,
+    RealizedPnl,
+    FundingFee,
+    Commission,
+    InsuranceClear,
+    ReferralKickback,
+    CommissionRebate,
+    ApiRebate,
+    ContestReward,
+    CrossCollateralTransfer,
+    OptionsPremiumFee,
+    OptionsSettleProfit,
+    InternalTransfer,
+    AutoExchange,
+    DeliveredSettlement,
+    CoinSwapDeposit,
+    CoinSwapWithdraw,
+    PositionLimitIncreaseFee,
+}
+
+impl From<IncomeType> for String {
+    fn from(item: IncomeType) -> Self {
+        match item {
+            IncomeType::Transfer => String::from("TRANSFER"),
+            IncomeType::WelcomeBonus => String::from("WELCOME_BONUS"),
+            IncomeType::RealizedPnl => String::from("REALIZED_PNL"),
+            IncomeType::FundingFee => String::from("FUNDING_FEE"),
+            IncomeType::Commission => String::from("COMMISSION"),
+            IncomeType::InsuranceClear => String::from("INSURANCE_CLEAR"),
+            IncomeType::ReferralKickback => String::from("REFERRAL_KICKBACK"),
+            IncomeType::CommissionRebate => String::from("COMMISSION_REBATE"),
+            IncomeType::ApiRebate => String::from("API_REBATE"),
+            IncomeType::ContestReward => String::from("CONTEST_REWARD"),
+            IncomeType::CrossCollateralTransfer => String::from("CROSS_COLLATERAL_TRANSFER"),
+            IncomeType::OptionsPremiumFee => String::from("OPTIONS_PREMIUM_FEE"),
+            IncomeType::OptionsSettleProfit => String::from("OPTIONS_SETTLE_PROFIT"),
+            IncomeType::InternalTransfer => String::from("INTERNAL_TRANSFER"),
+            IncomeType::AutoExchange => String::from("AUTO_EXCHANGE"),
+            IncomeType::DeliveredSettlement => String::from("DELIVERED_SETTELMENT"),
+            IncomeType::CoinSwapDeposit => String::from("COIN_SWAP_DEPOSIT"),
+            IncomeType::CoinSwapWithdraw => String::from("COIN_SWAP_WITHDRAW"),
+            IncomeType::PositionLimitIncreaseFee => String::from("POSITION_LIMIT_INCREASE_FEE"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum AutoCloseType {
+    Liquidation,
+    Adl,
+}
+
+impl From<AutoCloseType> for String {
+    fn from(item: AutoCloseType) -> Self {
+        match item {
+            AutoCloseType::Liquidation => String::from("LIQUIDATION"),
+            AutoCloseType::Adl => String::from("ADL"),
+        }
+    }
+}
+
+pub(crate) struct OrderRequest {
     pub symbol: String,
     pub side: OrderSide,
     pub position_side: Option<PositionSide>,
@@ -128,6 +244,41 @@ pub struct CustomOrderRequest {
     pub order_id: Option<String>
 }
 
+impl From<CustomOrderRequest> for OrderRequest {
+    fn from(order_request: CustomOrderRequest) -> Self {
+        OrderRequest {
+            symbol: order_request.symbol,
+            side: order_request.side,
+            position_side: order_request.position_side,
+            order_type: order_request.order_type,
+            time_in_force: order_request.time_in_force,
+            qty: order_request.qty,
+            reduce_only: order_request.reduce_only,
+            price: order_request.price,
+            stop_price: order_request.stop_price,
+            close_position: order_request.close_position,
+            activation_price: order_request.activation_price,
+            callback_rate: order_request.callback_rate,
+            working_type: order_request.working_type,
+            price_protect: order_request.price_protect,
+            order_id: order_request.order_id,
+        }
+    }
+}
+
+/// Modification of a LIMIT order, identified by `order_id` or `orig_client_order_id`.
+#[derive(Clone)]
+pub struct ModifyOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub quantity: f64,
+    /// Ignored when `price_match` is set
+    pub price: f64,
+    pub price_match: Option<PriceMatch>,
+    pub order_id: Option<u64>,
+    pub orig_client_order_id: Option<String>,
+}
+
 impl FuturesAccount {
     pub fn limit_buy(
         &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
@@ -150,7 +301,7 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
@@ -177,7 +328,7 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
@@ -206,7 +357,7 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(buy);
+        let order = build_order(buy);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
@@ -235,7 +386,7 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
@@ -290,7 +441,7 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
@@ -319,110 +470,151 @@ impl FuturesAccount {
             price_protect: None,
             order_id: None
         };
-        let order = self.build_order(sell);
+        let order = build_order(sell);
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
     }
 
-    // Modify Order
-    pub fn modify_order<S>(&self, symbol: S, side: OrderSide, price: f64, quantity: f64, client_order_id: S, _is_close_position: bool) -> Result<Transaction>
-     where
-        S: Into<String>,
+    // Modify the price and quantity of a LIMIT order, a refused modification
+    // is returned as `ModifyOrderResponse::Rejected`
+    pub fn modify_order(&self, modify_request: ModifyOrderRequest) -> Result<ModifyOrderResponse> {
+        let order = build_modify_order(modify_request)?;
+        let request = build_signed_request(order, self.recv_window)?;
+        match self
+            .client
+            .put_signed(API::Futures(Futures::ModifyOrder), request)
+        {
+            Ok(transaction) => Ok(ModifyOrderResponse::Modified(Box::new(transaction))),
+            Err(Error(ErrorKind::BinanceError(error), _))
+                if AmendRejectReason::from_code(error.code).is_some() =>
+            {
+                Ok(error.into())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    // Modification history of ONE order, identified by `order_id` or `orig_client_order_id`
+    pub fn get_order_amendments<S1, S2, S3, S4, S5, S6>(
+        &self, symbol: S1, order_id: S2, orig_client_order_id: S3, start_time: S4,
+        end_time: S5, limit: S6,
+    ) -> Result<Vec<OrderAmendment>>
+    where
+        S1: Into<String>,
+        S2: Into<Option<u64>>,
+        S3: Into<Option<String>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+        S6: Into<Option<u16>>,
     {
-        let mut parameters = BTreeMap::new();
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
         parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("side".into(), side.into());
-        parameters.insert("price".into(), price.to_string());
-        parameters.insert("origClientOrderId".into(), client_order_id.into());
-        //parameters.insert("type".into(), OrderType::Limit.into());
-
-        //if !is_close_position{
-        parameters.insert("quantity".into(), quantity.to_string());
-        //}
+        match (order_id.into(), orig_client_order_id.into()) {
+            (Some(order_id), _) => {
+                parameters.insert("orderId".into(), order_id.to_string());
+            }
+            (None, Some(orig_client_order_id)) => {
+                parameters.insert("origClientOrderId".into(), orig_client_order_id);
+            }
+            (None, None) => bail!("Either order_id or orig_client_order_id must be sent"),
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
 
         let request = build_signed_request(parameters, self.recv_window)?;
         self.client
-            .put_signed(API::Futures(Futures::ModifyOrder), request)
+            .get_signed(API::Futures(Futures::OrderAmendment), Some(request))
     }
 
 
     // Custom order for for professional traders
     pub fn custom_order(&self, order_request: CustomOrderRequest) -> Result<Transaction> {
-        let order: OrderRequest = OrderRequest {
-            symbol: order_request.symbol,
-            side: order_request.side,
-            position_side: order_request.position_side,
-            order_type: order_request.order_type,
-            time_in_force: order_request.time_in_force,
-            qty: order_request.qty,
-            reduce_only: order_request.reduce_only,
-            price: order_request.price,
-            stop_price: order_request.stop_price,
-            close_position: order_request.close_position,
-            activation_price: order_request.activation_price,
-            callback_rate: order_request.callback_rate,
-            working_type: order_request.working_type,
-            price_protect: order_request.price_protect,
-            order_id: order_request.order_id
-        };
-        let order = self.build_order(order);
+        let order = build_order(order_request.into());
         let request = build_signed_request(order, self.recv_window)?;
         self.client
             .post_signed(API::Futures(Futures::Order), request)
     }
 
-    fn build_order(&self, order: OrderRequest) -> BTreeMap<String, String> {
-        let mut parameters = BTreeMap::new();
-        parameters.insert("symbol".into(), order.symbol);
-        parameters.insert("side".into(), order.side.into());
-        parameters.insert("type".into(), order.order_type.into());
+    // Place up to 5 orders in one request
+    pub fn custom_batch_orders(
+        &self, order_requests: Vec<CustomOrderRequest>,
+    ) -> Result<Vec<BatchOrderResponse>> {
+        check_batch_size(order_requests.len(), MAX_BATCH_ORDERS)?;
+        let orders: Vec<BTreeMap<String, String>> = order_requests
+            .into_iter()
+            .map(|order_request| build_order(order_request.into()))
+            .collect();
 
-        if let Some(position_side) = order.position_side {
-            parameters.insert("positionSide".into(), position_side.into());
-        }
-        if let Some(time_in_force) = order.time_in_force {
-            parameters.insert("timeInForce".into(), time_in_force.into());
-        }
-        if let Some(qty) = order.qty {
-            parameters.insert("quantity".into(), qty.to_string());
-        }
-        if let Some(reduce_only) = order.reduce_only {
-            parameters.insert("reduceOnly".into(), reduce_only.to_string().to_uppercase());
-        }
-        if let Some(price) = order.price {
-            parameters.insert("price".into(), price.to_string());
-        }
-        if let Some(stop_price) = order.stop_price {
-            parameters.insert("stopPrice".into(), stop_price.to_string());
-        }
-        if let Some(close_position) = order.close_position {
-            parameters.insert(
-                "closePosition".into(),
-                close_position.to_string().to_uppercase(),
-            );
-        }
-        if let Some(activation_price) = order.activation_price {
-            parameters.insert("activationPrice".into(), activation_price.to_string());
-        }
-        if let Some(callback_rate) = order.callback_rate {
-            parameters.insert("callbackRate".into(), callback_rate.to_string());
-        }
-        if let Some(working_type) = order.working_type {
-            parameters.insert("workingType".into(), working_type.into());
-        }
-        if let Some(price_protect) = order.price_protect {
-            parameters.insert(
-                "priceProtect".into(),
-                price_protect.to_string().to_uppercase(),
-            );
-        }
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("batchOrders".into(), encode_batch(&orders)?);
 
-        if let Some(order_id) = order.order_id {
-            parameters.insert("newClientOrderId".into(), order_id.into());
-        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::BatchOrders), request)
+    }
+
+    // Modify up to 5 LIMIT orders in one request
+    pub fn modify_batch_orders(
+        &self, modify_requests: Vec<ModifyOrderRequest>,
+    ) -> Result<Vec<ModifyOrderResponse>> {
+        check_batch_size(modify_requests.len(), MAX_BATCH_ORDERS)?;
+        let orders = modify_requests
+            .into_iter()
+            .map(build_modify_order)
+            .collect::<Result<Vec<BTreeMap<String, String>>>>()?;
 
-        parameters
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("batchOrders".into(), encode_batch(&orders)?);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let responses: Vec<BatchOrderResponse> = self
+            .client
+            .put_signed(API::Futures(Futures::BatchOrders), request)?;
+        Ok(responses.into_iter().map(ModifyOrderResponse::from).collect())
+    }
+
+    // Cancel up to 10 orders of ONE symbol in one request
+    pub fn cancel_batch_orders<S>(
+        &self, symbol: S, order_ids: Vec<u64>,
+    ) -> Result<Vec<BatchCancelResponse>>
+    where
+        S: Into<String>,
+    {
+        check_batch_size(order_ids.len(), MAX_BATCH_CANCELS)?;
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderIdList".into(), encode_batch(&order_ids)?);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Futures(Futures::BatchOrders), Some(request))
+    }
+
+    pub fn cancel_batch_orders_with_client_ids<S>(
+        &self, symbol: S, orig_client_order_ids: Vec<String>,
+    ) -> Result<Vec<BatchCancelResponse>>
+    where
+        S: Into<String>,
+    {
+        check_batch_size(orig_client_order_ids.len(), MAX_BATCH_CANCELS)?;
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert(
+            "origClientOrderIdList".into(),
+            encode_batch(&orig_client_order_ids)?,
+        );
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Futures(Futures::BatchOrders), Some(request))
     }
 
     pub fn position_information<S>(&self, symbol: S) -> Result<Vec<PositionRisk>>
@@ -437,6 +629,15 @@ impl FuturesAccount {
             .get_signed(API::Futures(Futures::PositionRisk), Some(request))
     }
 
+    // Positions of every symbol
+    pub fn all_position_information(&self) -> Result<Vec<PositionRisk>> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::PositionRisk), Some(request))
+    }
+
     pub fn account_information(&self) -> Result<AccountInformation> {
         let parameters = BTreeMap::new();
 
@@ -515,6 +716,36 @@ impl FuturesAccount {
             .get_signed(API::Futures(Futures::AllOrders), Some(request))
     }
 
+    // Orders of every status from an order id or within a time range of at most 7 days
+    pub fn all_orders_query(&self, query: &HistoryQuery) -> Result<Vec<Order>> {
+        let request = build_signed_request(query.order_parameters(), self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::AllOrders), Some(request))
+    }
+
+    // Every order of the query, fetched by pages of up to 1000 orders
+    pub fn all_orders_iter(&self, query: HistoryQuery) -> HistoryIter<Order> {
+        let account = self.clone();
+        HistoryIter::new(query, 1000, 7 * DAY, move |query| {
+            account.all_orders_query(query)
+        })
+    }
+
+    // Trade history by order, trade id or time, at most 7 days between start and end time
+    pub fn trade_history_query(&self, query: &HistoryQuery) -> Result<Vec<TradeHistory>> {
+        let request = build_signed_request(query.trade_parameters(), self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::UserTrades), Some(request))
+    }
+
+    // Every trade of the query, fetched by pages of up to 1000 trades
+    pub fn trade_history_iter(&self, query: HistoryQuery) -> HistoryIter<TradeHistory> {
+        let account = self.clone();
+        HistoryIter::new(query, 1000, 7 * DAY, move |query| {
+            account.trade_history_query(query)
+        })
+    }
+
     pub fn get_order_status<S>(&self, symbol: S, client_order_id: S) -> Result<crate::futures::model::Order>
     where
         S: Into<String>,
@@ -526,4 +757,311 @@ impl FuturesAccount {
         self.client
             .get_signed(API::Futures(Futures::Order), Some(request))
     }
+
+    // Current position mode, true for Hedge Mode and false for One-way Mode
+    pub fn get_position_mode(&self) -> Result<PositionMode> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::PositionSide), Some(request))
+    }
+
+    pub fn change_margin_type<S>(&self, symbol: S, margin_type: MarginType) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("marginType".into(), margin_type.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed::<Empty>(API::Futures(Futures::MarginType), request)
+            .map(|_| ())
+    }
+
+    // Add margin to an isolated position
+    pub fn add_position_margin<S>(
+        &self, symbol: S, amount: f64, position_side: Option<PositionSide>,
+    ) -> Result<PositionMarginResponse>
+    where
+        S: Into<String>,
+    {
+        self.modify_position_margin(symbol.into(), amount, 1, position_side)
+    }
+
+    // Reduce the margin of an isolated position
+    pub fn reduce_position_margin<S>(
+        &self, symbol: S, amount: f64, position_side: Option<PositionSide>,
+    ) -> Result<PositionMarginResponse>
+    where
+        S: Into<String>,
+    {
+        self.modify_position_margin(symbol.into(), amount, 2, position_side)
+    }
+
+    // Notional and leverage brackets, of every symbol when `symbol` is None
+    pub fn leverage_brackets<S>(&self, symbol: S) -> Result<Vec<LeverageBracket>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data: Value = self
+            .client
+            .get_signed(API::Futures(Futures::LeverageBracket), Some(request))?;
+        one_or_many(data)
+    }
+
+    // Income history: funding fees, commissions, realized PnL, transfers...
+    pub fn get_income<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, income_type: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<Vec<Income>>
+    where
+        S1: Into<Option<String>>,
+        S2: Into<Option<IncomeType>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        if let Some(income_type) = income_type.into() {
+            parameters.insert("incomeType".into(), income_type.into());
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::Income), Some(request))
+    }
+
+    pub fn commission_rate<S>(&self, symbol: S) -> Result<CommissionRate>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::CommissionRate), Some(request))
+    }
+
+    // ADL quantile of the open positions, of every symbol when `symbol` is None
+    pub fn adl_quantile<S>(&self, symbol: S) -> Result<Vec<AdlQuantile>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        let data: Value = self
+            .client
+            .get_signed(API::Futures(Futures::AdlQuantile), Some(request))?;
+        one_or_many(data)
+    }
+
+    // Liquidation and ADL orders of the user
+    pub fn get_force_orders<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, auto_close_type: S2, start_time: S3, end_time: S4, limit: S5,
+    ) -> Result<Vec<Order>>
+    where
+        S1: Into<Option<String>>,
+        S2: Into<Option<AutoCloseType>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u16>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        if let Some(auto_close_type) = auto_close_type.into() {
+            parameters.insert("autoCloseType".into(), auto_close_type.into());
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::ForceOrders), Some(request))
+    }
+
+    pub fn get_multi_assets_margin(&self) -> Result<MultiAssetsMargin> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Futures(Futures::MultiAssetsMargin), Some(request))
+    }
+
+    pub fn change_multi_assets_margin(&self, multi_assets_margin: bool) -> Result<()> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        let multi_assets = if multi_assets_margin { "true" } else { "false" };
+        parameters.insert("multiAssetsMargin".into(), multi_assets.into());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed::<Empty>(API::Futures(Futures::MultiAssetsMargin), request)
+            .map(|_| ())
+    }
+
+    // Cancel every open order of `symbol` unless the countdown is re-armed within
+    // `countdown_time` milliseconds, see `futures::heartbeat::CountdownHeartbeat`
+    pub fn countdown_cancel_all<S>(
+        &self, symbol: S, countdown_time: u64,
+    ) -> Result<CountdownCancelAll>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("countdownTime".into(), countdown_time.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::CountdownCancelAll), request)
+    }
+
+    // Stop the countdown of `symbol`, its open orders are kept
+    pub fn stop_countdown_cancel_all<S>(&self, symbol: S) -> Result<CountdownCancelAll>
+    where
+        S: Into<String>,
+    {
+        self.countdown_cancel_all(symbol, 0)
+    }
+
+    fn modify_position_margin(
+        &self, symbol: String, amount: f64, modify_type: u8,
+        position_side: Option<PositionSide>,
+    ) -> Result<PositionMarginResponse> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol);
+        parameters.insert("amount".into(), amount.to_string());
+        parameters.insert("type".into(), modify_type.to_string());
+        if let Some(position_side) = position_side {
+            parameters.insert("positionSide".into(), position_side.into());
+        }
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .post_signed(API::Futures(Futures::PositionMargin), request)
+    }
+}
+
+pub(crate) fn build_order(order: OrderRequest) -> BTreeMap<String, String> {
+    let mut parameters = BTreeMap::new();
+    parameters.insert("symbol".into(), order.symbol);
+    parameters.insert("side".into(), order.side.into());
+    parameters.insert("type".into(), order.order_type.into());
+
+    if let Some(position_side) = order.position_side {
+        parameters.insert("positionSide".into(), position_side.into());
+    }
+    if let Some(time_in_force) = order.time_in_force {
+        parameters.insert("timeInForce".into(), time_in_force.into());
+    }
+    if let Some(qty) = order.qty {
+        parameters.insert("quantity".into(), qty.to_string());
+    }
+    if let Some(reduce_only) = order.reduce_only {
+        parameters.insert("reduceOnly".into(), reduce_only.to_string().to_uppercase());
+    }
+    if let Some(price) = order.price {
+        parameters.insert("price".into(), price.to_string());
+    }
+    if let Some(stop_price) = order.stop_price {
+        parameters.insert("stopPrice".into(), stop_price.to_string());
+    }
+    if let Some(close_position) = order.close_position {
+        parameters.insert(
+            "closePosition".into(),
+            close_position.to_string().to_uppercase(),
+        );
+    }
+    if let Some(activation_price) = order.activation_price {
+        parameters.insert("activationPrice".into(), activation_price.to_string());
+    }
+    if let Some(callback_rate) = order.callback_rate {
+        parameters.insert("callbackRate".into(), callback_rate.to_string());
+    }
+    if let Some(working_type) = order.working_type {
+        parameters.insert("workingType".into(), working_type.into());
+    }
+    if let Some(price_protect) = order.price_protect {
+        parameters.insert(
+            "priceProtect".into(),
+            price_protect.to_string().to_uppercase(),
+        );
+    }
+
+    if let Some(order_id) = order.order_id {
+        parameters.insert("newClientOrderId".into(), order_id.into());
+    }
+
+    parameters
+}
+
+fn build_modify_order(modify_request: ModifyOrderRequest) -> Result<BTreeMap<String, String>> {
+    let mut order: BTreeMap<String, String> = BTreeMap::new();
+    order.insert("symbol".into(), modify_request.symbol);
+    order.insert("side".into(), modify_request.side.into());
+    order.insert("quantity".into(), modify_request.quantity.to_string());
+    match modify_request.price_match {
+        Some(price_match) => {
+            order.insert("priceMatch".into(), price_match.into());
+        }
+        None => {
+            order.insert("price".into(), modify_request.price.to_string());
+        }
+    }
+    match (modify_request.order_id, modify_request.orig_client_order_id) {
+        (Some(order_id), _) => {
+            order.insert("orderId".into(), order_id.to_string());
+        }
+        (None, Some(orig_client_order_id)) => {
+            order.insert("origClientOrderId".into(), orig_client_order_id);
+        }
+        (None, None) => bail!("Either order_id or orig_client_order_id must be sent"),
+    }
+    Ok(order)
+}
+
+fn check_batch_size(size: usize, max: usize) -> Result<()> {
+    if size == 0 || size > max {
+        bail!(format!("A batch holds 1 to {} orders, got {}", max, size));
+    }
+    Ok(())
+}
+
+// JSON list as an url encoded query parameter
+fn encode_batch<T: serde::Serialize>(batch: &T) -> Result<String> {
+    let json = serde_json::to_string(batch)?;
+    Ok(url::form_urlencoded::byte_serialize(json.as_bytes()).collect())
 }
@@ -0,0 +1,214 @@
+/*!
+Offline USD-M futures margin calculator.
+
+Initial and maintenance margin, liquidation and break-even prices computed with
+Binance's published formulas from the leverage brackets of the symbol
+(`FuturesAccount::leverage_brackets`), to size orders before sending them.
+Balances and PnL are in the margin asset.
+*/
+
+use std::convert::TryFrom;
+
+use crate::errors::*;
+use crate::futures::account::MarginType;
+use crate::futures::model::{
+    AccountInformation, Bracket, FuturesPosition, LeverageBracket, PositionRisk,
+};
+
+/// Position of one symbol and position side.
+#[derive(Debug, Clone)]
+pub struct CalculatorPosition {
+    pub symbol: String,
+    /// Negative for short positions
+    pub position_amount: f64,
+    pub entry_price: f64,
+    pub leverage: f64,
+}
+
+impl CalculatorPosition {
+    pub fn new<S>(symbol: S, position_amount: f64, entry_price: f64, leverage: f64) -> Self
+    where
+        S: Into<String>,
+    {
+        CalculatorPosition {
+            symbol: symbol.into(),
+            position_amount,
+            entry_price,
+            leverage,
+        }
+    }
+
+    /// Absolute value of the position at the entry price.
+    pub fn notional(&self) -> f64 {
+        self.position_amount.abs() * self.entry_price
+    }
+
+    pub fn initial_margin(&self) -> f64 {
+        if self.leverage > 0.0 {
+            self.notional() / self.leverage
+        } else {
+            0.0
+        }
+    }
+
+    /// Maintenance margin at the entry price.
+    pub fn maint_margin(&self, brackets: &LeverageBracket) -> Result<f64> {
+        let notional = self.notional();
+        let bracket = find_bracket(brackets, &self.symbol, notional)?;
+        Ok(bracket.maint_margin(notional))
+    }
+
+    pub fn unrealized_pnl(&self, mark_price: f64) -> f64 {
+        self.position_amount * (mark_price - self.entry_price)
+    }
+
+    /// Price covering the opening and closing fees, `fee_rate` of 0.0004 is 0.04%.
+    pub fn break_even_price(&self, fee_rate: f64) -> f64 {
+        if self.position_amount < 0.0 {
+            self.entry_price * (1.0 - fee_rate) / (1.0 + fee_rate)
+        } else {
+            self.entry_price * (1.0 + fee_rate) / (1.0 - fee_rate)
+        }
+    }
+}
+
+impl TryFrom<&PositionRisk> for CalculatorPosition {
+    type Error = Error;
+
+    fn try_from(position: &PositionRisk) -> Result<Self> {
+        Ok(CalculatorPosition::new(
+            position.symbol.clone(),
+            position.position_amount,
+            position.entry_price,
+            position.leverage.parse()?,
+        ))
+    }
+}
+
+impl TryFrom<&FuturesPosition> for CalculatorPosition {
+    type Error = Error;
+
+    fn try_from(position: &FuturesPosition) -> Result<Self> {
+        Ok(CalculatorPosition::new(
+            position.symbol.clone(),
+            position.position_amount,
+            position.entry_price,
+            position.leverage.parse()?,
+        ))
+    }
+}
+
+/// Wallet backing the positions whose liquidation price is computed.
+#[derive(Clone)]
+pub struct MarginWallet {
+    pub margin_type: MarginType,
+    /// Cross wallet balance, or the isolated wallet of the position
+    pub wallet_balance: f64,
+    /// Maintenance margin of the cross positions of the other symbols
+    pub other_maint_margin: f64,
+    /// Unrealized PnL of the cross positions of the other symbols
+    pub other_unrealized_pnl: f64,
+}
+
+impl MarginWallet {
+    pub fn isolated(isolated_wallet: f64) -> Self {
+        MarginWallet {
+            margin_type: MarginType::Isolated,
+            wallet_balance: isolated_wallet,
+            other_maint_margin: 0.0,
+            other_unrealized_pnl: 0.0,
+        }
+    }
+
+    pub fn cross(wallet_balance: f64, other_maint_margin: f64, other_unrealized_pnl: f64) -> Self {
+        MarginWallet {
+            margin_type: MarginType::Crossed,
+            wallet_balance,
+            other_maint_margin,
+            other_unrealized_pnl,
+        }
+    }
+
+    /// Cross wallet of `information`, with the cross positions of every other symbol.
+    pub fn cross_from_account<S>(information: &AccountInformation, symbol: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let symbol = symbol.into();
+        let others = information
+            .positions
+            .iter()
+            .filter(|position| position.symbol != symbol && !position.isolated);
+
+        let (other_maint_margin, other_unrealized_pnl) =
+            others.fold((0.0, 0.0), |(maint_margin, pnl), position| {
+                (maint_margin + position.maint_margin, pnl + position.unrealized_profit)
+            });
+
+        MarginWallet::cross(
+            information.total_cross_wallet_balance,
+            other_maint_margin,
+            other_unrealized_pnl,
+        )
+    }
+}
+
+/// Liquidation price of the positions of one symbol, None when they can't be liquidated.
+///
+/// In one-way mode `positions` is the BOTH position. In hedge mode it is the LONG and
+/// SHORT positions for a cross wallet, or the position of one side for an isolated
+/// wallet. The maintenance brackets are the ones of the positions at their entry price.
+pub fn liquidation_price(
+    positions: &[CalculatorPosition], brackets: &LeverageBracket, wallet: &MarginWallet,
+) -> Result<Option<f64>> {
+    let mut numerator = wallet.wallet_balance;
+    if let MarginType::Crossed = wallet.margin_type {
+        numerator += wallet.other_unrealized_pnl - wallet.other_maint_margin;
+    }
+    let mut denominator = 0.0;
+
+    for position in positions {
+        let bracket = find_bracket(brackets, &position.symbol, position.notional())?;
+        numerator += bracket.cum - position.position_amount * position.entry_price;
+        denominator += position.position_amount.abs() * bracket.maint_margin_ratio
+            - position.position_amount;
+    }
+
+    if denominator == 0.0 {
+        return Ok(None);
+    }
+    let price = numerator / denominator;
+    if price > 0.0 {
+        Ok(Some(price))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Largest quantity `available_balance` can open at `price` and `leverage`,
+/// within the notional cap of the leverage.
+pub fn max_quantity(
+    brackets: &LeverageBracket, available_balance: f64, price: f64, leverage: f64,
+) -> Result<f64> {
+    if price <= 0.0 || leverage <= 0.0 {
+        bail!("Price and leverage must be positive");
+    }
+    let max_notional = match brackets.max_notional(leverage) {
+        Some(max_notional) => max_notional,
+        None => bail!("Leverage {} is above the maximum of {}", leverage, brackets.symbol),
+    };
+
+    Ok((available_balance * leverage).min(max_notional) / price)
+}
+
+fn find_bracket<'a>(
+    brackets: &'a LeverageBracket, symbol: &str, notional: f64,
+) -> Result<&'a Bracket> {
+    if brackets.symbol != symbol {
+        bail!("Leverage brackets of {} used for {}", brackets.symbol, symbol);
+    }
+    match brackets.bracket(notional) {
+        Some(bracket) => Ok(bracket),
+        None => bail!("No leverage bracket of {} for a notional of {}", symbol, notional),
+    }
+}
@@ -0,0 +1,117 @@
+/*!
+Dead man's switch for USD-M futures orders.
+
+`CountdownHeartbeat` arms `/fapi/v1/countdownCancelAll` for a symbol and re-arms it
+from a background thread at a third of the countdown. Re-arming stops when the
+heartbeat is dropped or marked unhealthy, so the open orders of the symbol are
+canceled by Binance once the countdown runs out, also when the process crashed.
+*/
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+
+// Binance rejects shorter countdowns
+const MIN_COUNTDOWN: Duration = Duration::from_secs(1);
+
+pub struct CountdownHeartbeat {
+    account: FuturesAccount,
+    symbol: String,
+    healthy: Arc<AtomicBool>,
+    last_error: Arc<Mutex<Option<String>>>,
+    // Dropping the sender wakes up and ends the thread
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl CountdownHeartbeat {
+    /// Arm the countdown of `symbol` and keep re-arming it in the background.
+    ///
+    /// The first arming is done before returning, its error is returned.
+    pub fn start<S>(account: FuturesAccount, symbol: S, countdown: Duration) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        if countdown < MIN_COUNTDOWN {
+            bail!("Countdown must be at least {:?}", MIN_COUNTDOWN);
+        }
+        let symbol = symbol.into();
+        let countdown_time = countdown.as_millis() as u64;
+        account.countdown_cancel_all(symbol.clone(), countdown_time)?;
+
+        let healthy = Arc::new(AtomicBool::new(true));
+        let last_error = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let interval = countdown / 3;
+
+        let thread = {
+            let account = account.clone();
+            let symbol = symbol.clone();
+            let healthy = healthy.clone();
+            let last_error = last_error.clone();
+            thread::spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    if !healthy.load(Ordering::SeqCst) {
+                        continue;
+                    }
+                    let result = account.countdown_cancel_all(symbol.clone(), countdown_time);
+                    *last_error.lock().unwrap() = result.err().map(|e| e.to_string());
+                }
+            })
+        };
+
+        Ok(CountdownHeartbeat {
+            account,
+            symbol,
+            healthy,
+            last_error,
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// While unhealthy the countdown isn't re-armed and the orders get canceled when
+    /// it runs out. Marking the heartbeat healthy again resumes re-arming.
+    pub fn set_healthy(&self, healthy: bool) {
+        self.healthy.store(healthy, Ordering::SeqCst);
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::SeqCst)
+    }
+
+    /// Error of the last re-arming, None when it succeeded.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Stop re-arming and stop the countdown, the open orders are kept.
+    pub fn disarm(mut self) -> Result<()> {
+        self.stop_thread();
+        self.account.stop_countdown_cancel_all(self.symbol.clone())?;
+        Ok(())
+    }
+
+    fn stop_thread(&mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for CountdownHeartbeat {
+    // The countdown is left armed, the orders are canceled when it runs out
+    fn drop(&mut self) {
+        self.stop.take();
+    }
+}
@@ -6,18 +6,21 @@
 - [x] `Compressed/Aggregate Trades List`
 - [x] `Kline/Candlestick Data`
 - [x] `Mark Price`
-- [ ] `Get Funding Rate History (MARKET_DATA)`
+- [x] `Continuous Contract, Index Price and Mark Price Kline/Candlestick Data`
+- [x] `Get Funding Rate History (MARKET_DATA)`
 - [x] `24hr Ticker Price Change Statistics`
 - [x] `Symbol Price Ticker`
 - [x] `Symbol Order Book Ticker`
 - [x] `Get all Liquidation Orders`
 - [x] `Open Interest`
-- [ ] `Notional and Leverage Brackets (MARKET_DATA)`
-- [ ] `Open Interest Statistics (MARKET_DATA)`
-- [ ] `Top Trader Long/Short Ratio (Accounts) (MARKET_DATA)`
-- [ ] `Top Trader Long/Short Ratio (Positions) (MARKET_DATA)`
-- [ ] `Long/Short Ratio (MARKET_DATA)`
-- [ ] `Taker Buy/Sell Volume (MARKET_DATA)`
+- [x] `Notional and Leverage Brackets (USER_DATA)`, see `FuturesAccount::leverage_brackets`
+- [x] `Open Interest Statistics (MARKET_DATA)`
+- [x] `Top Trader Long/Short Ratio (Accounts) (MARKET_DATA)`
+- [x] `Top Trader Long/Short Ratio (Positions) (MARKET_DATA)`
+- [x] `Long/Short Ratio (MARKET_DATA)`
+- [x] `Taker Buy/Sell Volume (MARKET_DATA)`
+- [x] `Historical BLVT NAV Kline/Candlestick`
+- [x] `Composite Index Symbol Information`
 */
 
 use crate::util::*;
@@ -28,7 +31,8 @@ use std::collections::BTreeMap;
 use serde_json::Value;
 use crate::api::API;
 use crate::api::Futures;
-use std::convert::TryInto;
+use crate::futures::account::ContractType;
+use std::convert::TryFrom;
 
 // TODO
 // Make enums for Strings
@@ -153,34 +157,84 @@ impl FuturesMarket {
         S5: Into<Option<u64>>,
     {
         let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-
         parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("interval".into(), interval.into());
-
-        // Add three optional parameters
-        if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
-        }
-        if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
-        }
-        if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
-        }
+        let klines = self.klines(Futures::Klines, parameters, interval, limit, start_time, end_time)?;
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
 
-        let request = build_request(parameters);
+    // Klines of a contract type of a pair ("BTCUSDT")
+    pub fn get_continuous_klines<S1, S2, S3, S4, S5>(
+        &self, pair: S1, contract_type: ContractType, interval: S2, limit: S3, start_time: S4,
+        end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+        parameters.insert("contractType".into(), contract_type.into());
+        let klines = self.klines(
+            Futures::ContinuousKlines, parameters, interval, limit, start_time, end_time,
+        )?;
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
 
-        let data: Vec<Vec<Value>> = self
-            .client
-            .get(API::Futures(Futures::Klines), Some(request))?;
+    // Klines of the index price of a pair, volumes are always 0
+    pub fn get_index_price_klines<S1, S2, S3, S4, S5>(
+        &self, pair: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("pair".into(), pair.into());
+        let klines = self.klines(
+            Futures::IndexPriceKlines, parameters, interval, limit, start_time, end_time,
+        )?;
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
 
-        let klines = KlineSummaries::AllKlineSummaries(
-            data.iter()
-                .map(|row| row.try_into())
-                .collect::<Result<Vec<KlineSummary>>>()?,
-        );
+    // Klines of the mark price of a symbol, volumes are always 0
+    pub fn get_mark_price_klines<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<KlineSummaries>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let klines = self.klines(
+            Futures::MarkPriceKlines, parameters, interval, limit, start_time, end_time,
+        )?;
+        Ok(KlineSummaries::AllKlineSummaries(klines))
+    }
 
-        Ok(klines)
+    // NAV klines of a leveraged token ("BTCDOWN")
+    pub fn get_lvt_klines<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<LvtKline>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.klines(Futures::LvtKlines, parameters, interval, limit, start_time, end_time)
     }
 
     // 24hr ticker price change statistics
@@ -268,22 +322,163 @@ impl FuturesMarket {
         S4: Into<Option<u64>>,
         S5: Into<Option<u64>>,
     {
-        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
-        parameters.insert("symbol".into(), symbol.into());
-        parameters.insert("period".into(), period.into());
+        self.statistics(Futures::OpenInterestHist, symbol, period, limit, start_time, end_time)
+    }
 
-        if let Some(lt) = limit.into() {
-            parameters.insert("limit".into(), format!("{}", lt));
-        }
-        if let Some(st) = start_time.into() {
-            parameters.insert("startTime".into(), format!("{}", st));
-        }
-        if let Some(et) = end_time.into() {
-            parameters.insert("endTime".into(), format!("{}", et));
+    // Funding rate history, of every symbol when `symbol` is None
+    pub fn get_funding_rate<S1, S2, S3, S4>(
+        &self, symbol: S1, limit: S2, start_time: S3, end_time: S4,
+    ) -> Result<Vec<FundingRate>>
+    where
+        S1: Into<Option<String>>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
         }
+        insert_range(&mut parameters, limit, start_time, end_time);
 
         let request = build_request(parameters);
         self.client
-            .get(API::Futures(Futures::OpenInterestHist), Some(request))
+            .get(API::Futures(Futures::FundingRate), Some(request))
+    }
+
+    pub fn top_long_short_account_ratio<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, period: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<LongShortRatio>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        self.statistics(
+            Futures::TopLongShortAccountRatio, symbol, period, limit, start_time, end_time,
+        )
+    }
+
+    pub fn top_long_short_position_ratio<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, period: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<LongShortRatio>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        self.statistics(
+            Futures::TopLongShortPositionRatio, symbol, period, limit, start_time, end_time,
+        )
+    }
+
+    pub fn global_long_short_account_ratio<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, period: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<LongShortRatio>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        self.statistics(
+            Futures::GlobalLongShortAccountRatio, symbol, period, limit, start_time, end_time,
+        )
+    }
+
+    pub fn taker_buy_sell_volume<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, period: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<TakerBuySellVolume>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        self.statistics(
+            Futures::TakerlongshortRatio, symbol, period, limit, start_time, end_time,
+        )
+    }
+
+    // Composite index components, of every index when `symbol` is None
+    pub fn get_index_info<S>(&self, symbol: S) -> Result<Vec<IndexInfo>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+
+        let request = build_request(parameters);
+        let data: Value = self
+            .client
+            .get(API::Futures(Futures::IndexInfo), Some(request))?;
+        one_or_many(data)
+    }
+
+    fn klines<T, S1, S2, S3, S4>(
+        &self, route: Futures, mut parameters: BTreeMap<String, String>, interval: S1,
+        limit: S2, start_time: S3, end_time: S4,
+    ) -> Result<Vec<T>>
+    where
+        T: for<'a> TryFrom<&'a Vec<Value>, Error = Error>,
+        S1: Into<String>,
+        S2: Into<Option<u16>>,
+        S3: Into<Option<u64>>,
+        S4: Into<Option<u64>>,
+    {
+        parameters.insert("interval".into(), interval.into());
+        insert_range(&mut parameters, limit, start_time, end_time);
+
+        let request = build_request(parameters);
+        let data: Vec<Vec<Value>> = self.client.get(API::Futures(route), Some(request))?;
+
+        data.iter().map(T::try_from).collect()
+    }
+
+    fn statistics<T, S1, S2, S3, S4, S5>(
+        &self, route: Futures, symbol: S1, period: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<T>>
+    where
+        T: serde::de::DeserializeOwned,
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("period".into(), period.into());
+        insert_range(&mut parameters, limit, start_time, end_time);
+
+        let request = build_request(parameters);
+        self.client.get(API::Futures(route), Some(request))
+    }
+}
+
+// Optional `limit`, `startTime` and `endTime` of the paginated endpoints
+fn insert_range<S1, S2, S3>(
+    parameters: &mut BTreeMap<String, String>, limit: S1, start_time: S2, end_time: S3,
+) where
+    S1: Into<Option<u16>>,
+    S2: Into<Option<u64>>,
+    S3: Into<Option<u64>>,
+{
+    if let Some(lt) = limit.into() {
+        parameters.insert("limit".into(), format!("{}", lt));
+    }
+    if let Some(st) = start_time.into() {
+        parameters.insert("startTime".into(), format!("{}", st));
+    }
+    if let Some(et) = end_time.into() {
+        parameters.insert("endTime".into(), format!("{}", et));
     }
 }
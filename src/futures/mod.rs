@@ -1,6 +1,9 @@
 pub mod account;
+pub mod calculator;
 pub mod general;
+pub mod heartbeat;
 pub mod market;
 pub mod model;
+pub mod position_tracker;
 pub mod userstream;
 pub mod websockets;
@@ -1,5 +1,8 @@
+use std::convert::TryFrom;
 use serde::{Deserialize, Serialize};
-use crate::model::{string_or_float, string_or_float_opt, string_or_bool};
+use serde_json::{from_value, Value};
+use crate::model::{get_value, string_or_float, string_or_float_opt, string_or_bool};
+use crate::errors::{BinanceContentError, Error, Result};
 
 pub use crate::model::{
     Asks, Bids, BookTickers, Filters, KlineSummaries, KlineSummary, RateLimit, ServerTime,
@@ -181,6 +184,229 @@ pub struct OpenInterestHist {
     pub timestamp: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub symbol: String,
+    pub funding_time: u64,
+    #[serde(with = "string_or_float")]
+    pub funding_rate: f64,
+}
+
+/// Long/short ratio of the top traders accounts or positions, or of all accounts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LongShortRatio {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub long_short_ratio: f64,
+    /// Share of long accounts, or of long positions for the top traders position ratio
+    #[serde(with = "string_or_float", alias = "longPosition")]
+    pub long_account: f64,
+    #[serde(with = "string_or_float", alias = "shortPosition")]
+    pub short_account: f64,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TakerBuySellVolume {
+    #[serde(with = "string_or_float")]
+    pub buy_sell_ratio: f64,
+    #[serde(with = "string_or_float", rename = "buyVol")]
+    pub buy_volume: f64,
+    #[serde(with = "string_or_float", rename = "sellVol")]
+    pub sell_volume: f64,
+    pub timestamp: u64,
+}
+
+/// BLVT NAV kline.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LvtKline {
+    pub open_time: i64,
+
+    pub open: String,
+
+    pub high: String,
+
+    pub low: String,
+
+    pub close: String,
+
+    pub real_leverage: String,
+
+    pub close_time: i64,
+
+    pub number_of_nav_updates: i64,
+}
+
+impl TryFrom<&Vec<Value>> for LvtKline {
+    type Error = Error;
+
+    fn try_from(row: &Vec<Value>) -> Result<Self> {
+        Ok(Self {
+            open_time: from_value(get_value(row, 0, "open_time")?)?,
+            open: from_value(get_value(row, 1, "open")?)?,
+            high: from_value(get_value(row, 2, "high")?)?,
+            low: from_value(get_value(row, 3, "low")?)?,
+            close: from_value(get_value(row, 4, "close")?)?,
+            real_leverage: from_value(get_value(row, 5, "real_leverage")?)?,
+            close_time: from_value(get_value(row, 6, "close_time")?)?,
+            number_of_nav_updates: from_value(get_value(row, 8, "number_of_nav_updates")?)?,
+        })
+    }
+}
+
+/// Composite index and its components.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexInfo {
+    pub symbol: String,
+    pub time: u64,
+    pub component: String,
+    pub base_asset_list: Vec<IndexComponent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexComponent {
+    pub base_asset: String,
+    pub quote_asset: String,
+    #[serde(with = "string_or_float")]
+    pub weight_in_quantity: f64,
+    #[serde(with = "string_or_float")]
+    pub weight_in_percentage: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMarginResponse {
+    pub amount: f64,
+    pub code: i64,
+    pub msg: String,
+    /// 1 when margin was added, 2 when it was reduced
+    #[serde(rename = "type")]
+    pub type_name: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageBracket {
+    pub symbol: String,
+    /// Only set for symbols with a user specific bracket
+    pub notional_coef: Option<f64>,
+    pub brackets: Vec<Bracket>,
+}
+
+impl LeverageBracket {
+    /// Bracket of a position worth `notional`, None above the last notional cap.
+    pub fn bracket(&self, notional: f64) -> Option<&Bracket> {
+        self.brackets
+            .iter()
+            .find(|bracket| notional.abs() <= bracket.notional_cap)
+    }
+
+    /// Highest leverage allowed for a position worth `notional`.
+    pub fn max_leverage(&self, notional: f64) -> Option<u8> {
+        self.bracket(notional).map(|bracket| bracket.initial_leverage)
+    }
+
+    /// Largest position value allowed at `leverage`.
+    pub fn max_notional(&self, leverage: f64) -> Option<f64> {
+        self.brackets
+            .iter()
+            .filter(|bracket| f64::from(bracket.initial_leverage) >= leverage)
+            .map(|bracket| bracket.notional_cap)
+            .fold(None, |max: Option<f64>, cap| Some(max.map_or(cap, |max| max.max(cap))))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bracket {
+    pub bracket: u8,
+    pub initial_leverage: u8,
+    pub notional_cap: f64,
+    pub notional_floor: f64,
+    pub maint_margin_ratio: f64,
+    pub cum: f64,
+}
+
+impl Bracket {
+    /// Maintenance margin of a position worth `notional` in this bracket.
+    pub fn maint_margin(&self, notional: f64) -> f64 {
+        notional.abs() * self.maint_margin_ratio - self.cum
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Income {
+    pub symbol: String,
+    pub income_type: String,
+    #[serde(with = "string_or_float")]
+    pub income: f64,
+    pub asset: String,
+    pub info: String,
+    pub time: u64,
+    pub tran_id: u64,
+    pub trade_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRate {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub maker_commission_rate: f64,
+    #[serde(with = "string_or_float")]
+    pub taker_commission_rate: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AdlQuantile {
+    pub symbol: String,
+    pub adl_quantile: AdlQuantiles,
+}
+
+/// ADL queue position from 0 (low) to 4 (high) of each position side.
+///
+/// In one-way mode and for isolated positions `long`, `short` and `both` are
+/// returned: `both` is the quantile of a one-way position, `long` and `short`
+/// those of the hedge mode sides. Cross positions in hedge mode return `hedge`
+/// instead of `both`, a flag whose value is meaningless; `long` and `short` then
+/// hold the same quantile, computed on the unrealized PnL of both sides.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct AdlQuantiles {
+    pub long: Option<u8>,
+    pub short: Option<u8>,
+    pub both: Option<u8>,
+    pub hedge: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionMode {
+    pub dual_side_position: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAssetsMargin {
+    pub multi_assets_margin: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CountdownCancelAll {
+    pub symbol: String,
+    /// Milliseconds, "0" when the countdown was stopped
+    pub countdown_time: String,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
@@ -214,11 +440,39 @@ pub struct Order {
     pub activation_price: f64,
     #[serde(with = "string_or_float", default = "default_price_rate")]
     pub price_rate: f64,
+    /// Creation time, missing from the order placement responses
+    #[serde(default)]
+    pub time: u64,
     pub update_time: u64,
     pub working_type: String,
+    #[serde(default)]
     pub price_protect: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeHistory {
+    pub symbol: String,
+    pub id: u64,
+    pub order_id: u64,
+    pub side: String,
+    #[serde(with = "string_or_float")]
+    pub price: f64,
+    #[serde(with = "string_or_float")]
+    pub qty: f64,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub realized_pnl: f64,
+    #[serde(with = "string_or_float")]
+    pub commission: f64,
+    pub commission_asset: String,
+    pub time: u64,
+    pub position_side: String,
+    pub buyer: bool,
+    pub maker: bool,
+}
+
 
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -259,6 +513,120 @@ pub struct Transaction {
     pub update_time: u64,
     pub working_type: String,
     pub price_protect: bool,
+    /// "NONE", "OPPONENT", "QUEUE_5"...
+    #[serde(default)]
+    pub price_match: Option<String>,
+}
+
+/// One order of a batch, orders of a batch succeed or fail independently.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BatchOrderResponse {
+    Success(Box<Transaction>),
+    Failure(BinanceContentError),
+}
+
+/// One cancellation of a batch, cancellations of a batch succeed or fail independently.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BatchCancelResponse {
+    Success(Box<CanceledOrder>),
+    Failure(BinanceContentError),
+}
+
+/// Reason the exchange refused to modify an order, the order is left unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmendRejectReason {
+    /// -2013, the order does not exist or is no longer open
+    OrderNotFound,
+    /// -5021, the FOK order could not be filled
+    FillOrKill,
+    /// -5022, the post only order would have been the taker
+    PostOnly,
+    /// -5024, the symbol is not trading
+    SymbolNotTrading,
+    /// -5025, only LIMIT orders can be modified
+    NotLimitOrder,
+    /// -5026, the order was modified too many times
+    TooManyAmendments,
+    /// -5027, price and quantity are the ones of the order
+    NoChange,
+}
+
+impl AmendRejectReason {
+    pub fn from_code(code: i16) -> Option<Self> {
+        match code {
+            -2013 => Some(AmendRejectReason::OrderNotFound),
+            -5021 => Some(AmendRejectReason::FillOrKill),
+            -5022 => Some(AmendRejectReason::PostOnly),
+            -5024 => Some(AmendRejectReason::SymbolNotTrading),
+            -5025 => Some(AmendRejectReason::NotLimitOrder),
+            -5026 => Some(AmendRejectReason::TooManyAmendments),
+            -5027 => Some(AmendRejectReason::NoChange),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of an order modification.
+///
+/// A single modification returns other errors as `Err`, only the orders of a batch
+/// can be `Failed`.
+#[derive(Debug)]
+pub enum ModifyOrderResponse {
+    Modified(Box<Transaction>),
+    Rejected {
+        reason: AmendRejectReason,
+        error: BinanceContentError,
+    },
+    Failed(BinanceContentError),
+}
+
+impl From<BinanceContentError> for ModifyOrderResponse {
+    fn from(error: BinanceContentError) -> Self {
+        match AmendRejectReason::from_code(error.code) {
+            Some(reason) => ModifyOrderResponse::Rejected { reason, error },
+            None => ModifyOrderResponse::Failed(error),
+        }
+    }
+}
+
+impl From<BatchOrderResponse> for ModifyOrderResponse {
+    fn from(response: BatchOrderResponse) -> Self {
+        match response {
+            BatchOrderResponse::Success(transaction) => ModifyOrderResponse::Modified(transaction),
+            BatchOrderResponse::Failure(error) => error.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub amendment_id: u64,
+    pub symbol: String,
+    pub pair: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub time: u64,
+    pub amendment: Amendment,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Amendment {
+    pub price: AmendedValue,
+    pub orig_qty: AmendedValue,
+    /// Number of modifications of the order so far
+    pub count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AmendedValue {
+    #[serde(with = "string_or_float")]
+    pub before: f64,
+    #[serde(with = "string_or_float")]
+    pub after: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -520,7 +888,7 @@ pub struct OrderUpdate {
     #[serde(rename = "L")]
     pub price_last_filled_trade: String,
 
-    #[serde(skip, rename = "N")]
+    #[serde(rename = "N")]
     pub asset_commisioned: Option<String>,
 
     #[serde(rename = "n")]
@@ -590,3 +958,299 @@ pub struct OrderTradeEvent {
     #[serde(rename = "o")]
     pub order: OrderUpdate,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "a")]
+    pub data: AccountUpdateData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateData {
+    /// DEPOSIT, WITHDRAW, ORDER, FUNDING_FEE, MARGIN_TRANSFER, ASSET_TRANSFER...
+    #[serde(rename = "m")]
+    pub reason: String,
+
+    #[serde(rename = "B")]
+    pub balances: Vec<AccountUpdateBalance>,
+
+    #[serde(rename = "P")]
+    pub positions: Vec<AccountUpdatePosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdateBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+
+    #[serde(rename = "wb")]
+    pub wallet_balance: String,
+
+    #[serde(rename = "cw")]
+    pub cross_wallet_balance: String,
+
+    /// Balance change except PnL and commission
+    #[serde(rename = "bc")]
+    pub balance_change: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountUpdatePosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "pa")]
+    pub position_amount: String,
+
+    #[serde(rename = "ep")]
+    pub entry_price: String,
+
+    #[serde(rename = "bep", default)]
+    pub breakeven_price: String,
+
+    /// (Pre-fee) accumulated realized
+    #[serde(rename = "cr")]
+    pub accumulated_realized: String,
+
+    #[serde(rename = "up")]
+    pub unrealized_pnl: String,
+
+    #[serde(rename = "mt")]
+    pub margin_type: String,
+
+    #[serde(rename = "iw")]
+    pub isolated_wallet: String,
+
+    #[serde(rename = "ps")]
+    pub position_side: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    /// Only pushed with crossed positions
+    #[serde(rename = "cw", default)]
+    pub cross_wallet_balance: Option<String>,
+
+    #[serde(rename = "p")]
+    pub positions: Vec<MarginCallPosition>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginCallPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "ps")]
+    pub position_side: String,
+
+    #[serde(rename = "pa")]
+    pub position_amount: String,
+
+    #[serde(rename = "mt")]
+    pub margin_type: String,
+
+    /// Only pushed with isolated positions
+    #[serde(rename = "iw", default)]
+    pub isolated_wallet: String,
+
+    #[serde(rename = "mp")]
+    pub mark_price: String,
+
+    #[serde(rename = "up")]
+    pub unrealized_pnl: String,
+
+    #[serde(rename = "mm")]
+    pub maintenance_margin_required: String,
+}
+
+/// Leverage change of a symbol (`leverage`) or multi-assets mode change (`multi_assets`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountConfigUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "ac", default)]
+    pub leverage: Option<LeverageConfig>,
+
+    #[serde(rename = "ai", default)]
+    pub multi_assets: Option<MultiAssetsConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageConfig {
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "l")]
+    pub leverage: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiAssetsConfig {
+    #[serde(rename = "j")]
+    pub multi_assets_margin: bool,
+}
+
+/// Reduced and faster ORDER_TRADE_UPDATE, only pushed for trades.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeLiteEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "q")]
+    pub qty: String,
+
+    #[serde(rename = "p")]
+    pub price: String,
+
+    #[serde(rename = "m")]
+    pub is_maker: bool,
+
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+
+    #[serde(rename = "S")]
+    pub side: String,
+
+    #[serde(rename = "L")]
+    pub last_filled_price: String,
+
+    #[serde(rename = "l")]
+    pub last_filled_qty: String,
+
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+
+    #[serde(rename = "i")]
+    pub order_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "su")]
+    pub strategy: StrategyUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyUpdate {
+    #[serde(rename = "si")]
+    pub strategy_id: u64,
+
+    #[serde(rename = "st")]
+    pub strategy_type: String,
+
+    /// NEW, WORKING, CANCELLED or EXPIRED
+    #[serde(rename = "ss")]
+    pub strategy_status: String,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "ut")]
+    pub update_time: u64,
+
+    /// Operation code, 8001 to 8010
+    #[serde(rename = "c")]
+    pub op_code: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GridUpdateEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    #[serde(rename = "E")]
+    pub event_time: u64,
+
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+
+    #[serde(rename = "gu")]
+    pub grid: GridUpdate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GridUpdate {
+    #[serde(rename = "si")]
+    pub strategy_id: u64,
+
+    #[serde(rename = "st")]
+    pub strategy_type: String,
+
+    #[serde(rename = "ss")]
+    pub strategy_status: String,
+
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    #[serde(rename = "r")]
+    pub realized_pnl: String,
+
+    #[serde(rename = "up")]
+    pub unmatched_average_price: String,
+
+    #[serde(rename = "uq")]
+    pub unmatched_qty: String,
+
+    #[serde(rename = "uf")]
+    pub unmatched_fee: String,
+
+    #[serde(rename = "mp")]
+    pub matched_pnl: String,
+
+    #[serde(rename = "ut")]
+    pub update_time: u64,
+}
@@ -0,0 +1,478 @@
+/*!
+Live USD-M futures positions and balances.
+
+`PositionTracker` is seeded with `FuturesAccount::position_information` and
+`account_information`, then kept up to date with the user data stream
+(`ACCOUNT_UPDATE`, `ORDER_TRADE_UPDATE`, `ACCOUNT_CONFIG_UPDATE`) and marked to
+market with the mark price stream.
+
+Fills are applied as they arrive, unless an `ACCOUNT_UPDATE` with the same or a
+later transaction time already reported the position. Liquidation prices are
+recomputed with the margin calculator from the leverage brackets loaded by
+`refresh` (or `set_leverage_brackets`) as positions, wallets and mark prices
+change.
+*/
+
+use std::collections::HashMap;
+
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+use crate::futures::calculator::{liquidation_price, CalculatorPosition, MarginWallet};
+use crate::futures::model::{
+    AccountConfigUpdateEvent, AccountInformation, AccountUpdateEvent, LeverageBracket,
+    OrderTradeEvent, PositionRisk,
+};
+use crate::futures::websockets::FuturesWebsocketEvent;
+use crate::model::MarkPriceEvent;
+
+/// Position of one symbol and position side ("BOTH", "LONG" or "SHORT").
+#[derive(Debug, Clone)]
+pub struct TrackedPosition {
+    pub symbol: String,
+    pub position_side: String,
+    /// Negative for short positions
+    pub position_amount: f64,
+    pub entry_price: f64,
+    pub leverage: f64,
+    /// "cross" or "isolated"
+    pub margin_type: String,
+    pub isolated_wallet: f64,
+    pub accumulated_realized: f64,
+    pub mark_price: Option<f64>,
+    /// Returned by the REST API, then recomputed as the position changes. Cleared
+    /// when it changes and the leverage brackets of the symbol are unknown.
+    pub liquidation_price: Option<f64>,
+    pub update_time: u64,
+    /// Transaction time of the last ACCOUNT_UPDATE or REST update of the position,
+    /// fills up to this time are already counted
+    pub confirmed_time: u64,
+}
+
+impl TrackedPosition {
+    pub fn is_open(&self) -> bool {
+        self.position_amount != 0.0
+    }
+
+    pub fn is_isolated(&self) -> bool {
+        self.margin_type == "isolated"
+    }
+
+    /// Absolute value of the position at the mark price.
+    pub fn notional(&self) -> Option<f64> {
+        self.mark_price
+            .map(|mark_price| self.position_amount.abs() * mark_price)
+    }
+
+    pub fn unrealized_pnl(&self) -> Option<f64> {
+        self.mark_price
+            .map(|mark_price| self.position_amount * (mark_price - self.entry_price))
+    }
+
+    /// Margin used to open the position at its leverage.
+    pub fn initial_margin(&self) -> f64 {
+        if self.leverage > 0.0 {
+            self.position_amount.abs() * self.entry_price / self.leverage
+        } else {
+            0.0
+        }
+    }
+
+    /// Return on equity, the unrealized PnL over the initial margin (0.1 is 10%).
+    pub fn roe(&self) -> Option<f64> {
+        let initial_margin = self.initial_margin();
+        if initial_margin == 0.0 {
+            return None;
+        }
+        self.unrealized_pnl().map(|pnl| pnl / initial_margin)
+    }
+
+    /// Relative distance from the mark price to the liquidation price (0.05 is 5%).
+    pub fn liquidation_distance(&self) -> Option<f64> {
+        match (self.mark_price, self.liquidation_price) {
+            (Some(mark_price), Some(liquidation_price))
+                if mark_price > 0.0 && liquidation_price > 0.0 && self.is_open() =>
+            {
+                Some((mark_price - liquidation_price).abs() / mark_price)
+            }
+            _ => None,
+        }
+    }
+
+    fn calculator_position(&self) -> CalculatorPosition {
+        CalculatorPosition::new(
+            self.symbol.clone(),
+            self.position_amount,
+            self.entry_price,
+            self.leverage,
+        )
+    }
+
+    // Fill of `signed_qty` (negative when selling) at `price`
+    fn apply_fill(&mut self, signed_qty: f64, price: f64) {
+        let amount = self.position_amount + signed_qty;
+        let increased =
+            self.position_amount == 0.0 || (self.position_amount > 0.0) == (signed_qty > 0.0);
+        let flipped = !increased && amount != 0.0 && (amount > 0.0) != (self.position_amount > 0.0);
+        if increased {
+            self.entry_price = (self.position_amount.abs() * self.entry_price
+                + signed_qty.abs() * price)
+                / amount.abs();
+        } else if amount == 0.0 {
+            self.entry_price = 0.0;
+        } else if flipped {
+            // Flipped, the remaining quantity was opened at `price`
+            self.entry_price = price;
+        }
+
+        // Estimated until the ACCOUNT_UPDATE: the opened quantity adds its initial
+        // margin, the closed quantity releases its share of the margin
+        if self.is_isolated() && self.leverage > 0.0 {
+            if increased {
+                self.isolated_wallet += signed_qty.abs() * price / self.leverage;
+            } else if flipped || amount == 0.0 {
+                self.isolated_wallet = amount.abs() * price / self.leverage;
+            } else {
+                self.isolated_wallet *= amount.abs() / self.position_amount.abs();
+            }
+        }
+
+        self.position_amount = amount;
+        self.liquidation_price = None;
+    }
+}
+
+/// Futures wallet of one asset.
+#[derive(Debug, Clone)]
+pub struct TrackedBalance {
+    pub asset: String,
+    pub wallet_balance: f64,
+    pub cross_wallet_balance: f64,
+    /// As of the last `refresh`
+    pub available_balance: f64,
+    /// As of the last `refresh`
+    pub initial_margin: f64,
+    /// As of the last `refresh`
+    pub maint_margin: f64,
+    pub update_time: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PositionTracker {
+    positions: HashMap<(String, String), TrackedPosition>,
+    balances: HashMap<String, TrackedBalance>,
+    mark_prices: HashMap<String, f64>,
+    brackets: HashMap<String, LeverageBracket>,
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tracker seeded with the positions and balances of `account`.
+    pub fn from_account(account: &FuturesAccount) -> Result<Self> {
+        let mut tracker = Self::new();
+        tracker.refresh(account)?;
+        Ok(tracker)
+    }
+
+    /// Reload positions, balances and leverage brackets, e.g. after the user data
+    /// stream reconnected.
+    pub fn refresh(&mut self, account: &FuturesAccount) -> Result<()> {
+        let information = account.account_information()?;
+        let position_risks = account.all_position_information()?;
+        let brackets = account.leverage_brackets(None)?;
+        self.apply_account_information(&information);
+        self.apply_position_risks(&position_risks)?;
+        self.set_leverage_brackets(brackets);
+        Ok(())
+    }
+
+    /// Leverage brackets used to recompute the liquidation prices, see `refresh`.
+    pub fn set_leverage_brackets(&mut self, brackets: Vec<LeverageBracket>) {
+        for bracket in brackets {
+            self.brackets.insert(bracket.symbol.clone(), bracket);
+        }
+    }
+
+    pub fn apply_account_information(&mut self, information: &AccountInformation) {
+        for asset in &information.assets {
+            self.balances.insert(
+                asset.asset.clone(),
+                TrackedBalance {
+                    asset: asset.asset.clone(),
+                    wallet_balance: asset.wallet_balance,
+                    cross_wallet_balance: asset.cross_wallet_balance,
+                    available_balance: asset.available_balance,
+                    initial_margin: asset.initial_margin,
+                    maint_margin: asset.maint_margin,
+                    update_time: asset.update_time,
+                },
+            );
+        }
+    }
+
+    pub fn apply_position_risks(&mut self, position_risks: &[PositionRisk]) -> Result<()> {
+        for risk in position_risks {
+            let leverage = risk.leverage.parse()?;
+            let position = self.position_entry(&risk.symbol, &risk.position_side);
+            position.position_amount = risk.position_amount;
+            position.entry_price = risk.entry_price;
+            position.leverage = leverage;
+            position.margin_type = risk.margin_type.clone();
+            position.isolated_wallet = risk.isolated_wallet;
+            position.liquidation_price = Some(risk.liquidation_price);
+            position.update_time = risk.update_time;
+            position.confirmed_time = position.confirmed_time.max(risk.update_time);
+            if risk.mark_price > 0.0 {
+                self.set_mark_price(&risk.symbol, risk.mark_price);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the user data and mark price events, other events are ignored.
+    pub fn handle_event(&mut self, event: &FuturesWebsocketEvent) -> Result<()> {
+        match event {
+            FuturesWebsocketEvent::AccountUpdate(event) => self.on_account_update(event),
+            FuturesWebsocketEvent::OrderTrade(event) => self.on_order_trade(event),
+            FuturesWebsocketEvent::AccountConfigUpdate(event) => {
+                self.on_account_config_update(event);
+                Ok(())
+            }
+            FuturesWebsocketEvent::MarkPrice(event) => self.on_mark_price(event),
+            FuturesWebsocketEvent::MarkPriceAll(events) => {
+                for event in events {
+                    self.on_mark_price(event)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Balances and positions as reported by the exchange, they override tracked fills.
+    pub fn on_account_update(&mut self, event: &AccountUpdateEvent) -> Result<()> {
+        for balance in &event.data.balances {
+            let wallet_balance = balance.wallet_balance.parse()?;
+            let cross_wallet_balance = balance.cross_wallet_balance.parse()?;
+            let tracked = self
+                .balances
+                .entry(balance.asset.clone())
+                .or_insert_with(|| TrackedBalance {
+                    asset: balance.asset.clone(),
+                    wallet_balance: 0.0,
+                    cross_wallet_balance: 0.0,
+                    available_balance: 0.0,
+                    initial_margin: 0.0,
+                    maint_margin: 0.0,
+                    update_time: 0,
+                });
+            tracked.wallet_balance = wallet_balance;
+            tracked.cross_wallet_balance = cross_wallet_balance;
+            tracked.update_time = event.event_time;
+        }
+
+        for update in &event.data.positions {
+            let position_amount: f64 = update.position_amount.parse()?;
+            let entry_price: f64 = update.entry_price.parse()?;
+            let accumulated_realized = update.accumulated_realized.parse()?;
+            let isolated_wallet = update.isolated_wallet.parse()?;
+
+            let position = self.position_entry(&update.symbol, &update.position_side);
+            if position.position_amount != position_amount || position.entry_price != entry_price
+            {
+                position.liquidation_price = None;
+            }
+            position.position_amount = position_amount;
+            position.entry_price = entry_price;
+            position.accumulated_realized = accumulated_realized;
+            position.isolated_wallet = isolated_wallet;
+            position.margin_type = update.margin_type.clone();
+            position.update_time = event.event_time;
+            position.confirmed_time = position.confirmed_time.max(event.transaction_time);
+        }
+
+        self.update_liquidation_prices(|_| true);
+        Ok(())
+    }
+
+    /// Fills, applied before the ACCOUNT_UPDATE confirming them arrives. Fills the
+    /// last ACCOUNT_UPDATE of the position already counted are skipped.
+    pub fn on_order_trade(&mut self, event: &OrderTradeEvent) -> Result<()> {
+        let order = &event.order;
+        if order.execution_type != "TRADE" {
+            return Ok(());
+        }
+
+        let qty: f64 = order.qty_last_filled_trade.parse()?;
+        let price: f64 = order.price_last_filled_trade.parse()?;
+        let realized: f64 = order.realized_profit.parse()?;
+        let signed_qty = if order.side == "SELL" { -qty } else { qty };
+
+        let position = self.position_entry(&order.symbol, &order.position_side);
+        if event.transaction_time <= position.confirmed_time {
+            return Ok(());
+        }
+        position.apply_fill(signed_qty, price);
+        position.accumulated_realized += realized;
+        position.update_time = event.event_time;
+
+        self.update_liquidation_prices(|_| true);
+        Ok(())
+    }
+
+    pub fn on_account_config_update(&mut self, event: &AccountConfigUpdateEvent) {
+        if let Some(ref config) = event.leverage {
+            for position in self.positions.values_mut() {
+                if position.symbol == config.symbol {
+                    position.leverage = f64::from(config.leverage);
+                    position.liquidation_price = None;
+                }
+            }
+            let symbol = config.symbol.clone();
+            self.update_liquidation_prices(|position| position.symbol == symbol);
+        }
+    }
+
+    pub fn on_mark_price(&mut self, event: &MarkPriceEvent) -> Result<()> {
+        let mark_price = event.mark_price.parse()?;
+        self.set_mark_price(&event.symbol, mark_price);
+
+        // The unrealized PnL of the symbol moves the cross positions of the others
+        let symbol = event.symbol.clone();
+        self.update_liquidation_prices(|position| {
+            position.symbol != symbol && !position.is_isolated()
+        });
+        Ok(())
+    }
+
+    pub fn position(&self, symbol: &str, position_side: &str) -> Option<&TrackedPosition> {
+        self.positions
+            .get(&(symbol.to_owned(), position_side.to_owned()))
+    }
+
+    /// Every tracked position, closed ones included.
+    pub fn positions(&self) -> Vec<&TrackedPosition> {
+        self.positions.values().collect()
+    }
+
+    pub fn open_positions(&self) -> Vec<&TrackedPosition> {
+        self.positions
+            .values()
+            .filter(|position| position.is_open())
+            .collect()
+    }
+
+    pub fn balance(&self, asset: &str) -> Option<&TrackedBalance> {
+        self.balances.get(asset)
+    }
+
+    pub fn balances(&self) -> Vec<&TrackedBalance> {
+        self.balances.values().collect()
+    }
+
+    pub fn mark_price(&self, symbol: &str) -> Option<f64> {
+        self.mark_prices.get(symbol).copied()
+    }
+
+    /// Unrealized PnL of the open positions with a known mark price.
+    pub fn total_unrealized_pnl(&self) -> f64 {
+        self.positions
+            .values()
+            .filter_map(|position| position.unrealized_pnl())
+            .sum()
+    }
+
+    // Recompute the liquidation prices of the positions matching `filter`, the ones
+    // without leverage brackets are left as they are
+    fn update_liquidation_prices<F>(&mut self, filter: F)
+    where
+        F: Fn(&TrackedPosition) -> bool,
+    {
+        let prices: Vec<((String, String), Option<f64>)> = self
+            .positions
+            .iter()
+            .filter(|(_, position)| filter(position))
+            .filter_map(|(key, position)| {
+                Some((key.clone(), self.compute_liquidation_price(position)?))
+            })
+            .collect();
+        for (key, price) in prices {
+            if let Some(position) = self.positions.get_mut(&key) {
+                position.liquidation_price = price;
+            }
+        }
+    }
+
+    // Liquidation price from the tracked positions and wallets, None when it can't be
+    // computed, Some(None) when the position can't be liquidated
+    fn compute_liquidation_price(&self, position: &TrackedPosition) -> Option<Option<f64>> {
+        if !position.is_open() {
+            return Some(None);
+        }
+        let brackets = self.brackets.get(&position.symbol)?;
+        if position.is_isolated() {
+            let wallet = MarginWallet::isolated(position.isolated_wallet);
+            return liquidation_price(&[position.calculator_position()], brackets, &wallet).ok();
+        }
+
+        // Cross positions share the wallet of the margin asset with the other symbols
+        let balance = self
+            .balances
+            .values()
+            .filter(|balance| position.symbol.ends_with(&balance.asset))
+            .max_by_key(|balance| balance.asset.len())?;
+        let mut positions = Vec::new();
+        let mut other_maint_margin = 0.0;
+        let mut other_unrealized_pnl = 0.0;
+        for other in self.positions.values() {
+            if !other.is_open() || other.is_isolated() || !other.symbol.ends_with(&balance.asset) {
+                continue;
+            }
+            if other.symbol == position.symbol {
+                positions.push(other.calculator_position());
+            } else {
+                let brackets = self.brackets.get(&other.symbol)?;
+                other_maint_margin += other.calculator_position().maint_margin(brackets).ok()?;
+                other_unrealized_pnl += other.unrealized_pnl().unwrap_or(0.0);
+            }
+        }
+
+        let wallet = MarginWallet::cross(
+            balance.cross_wallet_balance,
+            other_maint_margin,
+            other_unrealized_pnl,
+        );
+        liquidation_price(&positions, brackets, &wallet).ok()
+    }
+
+    fn set_mark_price(&mut self, symbol: &str, mark_price: f64) {
+        self.mark_prices.insert(symbol.to_owned(), mark_price);
+        for position in self.positions.values_mut() {
+            if position.symbol == symbol {
+                position.mark_price = Some(mark_price);
+            }
+        }
+    }
+
+    fn position_entry(&mut self, symbol: &str, position_side: &str) -> &mut TrackedPosition {
+        let mark_price = self.mark_prices.get(symbol).copied();
+        self.positions
+            .entry((symbol.to_owned(), position_side.to_owned()))
+            .or_insert_with(|| TrackedPosition {
+                symbol: symbol.to_owned(),
+                position_side: position_side.to_owned(),
+                position_amount: 0.0,
+                entry_price: 0.0,
+                leverage: 0.0,
+                margin_type: String::new(),
+                isolated_wallet: 0.0,
+                accumulated_realized: 0.0,
+                mark_price,
+                liquidation_price: None,
+                update_time: 0,
+                confirmed_time: 0,
+            })
+    }
+}
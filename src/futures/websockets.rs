@@ -2,19 +2,19 @@ use crate::errors::*;
 use crate::config::*;
 use crate::model::*;
 use crate::futures::model;
-use url::Url;
+use crate::proxy::Proxy;
+use crate::stream_stats::{event_time, fallback_stream_name, StaleAction, StaleWatch, StreamMonitor};
 use serde::{Deserialize, Serialize};
+use crate::websockets::{close_socket, connect_socket, StopHandle};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::net::TcpStream;
 
 use std::time::Duration;
 
-use native_tls::{TlsConnector, TlsStream};
-use tungstenite::client;
-
 use tungstenite::Message;
 use tungstenite::protocol::WebSocket;
 use tungstenite::handshake::client::Response;
+use tungstenite::stream::MaybeTlsStream;
 #[allow(clippy::all)]
 enum FuturesWebsocketAPI {
     Default,
@@ -51,8 +51,13 @@ impl FuturesWebsocketAPI {
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum FuturesWebsocketEvent {
-    AccountUpdate(AccountUpdateEvent),
+    AccountUpdate(model::AccountUpdateEvent),
     OrderTrade(model::OrderTradeEvent),
+    MarginCall(model::MarginCallEvent),
+    AccountConfigUpdate(model::AccountConfigUpdateEvent),
+    TradeLite(model::TradeLiteEvent),
+    StrategyUpdate(model::StrategyUpdateEvent),
+    GridUpdate(model::GridUpdateEvent),
     AggrTrades(AggrTradesEvent),
     Trade(TradeEvent),
     OrderBook(OrderBook),
@@ -73,8 +78,14 @@ pub enum FuturesWebsocketEvent {
 }
 
 pub struct FuturesWebSockets<'a> {
-    pub socket: Option<(WebSocket<TlsStream<TcpStream>>, Response)>,
+    pub socket: Option<(WebSocket<MaybeTlsStream<TcpStream>>, Response)>,
     handler: Box<dyn FnMut(FuturesWebsocketEvent) -> Result<()> + 'a>,
+    stop_handle: StopHandle,
+    monitor: StreamMonitor,
+    stream_name: Option<String>,
+    url: Option<String>,
+    proxy: Option<Proxy>,
+    stale: StaleWatch<'a>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -85,7 +96,6 @@ enum FuturesEvents {
     BookTickerEvent(BookTickerEvent),
     MiniTickerEvent(MiniTickerEvent),
     VecMiniTickerEvent(Vec<MiniTickerEvent>),
-    AccountUpdateEvent(AccountUpdateEvent),
     OrderTradeEvent(model::OrderTradeEvent),
     AggrTradesEvent(AggrTradesEvent),
     IndexPriceEvent(IndexPriceEvent),
@@ -109,37 +119,97 @@ impl<'a> FuturesWebSockets<'a> {
         FuturesWebSockets {
             socket: None,
             handler: Box::new(handler),
+            stop_handle: StopHandle::default(),
+            monitor: StreamMonitor::new(),
+            stream_name: None,
+            url: None,
+            proxy: None,
+            stale: StaleWatch::default(),
         }
     }
 
+    /// Connect through `proxy`, a proxy set in the `Config` of `connect_with_config` takes precedence.
+    pub fn set_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Report streams which received nothing for longer than `threshold`.
+    ///
+    /// Without an `on_stale` callback a stale stream reconnects the websocket.
+    pub fn set_stale_threshold(mut self, threshold: Duration) -> Self {
+        self.stale.set_threshold(threshold);
+        self
+    }
+
+    /// Callback deciding what to do with a stale stream, it receives the stream name
+    /// and the time since its last message.
+    pub fn on_stale<Callback>(mut self, callback: Callback) -> Self
+    where
+        Callback: FnMut(&str, Duration) -> StaleAction + 'a,
+    {
+        self.stale.set_handler(Box::new(callback));
+        self
+    }
+
+    /// Handle to stop `event_loop` from another thread.
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop_handle.clone()
+    }
+
+    /// Per-stream latency, rate and last-message statistics.
+    pub fn monitor(&self) -> StreamMonitor {
+        self.monitor.clone()
+    }
+
     pub fn connect<T: Into<String>>(&mut self, market: FuturesMarket, subscription: T) -> Result<()> {
-        self.connect_wss(FuturesWebsocketAPI::Default.params(market, &subscription.into()))
+        let subscription = subscription.into();
+        self.stream_name = Some(subscription.clone());
+        self.monitor.watch(std::slice::from_ref(&subscription));
+        self.connect_wss(FuturesWebsocketAPI::Default.params(market, &subscription))
     }
 
     pub fn connect_with_config<T: Into<String>>(
         &mut self, market: FuturesMarket, subscription: T, config: &Config,
     ) -> Result<()> {
+        let subscription = subscription.into();
+        self.stream_name = Some(subscription.clone());
+        self.monitor.watch(std::slice::from_ref(&subscription));
+        if config.proxy.is_some() {
+            self.proxy = config.proxy.clone();
+        }
         self.connect_wss(
-            FuturesWebsocketAPI::Custom(config.ws_endpoint.clone()).params(market, &subscription.into()),
+            FuturesWebsocketAPI::Custom(config.ws_endpoint.clone()).params(market, &subscription),
         )
     }
 
     pub fn connect_multiple_streams(
         &mut self, market: FuturesMarket, endpoints: &[String],
     ) -> Result<()> {
+        self.stream_name = None;
+        self.monitor.watch(endpoints);
         self.connect_wss(FuturesWebsocketAPI::MultiStream.params(market, &endpoints.join("/")))
     }
 
     fn connect_wss(&mut self, wss: String) -> Result<()> {
-        let url = Url::parse(&wss)?;
-        let host = url.host_str().unwrap();
-        let port = url.port().unwrap_or(443);
-        let connector = TlsConnector::new()?;
-        let tcp_stream = TcpStream::connect((host, port))?;
-        tcp_stream.set_read_timeout(Some(Duration::from_secs(10)))?; // Установите желаемый таймаут
-        let tls_stream = connector.connect(host, tcp_stream)?;
-        let (socket, response) = client(url, tls_stream)?;
+        let read_timeout = self.stale.read_timeout();
+        let (socket, response) = connect_socket(&wss, read_timeout, self.proxy.as_ref())?;
+        self.stop_handle.attach(&socket)?;
         self.socket = Some((socket, response));
+        self.url = Some(wss);
+        Ok(())
+    }
+
+    fn check_stale_streams(&mut self) -> Result<()> {
+        if self.stale.check(&self.monitor)? {
+            if let Some(url) = self.url.clone() {
+                if let Some(ref mut socket) = self.socket {
+                    close_socket(&mut socket.0);
+                }
+                self.connect_wss(url)?;
+                self.monitor.rewatch();
+            }
+        }
         Ok(())
     }
 
@@ -156,13 +226,33 @@ impl<'a> FuturesWebSockets<'a> {
     }
 
     fn handle_msg(&mut self, msg: &str) -> Result<()> {
-        let value: serde_json::Value = serde_json::from_str(msg)?;
+        let mut value: serde_json::Value = serde_json::from_str(msg)?;
 
-        if let Some(data) = value.get("data") {
-            self.handle_msg(&data.to_string())?;
-            return Ok(());
+        if let Some(data) = value.get_mut("data") {
+            let data = data.take();
+            let stream = match value.get("stream").and_then(|stream| stream.as_str()) {
+                Some(stream) => stream.to_owned(),
+                None => fallback_stream_name(&data),
+            };
+            self.monitor.record(&stream, event_time(&data));
+            return self.handle_event(data);
         }
 
+        let stream = match self.stream_name {
+            Some(ref name) => name.clone(),
+            None => fallback_stream_name(&value),
+        };
+        self.monitor.record(&stream, event_time(&value));
+        self.handle_event(value)
+    }
+
+    fn handle_event(&mut self, value: serde_json::Value) -> Result<()> {
+        match user_data_event(&value) {
+            Some(Ok(event)) => return (self.handler)(event),
+            // Like the market events, a user data event which fails to decode is skipped
+            Some(Err(_)) => return Ok(()),
+            None => (),
+        }
 
         if let Ok(events) = serde_json::from_value::<FuturesEvents>(value) {
             let action = match events {
@@ -171,7 +261,6 @@ impl<'a> FuturesWebSockets<'a> {
                 FuturesEvents::BookTickerEvent(v) => FuturesWebsocketEvent::BookTicker(v),
                 FuturesEvents::MiniTickerEvent(v) => FuturesWebsocketEvent::MiniTicker(v),
                 FuturesEvents::VecMiniTickerEvent(v) => FuturesWebsocketEvent::MiniTickerAll(v),
-                FuturesEvents::AccountUpdateEvent(v) => FuturesWebsocketEvent::AccountUpdate(v),
                 FuturesEvents::OrderTradeEvent(v) => FuturesWebsocketEvent::OrderTrade(v),
                 FuturesEvents::IndexPriceEvent(v) => FuturesWebsocketEvent::IndexPrice(v),
                 FuturesEvents::MarkPriceEvent(v) => FuturesWebsocketEvent::MarkPrice(v),
@@ -193,12 +282,10 @@ impl<'a> FuturesWebSockets<'a> {
         Ok(())
     }
 
-
-
     pub fn event_loop(&mut self, should_stop: &AtomicBool) -> Result<()> {
         let mut ping_counter = 0;
 
-        while !should_stop.load(Ordering::Relaxed) {
+        while !should_stop.load(Ordering::Relaxed) && !self.stop_handle.is_stopped() {
             if let Some(ref mut socket) = self.socket {
                 let message = socket.0.read_message();
                 match message {
@@ -217,6 +304,7 @@ impl<'a> FuturesWebSockets<'a> {
                         Message::Binary(_) => (),
                         Message::Close(e) => bail!(format!("Disconnected {:?}", e)),
                     },
+                    Err(_) if self.stop_handle.is_stopped() => break,
                     Err(error) => {
                         // Таймаут истек; вы можете обработать эту ситуацию, например, закрыть соединение
                         // отправляем 3 пинга если нет ответа - ошибка
@@ -231,7 +319,38 @@ impl<'a> FuturesWebSockets<'a> {
                     }
                 }
             }
+            self.check_stale_streams()?;
         }
-        bail!("running loop closed");
+
+        if let Some(ref mut socket) = self.socket {
+            close_socket(&mut socket.0);
+        }
+        Ok(())
     }
 }
+
+// User data events share too many fields to be told apart by the untagged `FuturesEvents`,
+// they are decoded from their event type instead
+fn user_data_event(
+    value: &serde_json::Value,
+) -> Option<serde_json::Result<FuturesWebsocketEvent>> {
+    let event = match value.get("e").and_then(|event_type| event_type.as_str()) {
+        Some("ACCOUNT_UPDATE") => model::AccountUpdateEvent::deserialize(value)
+            .map(FuturesWebsocketEvent::AccountUpdate),
+        Some("MARGIN_CALL") => {
+            model::MarginCallEvent::deserialize(value).map(FuturesWebsocketEvent::MarginCall)
+        }
+        Some("ACCOUNT_CONFIG_UPDATE") => model::AccountConfigUpdateEvent::deserialize(value)
+            .map(FuturesWebsocketEvent::AccountConfigUpdate),
+        Some("TRADE_LITE") => {
+            model::TradeLiteEvent::deserialize(value).map(FuturesWebsocketEvent::TradeLite)
+        }
+        Some("STRATEGY_UPDATE") => model::StrategyUpdateEvent::deserialize(value)
+            .map(FuturesWebsocketEvent::StrategyUpdate),
+        Some("GRID_UPDATE") => {
+            model::GridUpdateEvent::deserialize(value).map(FuturesWebsocketEvent::GridUpdate)
+        }
+        _ => return None,
+    };
+    Some(event)
+}
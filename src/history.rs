@@ -0,0 +1,394 @@
+/*!
+Trade and order history queries.
+
+`HistoryQuery` carries the optional filters of the history endpoints (`myTrades`,
+`allOrders`, `userTrades`...). The `*_iter` methods of `Account`, `MarginAccount`
+and `FuturesAccount` return a `HistoryIter`, which pages through the whole range
+one request at a time:
+
+* without `start_time` the records are walked by id from `from_id` (0, the
+  oldest record, by default);
+* with `start_time` the range up to `end_time` (now by default) is cut into the
+  longest window accepted by the endpoint, 24 hours for spot and margin, 7 days
+  for futures, and each window is walked by time. A full page of records sharing
+  one millisecond can't be split by time, the rest of that millisecond is walked
+  by id before going on by time.
+
+Requests are spaced by `set_pause` and retried after `set_backoff` when the
+exchange answers "Too many requests" (-1003).
+*/
+
+use std::collections::{BTreeMap, VecDeque};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::errors::*;
+use crate::futures::model::{Order as FuturesOrder, TradeHistory as FuturesTradeHistory};
+use crate::model::{Order, TradeHistory};
+
+pub(crate) const DAY: u64 = 24 * 60 * 60 * 1000;
+
+// "Too many requests" and "IP banned", both answered with -1003
+const TOO_MANY_REQUESTS: i16 = -1003;
+
+#[derive(Debug, Clone, Default)]
+pub struct HistoryQuery {
+    pub symbol: String,
+    /// Trades or allocations of ONE order, ignored by the order history
+    pub order_id: Option<u64>,
+    /// First trade id, first order id for the order history, first allocation id
+    /// for the allocations
+    pub from_id: Option<u64>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub limit: Option<u16>,
+}
+
+impl HistoryQuery {
+    pub fn new<S>(symbol: S) -> Self
+    where
+        S: Into<String>,
+    {
+        HistoryQuery {
+            symbol: symbol.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_order_id(mut self, order_id: u64) -> Self {
+        self.order_id = Some(order_id);
+        self
+    }
+
+    pub fn set_from_id(mut self, from_id: u64) -> Self {
+        self.from_id = Some(from_id);
+        self
+    }
+
+    pub fn set_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    pub fn set_end_time(mut self, end_time: u64) -> Self {
+        self.end_time = Some(end_time);
+        self
+    }
+
+    pub fn set_limit(mut self, limit: u16) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    // Parameters of the trade history endpoints
+    pub(crate) fn trade_parameters(&self) -> BTreeMap<String, String> {
+        let mut parameters = self.time_parameters();
+        if let Some(order_id) = self.order_id {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(from_id) = self.from_id {
+            parameters.insert("fromId".into(), from_id.to_string());
+        }
+        parameters
+    }
+
+    // Parameters of the order history endpoints, orders are listed from `orderId`
+    pub(crate) fn order_parameters(&self) -> BTreeMap<String, String> {
+        let mut parameters = self.time_parameters();
+        if let Some(from_id) = self.from_id {
+            parameters.insert("orderId".into(), from_id.to_string());
+        }
+        parameters
+    }
+
+    // Parameters of the SOR allocations endpoint
+    pub(crate) fn allocation_parameters(&self) -> BTreeMap<String, String> {
+        let mut parameters = self.time_parameters();
+        if let Some(order_id) = self.order_id {
+            parameters.insert("orderId".into(), order_id.to_string());
+        }
+        if let Some(from_id) = self.from_id {
+            parameters.insert("fromAllocationId".into(), from_id.to_string());
+        }
+        parameters
+    }
+
+    fn time_parameters(&self) -> BTreeMap<String, String> {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), self.symbol.clone());
+        if let Some(st) = self.start_time {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = self.end_time {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+        if let Some(lt) = self.limit {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        parameters
+    }
+}
+
+/// Id and time used to page through the records.
+pub trait HistoryRecord {
+    fn record_id(&self) -> u64;
+    fn record_time(&self) -> u64;
+}
+
+impl HistoryRecord for TradeHistory {
+    fn record_id(&self) -> u64 {
+        self.id
+    }
+
+    fn record_time(&self) -> u64 {
+        self.time
+    }
+}
+
+impl HistoryRecord for Order {
+    fn record_id(&self) -> u64 {
+        self.order_id
+    }
+
+    fn record_time(&self) -> u64 {
+        self.time
+    }
+}
+
+impl HistoryRecord for FuturesTradeHistory {
+    fn record_id(&self) -> u64 {
+        self.id
+    }
+
+    fn record_time(&self) -> u64 {
+        self.time
+    }
+}
+
+impl HistoryRecord for FuturesOrder {
+    fn record_id(&self) -> u64 {
+        self.order_id
+    }
+
+    fn record_time(&self) -> u64 {
+        self.time
+    }
+}
+
+type Fetch<T> = Box<dyn FnMut(&HistoryQuery) -> Result<Vec<T>>>;
+
+/// Records of a history query, fetched page by page.
+pub struct HistoryIter<T> {
+    fetch: Fetch<T>,
+    query: HistoryQuery,
+    limit: usize,
+    window: u64,
+    // End of the whole range when walking by time
+    end_time: u64,
+    last_id: Option<u64>,
+    // Millisecond walked by id, and the end of its window
+    same_millisecond: Option<(u64, u64)>,
+    buffer: VecDeque<T>,
+    requests: usize,
+    done: bool,
+    pause: Duration,
+    backoff: Duration,
+    max_retries: usize,
+}
+
+impl<T: HistoryRecord> HistoryIter<T> {
+    pub(crate) fn new<F>(query: HistoryQuery, max_limit: u16, window: u64, fetch: F) -> Self
+    where
+        F: FnMut(&HistoryQuery) -> Result<Vec<T>> + 'static,
+    {
+        let mut query = query;
+        let limit = query.limit.unwrap_or(max_limit).min(max_limit);
+        query.limit = Some(limit);
+
+        let mut end_time = 0;
+        match query.start_time {
+            Some(start_time) => {
+                end_time = query.end_time.unwrap_or_else(now);
+                query.from_id = None;
+                query.end_time = Some(window_end(start_time, window, end_time));
+            }
+            None => {
+                query.end_time = None;
+                query.from_id = Some(query.from_id.unwrap_or(0));
+            }
+        }
+
+        HistoryIter {
+            fetch: Box::new(fetch),
+            query,
+            limit: limit as usize,
+            window,
+            end_time,
+            last_id: None,
+            same_millisecond: None,
+            buffer: VecDeque::new(),
+            requests: 0,
+            done: false,
+            pause: Duration::from_millis(0),
+            backoff: Duration::from_secs(60),
+            max_retries: 3,
+        }
+    }
+
+    /// Wait between two requests.
+    pub fn set_pause(mut self, pause: Duration) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Wait before retrying a request rejected by the rate limits, `max_retries`
+    /// times at most.
+    pub fn set_backoff(mut self, backoff: Duration, max_retries: usize) -> Self {
+        self.backoff = backoff;
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Requests sent so far.
+    pub fn requests(&self) -> usize {
+        self.requests
+    }
+
+    fn request(&mut self) -> Result<Vec<T>> {
+        if self.requests > 0 && self.pause > Duration::from_millis(0) {
+            thread::sleep(self.pause);
+        }
+        let mut retries = 0;
+        loop {
+            self.requests += 1;
+            match (self.fetch)(&self.query) {
+                Err(Error(ErrorKind::BinanceError(ref response), _))
+                    if response.code == TOO_MANY_REQUESTS && retries < self.max_retries =>
+                {
+                    retries += 1;
+                    thread::sleep(self.backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Fetch the next page and move the query after it
+    fn next_page(&mut self) -> Result<()> {
+        let page = self.request()?;
+        let full = page.len() >= self.limit;
+        let last = page
+            .last()
+            .map(|record| (record.record_id(), record.record_time()));
+
+        if let Some((time, current_end)) = self.same_millisecond {
+            self.next_millisecond_page(page, full, time, current_end);
+            return Ok(());
+        }
+
+        // Records of the previous page sharing its last timestamp come again
+        let last_id = self.last_id;
+        self.buffer
+            .extend(page.into_iter().filter(|record| match last_id {
+                Some(id) => record.record_id() > id,
+                None => true,
+            }));
+
+        match self.query.start_time {
+            None => match last {
+                Some((id, _)) if full => self.query.from_id = Some(id + 1),
+                _ => self.done = true,
+            },
+            Some(start_time) => {
+                let current_end = self.query.end_time.unwrap_or(self.end_time);
+                match last {
+                    Some((id, time)) if full && time > start_time => {
+                        self.last_id = Some(id);
+                        self.resume(time, current_end);
+                    }
+                    Some((id, time)) if full => {
+                        // A full page within one millisecond can't be split by time
+                        self.last_id = Some(id);
+                        self.same_millisecond = Some((time, current_end));
+                        self.query.start_time = None;
+                        self.query.end_time = None;
+                        self.query.from_id = Some(id + 1);
+                    }
+                    _ => {
+                        if let Some((id, _)) = last {
+                            self.last_id = Some(id);
+                        }
+                        self.resume(current_end + 1, current_end);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Page of the millisecond `time` walked by id, the records after it are fetched
+    // again by time
+    fn next_millisecond_page(&mut self, page: Vec<T>, full: bool, time: u64, current_end: u64) {
+        let past = match page.last() {
+            Some(record) => record.record_time() > time,
+            None => true,
+        };
+        for record in page {
+            if record.record_time() == time {
+                self.last_id = Some(record.record_id());
+                self.buffer.push_back(record);
+            }
+        }
+
+        if full && !past {
+            self.query.from_id = self.last_id.map(|id| id + 1);
+        } else {
+            self.same_millisecond = None;
+            self.query.from_id = None;
+            self.resume(time + 1, current_end);
+        }
+    }
+
+    // Walk by time from `start_time`, within the window ending at `current_end` or
+    // the next one
+    fn resume(&mut self, start_time: u64, current_end: u64) {
+        self.query.start_time = Some(start_time);
+        if start_time > self.end_time {
+            self.done = true;
+        } else if start_time > current_end {
+            self.query.end_time = Some(window_end(start_time, self.window, self.end_time));
+        } else {
+            self.query.end_time = Some(current_end);
+        }
+    }
+}
+
+impl<T: HistoryRecord> Iterator for HistoryIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(record) = self.buffer.pop_front() {
+                return Some(Ok(record));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(error) = self.next_page() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+fn window_end(start_time: u64, window: u64, end_time: u64) -> u64 {
+    (start_time + window - 1).min(end_time)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or_default()
+}
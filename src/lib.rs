@@ -10,6 +10,9 @@
 #[macro_use]
 extern crate error_chain;
 
+#[cfg(feature = "decimal")]
+pub use rust_decimal;
+
 pub mod client;
 pub mod errors;
 pub mod util;
@@ -19,11 +22,21 @@ pub mod model;
 pub mod account;
 pub mod api;
 pub mod config;
+pub mod filters;
 pub mod general;
+pub mod history;
 pub mod market;
+pub mod order_tracker;
+pub mod portfolio;
+pub mod proxy;
 pub mod savings;
+pub mod stream_stats;
+pub mod symbol_registry;
 pub mod userstream;
 pub mod websockets;
+pub mod ws_api;
 
+pub mod delivery;
 pub mod futures;
 pub mod margin;
+pub mod options;
@@ -6,6 +6,7 @@ use std::collections::BTreeMap;
 use crate::account::{OrderSide, OrderType, TimeInForce};
 use crate::api::{API, Margin};
 use crate::margin::SideEffectType;
+use crate::history::{HistoryIter, HistoryQuery, DAY};
 
 #[derive(Clone)]
 pub struct MarginAccount {
@@ -71,6 +72,40 @@ impl MarginAccount {
             .get_signed(API::Margin(Margin::AllOrders), Some(request))
     }
 
+    // Orders of every status from an order id or within a time range of at most 24 hours
+    pub fn all_orders_query(&self, query: &HistoryQuery) -> Result<Vec<Order>> {
+        let mut parameters = query.order_parameters();
+        parameters.insert("isIsolated".into(), bool_to_string(self.is_isolated));
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Margin(Margin::AllOrders), Some(request))
+    }
+
+    // Every order of the query, fetched by pages of up to 500 orders
+    pub fn all_orders_iter(&self, query: HistoryQuery) -> HistoryIter<Order> {
+        let account = self.clone();
+        HistoryIter::new(query, 500, DAY, move |query| account.all_orders_query(query))
+    }
+
+    // Trade history by order, trade id or time, at most 24 hours between start and end time
+    pub fn trade_history_query(&self, query: &HistoryQuery) -> Result<Vec<TradeHistory>> {
+        let mut parameters = query.trade_parameters();
+        parameters.insert("isIsolated".into(), bool_to_string(self.is_isolated));
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Margin(Margin::MyTrades), Some(request))
+    }
+
+    // Every trade of the query, fetched by pages of up to 1000 trades
+    pub fn trade_history_iter(&self, query: HistoryQuery) -> HistoryIter<TradeHistory> {
+        let account = self.clone();
+        HistoryIter::new(query, 1000, DAY, move |query| {
+            account.trade_history_query(query)
+        })
+    }
+
     pub fn get_order_status<S>(&self, symbol: S, client_order_id: S) -> Result<Order>
     where
         S: Into<String>,
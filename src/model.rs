@@ -3,6 +3,24 @@ use serde_json::{from_value, Value};
 use std::convert::TryFrom;
 use crate::errors::*;
 
+/// Prices and quantities of the spot models and orders, `f64` by default and an
+/// exact `rust_decimal::Decimal` with the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
+// Float value of a `Number`, for the computations which need no exact decimals
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn number_to_f64(number: Number) -> f64 {
+    number
+}
+
+#[cfg(feature = "decimal")]
+pub(crate) fn number_to_f64(number: Number) -> f64 {
+    rust_decimal::prelude::ToPrimitive::to_f64(&number).unwrap_or_default()
+}
+
 #[derive(Deserialize, Clone)]
 pub struct Empty {}
 
@@ -30,6 +48,28 @@ pub struct RateLimit {
     pub limit: u64,
 }
 
+/// Rate limit with the current usage, as returned by the WebSocket API.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitUsage {
+    pub rate_limit_type: String,
+    pub interval: String,
+    pub interval_num: u16,
+    pub limit: u64,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    pub api_key: Option<String>,
+    pub authorized_since: Option<u64>,
+    pub connected_since: u64,
+    pub return_rate_limits: bool,
+    pub server_time: u64,
+    pub user_data_stream: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Symbol {
@@ -141,6 +181,109 @@ pub struct AccountInformation {
     pub balances: Vec<Balance>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCommission {
+    pub symbol: String,
+    pub standard_commission: CommissionRates,
+    pub tax_commission: CommissionRates,
+    #[serde(default)]
+    pub special_commission: Option<CommissionRates>,
+    pub discount: CommissionDiscount,
+}
+
+impl AccountCommission {
+    /// Commission rate of a trade, before the discount for paying it with the
+    /// discount asset.
+    pub fn rate(&self, is_buyer: bool, is_maker: bool) -> Number {
+        let mut rate = self.standard_commission.rate(is_buyer, is_maker)
+            + self.tax_commission.rate(is_buyer, is_maker);
+        if let Some(ref special) = self.special_commission {
+            rate += special.rate(is_buyer, is_maker);
+        }
+        rate
+    }
+
+    /// Commission of a trade of `quote_qty`, in the quote asset.
+    pub fn commission(&self, quote_qty: Number, is_buyer: bool, is_maker: bool) -> Number {
+        quote_qty * self.rate(is_buyer, is_maker)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRates {
+    #[serde(with = "string_or_float")]
+    pub maker: Number,
+    #[serde(with = "string_or_float")]
+    pub taker: Number,
+    #[serde(with = "string_or_float")]
+    pub buyer: Number,
+    #[serde(with = "string_or_float")]
+    pub seller: Number,
+}
+
+impl CommissionRates {
+    pub fn rate(&self, is_buyer: bool, is_maker: bool) -> Number {
+        let liquidity = if is_maker { self.maker } else { self.taker };
+        let side = if is_buyer { self.buyer } else { self.seller };
+        liquidity + side
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionDiscount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: String,
+    /// Share of the commission paid when paying with `discount_asset`
+    #[serde(with = "string_or_float")]
+    pub discount: Number,
+}
+
+/// Match of two orders of the account prevented by self-trade prevention.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatch {
+    pub symbol: String,
+    pub prevented_match_id: u64,
+    pub taker_order_id: u64,
+    pub maker_symbol: String,
+    pub maker_order_id: u64,
+    pub trade_group_id: i64,
+    pub self_trade_prevention_mode: String,
+    #[serde(with = "string_or_float")]
+    pub price: Number,
+    #[serde(with = "string_or_float")]
+    pub maker_prevented_quantity: Number,
+    pub transact_time: u64,
+}
+
+/// Fill of an order placed by the Smart Order Routing.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocation {
+    pub symbol: String,
+    pub allocation_id: u64,
+    pub allocation_type: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    #[serde(with = "string_or_float")]
+    pub price: Number,
+    #[serde(with = "string_or_float")]
+    pub qty: Number,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: Number,
+    #[serde(with = "string_or_float")]
+    pub commission: Number,
+    pub commission_asset: String,
+    pub time: u64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_allocator: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct IsolatedMarginAsset {
@@ -207,7 +350,7 @@ pub struct MarginAccountInformation {
     pub total_net_asset_of_btc: f64,
     pub trade_enabled: bool,
     pub transfer_enabled: bool,
-    #[serde(alias = "user_assets")]
+    #[serde(alias = "user_assets", alias = "userAssets")]
     pub assets: Vec<MarginAsset>,
 }
 
@@ -227,27 +370,27 @@ pub struct Order {
     pub order_list_id: Option<i64>,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: String,
     pub time_in_force: String,
     #[serde(rename = "type")]
     pub order_type: String,
     pub side: String,
     #[serde(with = "string_or_float")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     #[serde(with = "string_or_float")]
-    pub iceberg_qty: f64,
+    pub iceberg_qty: Number,
     pub time: u64,
     pub update_time: u64,
     pub is_working: bool,
     //#[serde(with = "string_or_float")]
-    //pub orig_quote_order_qty: f64,
+    //pub orig_quote_order_qty: Number,
     pub self_trade_prevention_mode: Option<String>,
 }
 
@@ -258,11 +401,11 @@ pub struct OrderCanceled {
     pub orig_client_order_id: String,
     pub client_order_id: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     pub status: String,
     #[serde(rename = "type")]
     pub type_name: String,
@@ -281,13 +424,13 @@ pub struct ReplaceOrderResponse {
     pub client_order_id: String,
     pub transact_time: Option<u64>,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     pub status: String,
     pub time_in_force: String,
     #[serde(rename = "type")]
@@ -321,6 +464,14 @@ impl CancelReplace{
         }
     }
 
+    /// New order placed with a `NewOrderResponseType::Ack` response
+    pub fn acknowledged_submit(&self) -> Option<&OrderAck>{
+        match &self.new_order_response{
+            Some(NewOrderResponse::Ack(e)) => Some(e),
+            _ => None
+        }
+    }
+
     pub fn failed_cancel(&self) -> Option<&BinanceContentError>{
         match &self.cancel_response{
             CancelOrderResponse::Success(_) => None,
@@ -330,7 +481,6 @@ impl CancelReplace{
 
     pub fn failed_submit(&self) -> Option<&BinanceContentError>{
         match &self.new_order_response{
-            Some(NewOrderResponse::Success(_)) => None,
             Some(NewOrderResponse::Failure(e)) => Some(e),
             _ => None
         }
@@ -344,10 +494,12 @@ pub enum CancelOrderResponse {
     Failure(BinanceContentError),
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum NewOrderResponse {
     Success(Transaction),
+    Ack(OrderAck),
     Failure(BinanceContentError),
 }
 
@@ -360,39 +512,185 @@ pub struct Transaction {
     pub client_order_id: String,
     pub transact_time: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub orig_qty: f64,
+    pub orig_qty: Number,
     #[serde(with = "string_or_float")]
-    pub executed_qty: f64,
+    pub executed_qty: Number,
     #[serde(with = "string_or_float")]
-    pub cummulative_quote_qty: f64,
+    pub cummulative_quote_qty: Number,
     #[serde(with = "string_or_float", default = "default_stop_price")]
-    pub stop_price: f64,
+    pub stop_price: Number,
     pub status: String,
     pub time_in_force: String,
     #[serde(rename = "type")]
     pub type_name: String,
     pub side: String,
     pub fills: Option<Vec<FillInfo>>,
+    /// -1 while a stop order is not triggered
+    pub working_time: Option<i64>,
+    pub self_trade_prevention_mode: Option<String>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub iceberg_qty: Option<Number>,
+    pub trailing_delta: Option<u64>,
+    pub strategy_id: Option<u64>,
+    pub strategy_type: Option<u64>,
+    pub peg_price_type: Option<String>,
+    #[serde(default, with = "string_or_float_opt")]
+    pub pegged_price: Option<Number>,
+    /// "SOR" when placed by the Smart Order Routing
+    pub working_floor: Option<String>,
+    pub used_sor: Option<bool>,
+}
+
+/// OCO, OTO or OTOCO list of orders
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: i64,
+    /// "OCO" or "OTO", an OTOCO is an "OTO"
+    pub contingency_type: String,
+    /// "RESPONSE", "EXEC_STARTED" or "ALL_DONE"
+    pub list_status_type: String,
+    /// "EXECUTING", "ALL_DONE" or "REJECT"
+    pub list_order_status: String,
+    pub list_client_order_id: String,
+    pub transaction_time: u64,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+    /// State of the orders, only in the response to placing or canceling a list
+    #[serde(default)]
+    pub order_reports: Vec<OrderReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderReport {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    /// Set when the order was canceled
+    pub orig_client_order_id: Option<String>,
+    pub transact_time: u64,
+    #[serde(with = "string_or_float")]
+    pub price: Number,
+    #[serde(with = "string_or_float")]
+    pub orig_qty: Number,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: Number,
+    #[serde(with = "string_or_float")]
+    pub cummulative_quote_qty: Number,
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub side: String,
+    #[serde(default, with = "string_or_float_opt")]
+    pub stop_price: Option<Number>,
+    pub trailing_delta: Option<u64>,
+    pub self_trade_prevention_mode: Option<String>,
+}
+
+/// Entry of the response to canceling all open orders, the orders of a list
+/// are reported together as the list.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum OpenOrderCanceled {
+    Order(OrderCanceled),
+    OrderList(OrderList),
+}
+
+/// Response to a new order placed with `NewOrderResponseType::Ack`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAck {
+    pub symbol: String,
+    pub order_id: u64,
+    pub order_list_id: i64,
+    pub client_order_id: String,
+    pub transact_time: u64,
 }
 
-fn default_stop_price() -> f64 {
-    0.0
+/// Response to a new order, in the shape of its `NewOrderResponseType`.
+/// `Result` has no fills, `Full` lists the fills of the order.
+#[derive(Debug, Clone)]
+pub enum OrderResponse {
+    Ack(OrderAck),
+    Result(Transaction),
+    Full(Transaction),
+}
+
+impl OrderResponse {
+    pub fn order_id(&self) -> u64 {
+        match self {
+            OrderResponse::Ack(ack) => ack.order_id,
+            OrderResponse::Result(transaction) | OrderResponse::Full(transaction) => {
+                transaction.order_id
+            }
+        }
+    }
+
+    pub fn client_order_id(&self) -> &str {
+        match self {
+            OrderResponse::Ack(ack) => &ack.client_order_id,
+            OrderResponse::Result(transaction) | OrderResponse::Full(transaction) => {
+                &transaction.client_order_id
+            }
+        }
+    }
+
+    /// State of the order, `None` for an `Ack`
+    pub fn transaction(&self) -> Option<&Transaction> {
+        match self {
+            OrderResponse::Ack(_) => None,
+            OrderResponse::Result(transaction) | OrderResponse::Full(transaction) => {
+                Some(transaction)
+            }
+        }
+    }
+}
+
+fn default_stop_price() -> Number {
+    Number::default()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct FillInfo {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     #[serde(with = "string_or_float")]
-    pub commission: f64,
+    pub commission: Number,
     pub commission_asset: String,
+    /// None for the SOR fills without a trade id
+    #[serde(default, deserialize_with = "trade_id_or_none")]
     pub trade_id: Option<u64>,
+    /// SOR fills only, "ONE_PARTY_TRADE_REPORT" for a fill of an allocation
+    pub match_type: Option<String>,
+    /// SOR fills only, see `Account::get_allocations`
+    pub alloc_id: Option<u64>,
 }
+
+// SOR allocations carry the trade id -1
+fn trade_id_or_none<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let trade_id: Option<i64> = Option::deserialize(deserializer)?;
+    Ok(trade_id.and_then(|trade_id| u64::try_from(trade_id).ok()))
+}
+
 /// Response to a test order (endpoint /api/v3/order/test).
 ///
 /// Currently, the API responds {} on a successfull test transaction,
@@ -413,13 +711,13 @@ pub struct OrderBook {
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct Bids {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 impl Bids {
-    pub fn new(price: f64, qty: f64) -> Bids {
+    pub fn new(price: Number, qty: Number) -> Bids {
         Bids { price, qty }
     }
 }
@@ -427,9 +725,9 @@ impl Bids {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Asks {
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -452,14 +750,14 @@ pub enum Prices {
 pub struct SymbolPrice {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AveragePrice {
     pub mins: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -479,13 +777,13 @@ pub enum KlineSummaries {
 pub struct Tickers {
     pub symbol: String,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Number,
     #[serde(with = "string_or_float")]
-    pub bid_qty: f64,
+    pub bid_qty: Number,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Number,
     #[serde(with = "string_or_float")]
-    pub ask_qty: f64,
+    pub ask_qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -493,9 +791,9 @@ pub struct Tickers {
 pub struct TradeHistory {
     pub id: u64,
     #[serde(with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
     pub commission: String,
     pub commission_asset: String,
     pub time: u64,
@@ -512,21 +810,21 @@ pub struct PriceStats {
     pub price_change_percent: String,
     pub weighted_avg_price: String,
     #[serde(with = "string_or_float")]
-    pub prev_close_price: f64,
+    pub prev_close_price: Number,
     #[serde(with = "string_or_float")]
-    pub last_price: f64,
+    pub last_price: Number,
     #[serde(with = "string_or_float")]
-    pub bid_price: f64,
+    pub bid_price: Number,
     #[serde(with = "string_or_float")]
-    pub ask_price: f64,
+    pub ask_price: Number,
     #[serde(with = "string_or_float")]
-    pub open_price: f64,
+    pub open_price: Number,
     #[serde(with = "string_or_float")]
-    pub high_price: f64,
+    pub high_price: Number,
     #[serde(with = "string_or_float")]
-    pub low_price: f64,
+    pub low_price: Number,
     #[serde(with = "string_or_float")]
-    pub volume: f64,
+    pub volume: Number,
     pub open_time: u64,
     pub close_time: u64,
     pub first_id: i64,
@@ -549,9 +847,9 @@ pub struct AggTrade {
     #[serde(rename = "M")]
     pub best_match: bool,
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 }
 
 
@@ -708,7 +1006,7 @@ pub struct OrderTradeEvent {
     #[serde(rename = "n")]
     pub commission: String,
 
-    #[serde(skip, rename = "N")]
+    #[serde(rename = "N")]
     pub asset_commisioned: Option<String>,
 
     #[serde(rename = "T")]
@@ -797,10 +1095,10 @@ pub struct TradeEvent {
     pub trade_id: u64,
 
     #[serde(rename = "p", with = "string_or_float")]
-    pub price: f64,
+    pub price: Number,
 
     #[serde(rename = "q", with = "string_or_float")]
-    pub qty: f64,
+    pub qty: Number,
 
     #[serde(rename = "b")]
     pub buyer_order_id: Option<u64>,
@@ -933,16 +1231,16 @@ pub struct BookTickerEvent {
     pub symbol: String,
 
     #[serde(rename = "b", with = "string_or_float")]
-    pub best_bid: f64,
+    pub best_bid: Number,
 
     #[serde(rename = "B", with = "string_or_float")]
-    pub best_bid_qty: f64,
+    pub best_bid_qty: Number,
 
     #[serde(rename = "a", with = "string_or_float")]
-    pub best_ask: f64,
+    pub best_ask: Number,
 
     #[serde(rename = "A", with = "string_or_float")]
-    pub best_ask_qty: f64,
+    pub best_ask_qty: Number,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1129,7 +1427,7 @@ pub struct KlineSummary {
     pub taker_buy_quote_asset_volume: String,
 }
 
-fn get_value(row: &[Value], index: usize, name: &'static str) -> Result<Value> {
+pub(crate) fn get_value(row: &[Value], index: usize, name: &'static str) -> Result<Value> {
     Ok(row
         .get(index)
         .ok_or(ErrorKind::KlineValueMissingError(index, name))?
@@ -1442,6 +1740,42 @@ pub(crate) mod string_or_float {
 
     use serde::{de, Serializer, Deserialize, Deserializer};
 
+    // Numbers sent by Binance as a string or as a JSON number
+    pub trait FromStringOrFloat: Sized {
+        fn from_string(s: &str) -> Result<Self, String>;
+        fn from_float(f: f64) -> Result<Self, String>;
+    }
+
+    impl FromStringOrFloat for f64 {
+        fn from_string(s: &str) -> Result<Self, String> {
+            if s == "INF" {
+                Ok(f64::INFINITY)
+            } else {
+                s.parse().map_err(|e| format!("{}", e))
+            }
+        }
+
+        fn from_float(f: f64) -> Result<Self, String> {
+            Ok(f)
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    impl FromStringOrFloat for rust_decimal::Decimal {
+        fn from_string(s: &str) -> Result<Self, String> {
+            if s == "INF" {
+                return Ok(rust_decimal::Decimal::MAX);
+            }
+            rust_decimal::Decimal::from_str_exact(s)
+                .or_else(|_| rust_decimal::Decimal::from_scientific(s))
+                .map_err(|e| format!("{}", e))
+        }
+
+        fn from_float(f: f64) -> Result<Self, String> {
+            Self::from_string(&f.to_string())
+        }
+    }
+
     pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
     where
         T: fmt::Display,
@@ -1450,8 +1784,9 @@ pub(crate) mod string_or_float {
         serializer.collect_str(value)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<f64, D::Error>
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
     where
+        T: FromStringOrFloat,
         D: Deserializer<'de>,
     {
         #[derive(Deserialize)]
@@ -1462,14 +1797,8 @@ pub(crate) mod string_or_float {
         }
 
         match StringOrFloat::deserialize(deserializer)? {
-            StringOrFloat::String(s) => {
-                if s == "INF" {
-                    Ok(f64::INFINITY)
-                } else {
-                    s.parse().map_err(de::Error::custom)
-                }
-            }
-            StringOrFloat::Float(i) => Ok(i),
+            StringOrFloat::String(s) => T::from_string(&s).map_err(de::Error::custom),
+            StringOrFloat::Float(i) => T::from_float(i).map_err(de::Error::custom),
         }
     }
 }
@@ -1490,8 +1819,9 @@ pub(crate) mod string_or_float_opt {
         }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
     where
+        T: crate::model::string_or_float::FromStringOrFloat,
         D: Deserializer<'de>,
     {
         #[derive(Deserialize)]
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use crate::util::*;
+use crate::errors::*;
+use crate::client::Client;
+use crate::api::{API, Options};
+use crate::model::Empty;
+use crate::account::{OrderSide, TimeInForce};
+use super::model::{MarginAccount, Order, Position};
+
+/// European options account and orders, options only take LIMIT orders.
+#[derive(Clone)]
+pub struct OptionsAccount {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+pub struct OptionsOrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub qty: f64,
+    pub price: f64,
+    pub time_in_force: Option<TimeInForce>,
+    pub reduce_only: Option<bool>,
+    pub post_only: Option<bool>,
+    pub client_order_id: Option<String>,
+    /// Market maker protection order
+    pub is_mmp: Option<bool>,
+}
+
+impl OptionsAccount {
+    pub fn limit_buy(
+        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<Order> {
+        self.custom_order(simple_order(
+            symbol.into(), OrderSide::Buy, qty.into(), price, time_in_force,
+        ))
+    }
+
+    pub fn limit_sell(
+        &self, symbol: impl Into<String>, qty: impl Into<f64>, price: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<Order> {
+        self.custom_order(simple_order(
+            symbol.into(), OrderSide::Sell, qty.into(), price, time_in_force,
+        ))
+    }
+
+    pub fn custom_order(&self, order: OptionsOrderRequest) -> Result<Order> {
+        let order = build_order(order);
+        let request = build_signed_request(order, self.recv_window)?;
+        self.client
+            .post_signed(API::Options(Options::Order), request)
+    }
+
+    pub fn cancel_order<S>(&self, symbol: S, order_id: u64) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Options(Options::Order), Some(request))
+    }
+
+    pub fn cancel_order_with_client_id<S>(
+        &self, symbol: S, client_order_id: String,
+    ) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("clientOrderId".into(), client_order_id);
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed(API::Options(Options::Order), Some(request))
+    }
+
+    pub fn cancel_all_open_orders<S>(&self, symbol: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .delete_signed::<Empty>(API::Options(Options::AllOpenOrders), Some(request))
+            .map(|_| ())
+    }
+
+    // Open orders of ONE symbol, of every symbol when `symbol` is None
+    pub fn get_open_orders<S>(&self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Options(Options::OpenOrders), Some(request))
+    }
+
+    pub fn get_order_status<S>(&self, symbol: S, order_id: u64) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Options(Options::Order), Some(request))
+    }
+
+    // Balances and greeks of the options margin account
+    pub fn account_information(&self) -> Result<MarginAccount> {
+        let parameters = BTreeMap::new();
+
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Options(Options::MarginAccount), Some(request))
+    }
+
+    // Positions of ONE symbol, of every symbol when `symbol` is None
+    pub fn position_information<S>(&self, symbol: S) -> Result<Vec<Position>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_signed_request(parameters, self.recv_window)?;
+        self.client
+            .get_signed(API::Options(Options::Position), Some(request))
+    }
+}
+
+fn simple_order(
+    symbol: String, side: OrderSide, qty: f64, price: f64, time_in_force: TimeInForce,
+) -> OptionsOrderRequest {
+    OptionsOrderRequest {
+        symbol,
+        side,
+        qty,
+        price,
+        time_in_force: Some(time_in_force),
+        reduce_only: None,
+        post_only: None,
+        client_order_id: None,
+        is_mmp: None,
+    }
+}
+
+fn build_order(order: OptionsOrderRequest) -> BTreeMap<String, String> {
+    let mut parameters = BTreeMap::new();
+    parameters.insert("symbol".into(), order.symbol);
+    parameters.insert("side".into(), order.side.into());
+    parameters.insert("type".into(), "LIMIT".into());
+    parameters.insert("quantity".into(), order.qty.to_string());
+    parameters.insert("price".into(), order.price.to_string());
+
+    if let Some(time_in_force) = order.time_in_force {
+        parameters.insert("timeInForce".into(), time_in_force.into());
+    }
+    if let Some(reduce_only) = order.reduce_only {
+        parameters.insert("reduceOnly".into(), reduce_only.to_string());
+    }
+    if let Some(post_only) = order.post_only {
+        parameters.insert("postOnly".into(), post_only.to_string());
+    }
+    if let Some(client_order_id) = order.client_order_id {
+        parameters.insert("clientOrderId".into(), client_order_id);
+    }
+    if let Some(is_mmp) = order.is_mmp {
+        parameters.insert("isMmp".into(), is_mmp.to_string());
+    }
+
+    parameters
+}
@@ -0,0 +1,48 @@
+use crate::options::model::*;
+use crate::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Options;
+
+#[derive(Clone)]
+pub struct OptionsGeneral {
+    pub client: Client,
+}
+
+impl OptionsGeneral {
+    // Test connectivity
+    pub fn ping(&self) -> Result<String> {
+        self.client.get::<crate::model::Empty>(API::Options(Options::Ping), None)?;
+        Ok("pong".into())
+    }
+
+    // Check server time
+    pub fn get_server_time(&self) -> Result<ServerTime> {
+        self.client.get(API::Options(Options::Time), None)
+    }
+
+    // Obtain exchange information
+    // - Option contracts, assets and symbols with their trading rules
+    pub fn exchange_info(&self) -> Result<ExchangeInformation> {
+        self.client.get(API::Options(Options::ExchangeInfo), None)
+    }
+
+    // Get Symbol information
+    pub fn get_symbol_info<S>(&self, symbol: S) -> Result<OptionSymbol>
+    where
+        S: Into<String>,
+    {
+        let upper_symbol = symbol.into().to_uppercase();
+        match self.exchange_info() {
+            Ok(info) => {
+                for item in info.option_symbols {
+                    if item.symbol == upper_symbol {
+                        return Ok(item);
+                    }
+                }
+                bail!("Symbol not found")
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
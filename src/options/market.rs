@@ -0,0 +1,136 @@
+/*!
+European options market data, prices are in the quote asset (USDT).
+*/
+
+use crate::util::*;
+use crate::options::model::*;
+use crate::client::*;
+use crate::errors::*;
+use std::collections::BTreeMap;
+use crate::api::API;
+use crate::api::Options;
+
+#[derive(Clone)]
+pub struct OptionsMarket {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl OptionsMarket {
+    // Order book (Default 100; max 1000)
+    pub fn get_depth<S>(&self, symbol: S) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Depth), Some(request))
+    }
+
+    // Order book at a custom depth. Currently supported values
+    // are 10, 20, 50, 100, 500, 1000
+    pub fn get_custom_depth<S>(&self, symbol: S, depth: u64) -> Result<OrderBook>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("limit".into(), depth.to_string());
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Depth), Some(request))
+    }
+
+    // Recent trades (Default 100; max 500)
+    pub fn get_trades<S>(&self, symbol: S) -> Result<Vec<Trade>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Trades), Some(request))
+    }
+
+    // Returns up to 'limit' klines for given symbol and interval ("1m", "5m", ...)
+    pub fn get_klines<S1, S2, S3, S4, S5>(
+        &self, symbol: S1, interval: S2, limit: S3, start_time: S4, end_time: S5,
+    ) -> Result<Vec<Kline>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<Option<u16>>,
+        S4: Into<Option<u64>>,
+        S5: Into<Option<u64>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("interval".into(), interval.into());
+
+        if let Some(lt) = limit.into() {
+            parameters.insert("limit".into(), format!("{}", lt));
+        }
+        if let Some(st) = start_time.into() {
+            parameters.insert("startTime".into(), format!("{}", st));
+        }
+        if let Some(et) = end_time.into() {
+            parameters.insert("endTime".into(), format!("{}", et));
+        }
+
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Klines), Some(request))
+    }
+
+    // Mark price and greeks of ONE symbol, of every symbol when `symbol` is None
+    pub fn get_mark_prices<S>(&self, symbol: S) -> Result<Vec<MarkPrice>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Mark), Some(request))
+    }
+
+    // 24hr ticker price change statistics of ONE symbol, of every symbol when `symbol` is None
+    pub fn get_24h_price_stats<S>(&self, symbol: S) -> Result<Vec<Ticker>>
+    where
+        S: Into<Option<String>>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        if let Some(symbol) = symbol.into() {
+            parameters.insert("symbol".into(), symbol);
+        }
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Ticker), Some(request))
+    }
+
+    // Spot index price of an underlying ("BTCUSDT")
+    pub fn get_index_price<S>(&self, underlying: S) -> Result<IndexPrice>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("underlying".into(), underlying.into());
+        let request = build_request(parameters);
+        self.client.get(API::Options(Options::Index), Some(request))
+    }
+
+    // Open interest of the options on `underlying_asset` ("BTC") expiring on `expiration` ("221225")
+    pub fn open_interest<S1, S2>(
+        &self, underlying_asset: S1, expiration: S2,
+    ) -> Result<Vec<OpenInterest>>
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("underlyingAsset".into(), underlying_asset.into());
+        parameters.insert("expiration".into(), expiration.into());
+        let request = build_request(parameters);
+        self.client
+            .get(API::Options(Options::OpenInterest), Some(request))
+    }
+}
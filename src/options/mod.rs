@@ -0,0 +1,6 @@
+pub mod account;
+pub mod general;
+pub mod market;
+pub mod model;
+pub mod userstream;
+pub mod websockets;
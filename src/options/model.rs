@@ -0,0 +1,521 @@
+use serde::{Deserialize, Serialize};
+use crate::model::{string_or_float, string_or_bool};
+
+pub use crate::model::{Asks, Bids, Filters, RateLimit, ServerTime};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeInformation {
+    pub timezone: String,
+    pub server_time: u64,
+    pub option_contracts: Vec<OptionContract>,
+    pub option_assets: Vec<OptionAsset>,
+    pub option_symbols: Vec<OptionSymbol>,
+    pub rate_limits: Vec<RateLimit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionContract {
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub underlying: String,
+    pub settle_asset: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OptionAsset {
+    pub name: String,
+}
+
+/// Option contract, "BTC-220815-50000-C" is a call on BTCUSDT at 50000 expiring on 2022-08-15.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionSymbol {
+    pub symbol: String,
+    /// "CALL" or "PUT"
+    pub side: String,
+    pub underlying: String,
+    #[serde(with = "string_or_float")]
+    pub strike_price: f64,
+    pub expiry_date: u64,
+    /// Underlying quantity of one contract
+    pub unit: u64,
+    #[serde(with = "string_or_float")]
+    pub maker_fee_rate: f64,
+    #[serde(with = "string_or_float")]
+    pub taker_fee_rate: f64,
+    #[serde(with = "string_or_float")]
+    pub min_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub max_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub initial_margin: f64,
+    #[serde(with = "string_or_float")]
+    pub maintenance_margin: f64,
+    #[serde(with = "string_or_float")]
+    pub min_initial_margin: f64,
+    #[serde(with = "string_or_float")]
+    pub min_maintenance_margin: f64,
+    pub price_scale: u16,
+    pub quantity_scale: u16,
+    pub quote_asset: String,
+    pub filters: Vec<Filters>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderBook {
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    pub bids: Vec<Bids>,
+    pub asks: Vec<Asks>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: String,
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub price: f64,
+    #[serde(with = "string_or_float")]
+    pub qty: f64,
+    #[serde(with = "string_or_float")]
+    pub quote_qty: f64,
+    /// 1 when the taker bought, -1 when it sold
+    pub side: i8,
+    pub time: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Kline {
+    pub open_time: u64,
+    pub close_time: u64,
+    pub interval: String,
+    #[serde(with = "string_or_float")]
+    pub open: f64,
+    #[serde(with = "string_or_float")]
+    pub high: f64,
+    #[serde(with = "string_or_float")]
+    pub low: f64,
+    #[serde(with = "string_or_float")]
+    pub close: f64,
+    #[serde(with = "string_or_float")]
+    pub volume: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: f64,
+    pub trade_count: u64,
+    #[serde(with = "string_or_float")]
+    pub taker_volume: f64,
+    #[serde(with = "string_or_float")]
+    pub taker_amount: f64,
+}
+
+/// Mark price, implied volatilities and greeks.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkPrice {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub mark_price: f64,
+    #[serde(with = "string_or_float", rename = "bidIV")]
+    pub bid_iv: f64,
+    #[serde(with = "string_or_float", rename = "askIV")]
+    pub ask_iv: f64,
+    #[serde(with = "string_or_float", rename = "markIV")]
+    pub mark_iv: f64,
+    #[serde(with = "string_or_float")]
+    pub delta: f64,
+    #[serde(with = "string_or_float")]
+    pub theta: f64,
+    #[serde(with = "string_or_float")]
+    pub gamma: f64,
+    #[serde(with = "string_or_float")]
+    pub vega: f64,
+    #[serde(with = "string_or_float")]
+    pub high_price_limit: f64,
+    #[serde(with = "string_or_float")]
+    pub low_price_limit: f64,
+    #[serde(with = "string_or_float")]
+    pub risk_free_interest: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub price_change: f64,
+    #[serde(with = "string_or_float")]
+    pub price_change_percent: f64,
+    #[serde(with = "string_or_float")]
+    pub last_price: f64,
+    #[serde(with = "string_or_float")]
+    pub last_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub open: f64,
+    #[serde(with = "string_or_float")]
+    pub high: f64,
+    #[serde(with = "string_or_float")]
+    pub low: f64,
+    #[serde(with = "string_or_float")]
+    pub volume: f64,
+    #[serde(with = "string_or_float")]
+    pub amount: f64,
+    #[serde(with = "string_or_float")]
+    pub bid_price: f64,
+    #[serde(with = "string_or_float")]
+    pub ask_price: f64,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub first_trade_id: u64,
+    pub trade_count: u64,
+    #[serde(with = "string_or_float")]
+    pub strike_price: f64,
+    /// Underlying price at the last expiry
+    #[serde(with = "string_or_float")]
+    pub exercise_price: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexPrice {
+    pub time: u64,
+    #[serde(with = "string_or_float")]
+    pub index_price: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub sum_open_interest: f64,
+    #[serde(with = "string_or_float")]
+    pub sum_open_interest_usd: f64,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAccount {
+    pub asset: Vec<MarginAsset>,
+    pub greek: Vec<AccountGreek>,
+    pub time: u64,
+    #[serde(with = "string_or_bool")]
+    pub can_trade: bool,
+    #[serde(with = "string_or_bool")]
+    pub can_deposit: bool,
+    #[serde(with = "string_or_bool")]
+    pub can_withdraw: bool,
+    #[serde(with = "string_or_bool")]
+    pub reduce_only: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAsset {
+    pub asset: String,
+    #[serde(with = "string_or_float")]
+    pub margin_balance: f64,
+    #[serde(with = "string_or_float")]
+    pub equity: f64,
+    #[serde(with = "string_or_float")]
+    pub available: f64,
+    #[serde(with = "string_or_float")]
+    pub initial_margin: f64,
+    #[serde(with = "string_or_float")]
+    pub maint_margin: f64,
+    #[serde(with = "string_or_float", rename = "unrealizedPNL")]
+    pub unrealized_pnl: f64,
+    #[serde(with = "string_or_float")]
+    pub lp_profit: f64,
+}
+
+/// Greeks of the positions on one underlying.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGreek {
+    pub underlying: String,
+    #[serde(with = "string_or_float")]
+    pub delta: f64,
+    #[serde(with = "string_or_float")]
+    pub gamma: f64,
+    #[serde(with = "string_or_float")]
+    pub theta: f64,
+    #[serde(with = "string_or_float")]
+    pub vega: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub symbol: String,
+    /// "LONG" or "SHORT"
+    pub side: String,
+    /// Negative for short positions
+    #[serde(with = "string_or_float")]
+    pub quantity: f64,
+    #[serde(with = "string_or_float")]
+    pub reducible_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub entry_price: f64,
+    #[serde(with = "string_or_float")]
+    pub mark_price: f64,
+    #[serde(with = "string_or_float")]
+    pub mark_value: f64,
+    /// Return on cost
+    #[serde(with = "string_or_float")]
+    pub ror: f64,
+    #[serde(with = "string_or_float", rename = "unrealizedPNL")]
+    pub unrealized_pnl: f64,
+    #[serde(with = "string_or_float")]
+    pub strike_price: f64,
+    #[serde(with = "string_or_float")]
+    pub position_cost: f64,
+    pub expiry_date: u64,
+    pub price_scale: u16,
+    pub quantity_scale: u16,
+    /// "CALL" or "PUT"
+    pub option_side: String,
+    pub quote_asset: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub order_id: u64,
+    pub symbol: String,
+    #[serde(with = "string_or_float")]
+    pub price: f64,
+    #[serde(with = "string_or_float")]
+    pub quantity: f64,
+    #[serde(with = "string_or_float")]
+    pub executed_qty: f64,
+    #[serde(with = "string_or_float")]
+    pub fee: f64,
+    pub side: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub time_in_force: String,
+    pub reduce_only: bool,
+    pub post_only: bool,
+    pub create_time: u64,
+    pub update_time: u64,
+    /// "ACCEPTED", "PARTIALLY_FILLED", "FILLED", "CANCELLED"...
+    pub status: String,
+    #[serde(with = "string_or_float")]
+    pub avg_price: f64,
+    pub client_order_id: String,
+    pub price_scale: u16,
+    pub quantity_scale: u16,
+    pub option_side: String,
+    pub quote_asset: String,
+    #[serde(default)]
+    pub mmp: bool,
+}
+
+// https://developers.binance.com/docs/derivatives/option/websocket-market-streams
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TradeEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "t")]
+    pub trade_id: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: f64,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub qty: f64,
+    #[serde(rename = "b")]
+    pub buyer_order_id: u64,
+    #[serde(rename = "a")]
+    pub seller_order_id: u64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    /// "1" when the taker bought, "-1" when it sold
+    #[serde(rename = "S")]
+    pub side: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IndexPriceEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    /// Underlying, "BTCUSDT"
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarkPriceEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TickerEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: f64,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: f64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: f64,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: f64,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub volume: f64,
+    #[serde(rename = "A", with = "string_or_float")]
+    pub amount: f64,
+    #[serde(rename = "P", with = "string_or_float")]
+    pub price_change_percent: f64,
+    #[serde(rename = "p", with = "string_or_float")]
+    pub price_change: f64,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub last_qty: f64,
+    #[serde(rename = "F")]
+    pub first_trade_id: u64,
+    #[serde(rename = "L")]
+    pub last_trade_id: u64,
+    #[serde(rename = "n")]
+    pub trade_count: u64,
+    #[serde(rename = "bo", with = "string_or_float")]
+    pub best_bid_price: f64,
+    #[serde(rename = "ao", with = "string_or_float")]
+    pub best_ask_price: f64,
+    #[serde(rename = "bq", with = "string_or_float")]
+    pub best_bid_qty: f64,
+    #[serde(rename = "aq", with = "string_or_float")]
+    pub best_ask_qty: f64,
+    #[serde(rename = "b", with = "string_or_float")]
+    pub bid_iv: f64,
+    #[serde(rename = "a", with = "string_or_float")]
+    pub ask_iv: f64,
+    #[serde(rename = "d", with = "string_or_float")]
+    pub delta: f64,
+    #[serde(rename = "t", with = "string_or_float")]
+    pub theta: f64,
+    #[serde(rename = "g", with = "string_or_float")]
+    pub gamma: f64,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub vega: f64,
+    #[serde(rename = "vo", with = "string_or_float")]
+    pub mark_iv: f64,
+    #[serde(rename = "mp", with = "string_or_float")]
+    pub mark_price: f64,
+    #[serde(rename = "hl", with = "string_or_float")]
+    pub high_price_limit: f64,
+    #[serde(rename = "ll", with = "string_or_float")]
+    pub low_price_limit: f64,
+    #[serde(rename = "eep", with = "string_or_float")]
+    pub estimated_exercise_price: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KlineEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KlineData {
+    #[serde(rename = "t")]
+    pub open_time: u64,
+    #[serde(rename = "T")]
+    pub close_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "i")]
+    pub interval: String,
+    #[serde(rename = "F")]
+    pub first_trade_id: i64,
+    #[serde(rename = "L")]
+    pub last_trade_id: i64,
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open: f64,
+    #[serde(rename = "c", with = "string_or_float")]
+    pub close: f64,
+    #[serde(rename = "h", with = "string_or_float")]
+    pub high: f64,
+    #[serde(rename = "l", with = "string_or_float")]
+    pub low: f64,
+    #[serde(rename = "v", with = "string_or_float")]
+    pub volume: f64,
+    #[serde(rename = "n")]
+    pub trade_count: u64,
+    #[serde(rename = "x")]
+    pub is_final_bar: bool,
+    #[serde(rename = "q", with = "string_or_float")]
+    pub amount: f64,
+    #[serde(rename = "V", with = "string_or_float")]
+    pub taker_volume: f64,
+    #[serde(rename = "Q", with = "string_or_float")]
+    pub taker_amount: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DepthEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "pu")]
+    pub previous_update_id: u64,
+    #[serde(rename = "b")]
+    pub bids: Vec<Bids>,
+    #[serde(rename = "a")]
+    pub asks: Vec<Asks>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenInterestEvent {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    /// In contracts
+    #[serde(rename = "o", with = "string_or_float")]
+    pub open_interest: f64,
+    /// In USDT
+    #[serde(rename = "h", with = "string_or_float")]
+    pub open_interest_usd: f64,
+}
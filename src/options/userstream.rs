@@ -0,0 +1,28 @@
+use crate::model::*;
+use crate::client::*;
+use crate::errors::*;
+use crate::api::API;
+use crate::api::Options;
+
+#[derive(Clone)]
+pub struct OptionsUserStream {
+    pub client: Client,
+    pub recv_window: u64,
+}
+
+impl OptionsUserStream {
+    // User Stream
+    pub fn start(&self) -> Result<UserDataStream> {
+        self.client.post(API::Options(Options::UserDataStream))
+    }
+
+    pub fn keep_alive(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .put(API::Options(Options::UserDataStream), listen_key)
+    }
+
+    pub fn close(&self, listen_key: &str) -> Result<Success> {
+        self.client
+            .delete(API::Options(Options::UserDataStream), listen_key)
+    }
+}
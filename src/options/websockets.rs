@@ -0,0 +1,275 @@
+use crate::errors::*;
+use crate::config::*;
+use crate::options::model::*;
+use crate::proxy::Proxy;
+use crate::stream_stats::{event_time, fallback_stream_name, StaleAction, StaleWatch, StreamMonitor};
+use crate::websockets::{close_socket, connect_socket, StopHandle};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::net::TcpStream;
+
+use std::time::Duration;
+
+use tungstenite::Message;
+use tungstenite::protocol::WebSocket;
+use tungstenite::handshake::client::Response;
+use tungstenite::stream::MaybeTlsStream;
+
+static OPTIONS_WS_ENDPOINT: &str = "wss://nbstream.binance.com/eoptions";
+
+#[allow(clippy::all)]
+enum OptionsWebsocketAPI {
+    Default,
+    MultiStream,
+    Custom(String),
+}
+
+impl OptionsWebsocketAPI {
+    fn params(self, subscription: &str) -> String {
+        match self {
+            OptionsWebsocketAPI::Default => format!("{}/ws/{}", OPTIONS_WS_ENDPOINT, subscription),
+            OptionsWebsocketAPI::MultiStream => {
+                format!("{}/stream?streams={}", OPTIONS_WS_ENDPOINT, subscription)
+            }
+            OptionsWebsocketAPI::Custom(url) => format!("{}/ws/{}", url, subscription),
+        }
+    }
+}
+
+/// Option market streams: "BTC-220930-18000-C@trade", "BTCUSDT@index",
+/// "BTC-220930-18000-C@ticker", "ETH@ticker@220930", "ETH@markPrice",
+/// "BTC-220930-18000-C@kline_1m", "BTC-220930-18000-C@depth10", "ETH@openInterest@221125".
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum OptionsWebsocketEvent {
+    Trade(TradeEvent),
+    IndexPrice(IndexPriceEvent),
+    MarkPriceAll(Vec<MarkPriceEvent>),
+    Ticker(TickerEvent),
+    TickerAll(Vec<TickerEvent>),
+    Kline(KlineEvent),
+    DepthOrderBook(DepthEvent),
+    OpenInterestAll(Vec<OpenInterestEvent>),
+}
+
+pub struct OptionsWebSockets<'a> {
+    pub socket: Option<(WebSocket<MaybeTlsStream<TcpStream>>, Response)>,
+    handler: Box<dyn FnMut(OptionsWebsocketEvent) -> Result<()> + 'a>,
+    stop_handle: StopHandle,
+    monitor: StreamMonitor,
+    stream_name: Option<String>,
+    url: Option<String>,
+    proxy: Option<Proxy>,
+    stale: StaleWatch<'a>,
+}
+
+impl<'a> OptionsWebSockets<'a> {
+    pub fn new<Callback>(handler: Callback) -> OptionsWebSockets<'a>
+    where
+        Callback: FnMut(OptionsWebsocketEvent) -> Result<()> + 'a,
+    {
+        OptionsWebSockets {
+            socket: None,
+            handler: Box::new(handler),
+            stop_handle: StopHandle::default(),
+            monitor: StreamMonitor::new(),
+            stream_name: None,
+            url: None,
+            proxy: None,
+            stale: StaleWatch::default(),
+        }
+    }
+
+    /// Connect through `proxy`, a proxy set in the `Config` of `connect_with_config` takes precedence.
+    pub fn set_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Report streams which received nothing for longer than `threshold`.
+    ///
+    /// Without an `on_stale` callback a stale stream reconnects the websocket.
+    pub fn set_stale_threshold(mut self, threshold: Duration) -> Self {
+        self.stale.set_threshold(threshold);
+        self
+    }
+
+    /// Callback deciding what to do with a stale stream, it receives the stream name
+    /// and the time since its last message.
+    pub fn on_stale<Callback>(mut self, callback: Callback) -> Self
+    where
+        Callback: FnMut(&str, Duration) -> StaleAction + 'a,
+    {
+        self.stale.set_handler(Box::new(callback));
+        self
+    }
+
+    /// Handle to stop `event_loop` from another thread.
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop_handle.clone()
+    }
+
+    /// Per-stream latency, rate and last-message statistics.
+    pub fn monitor(&self) -> StreamMonitor {
+        self.monitor.clone()
+    }
+
+    pub fn connect(&mut self, subscription: &str) -> Result<()> {
+        self.stream_name = Some(subscription.into());
+        self.monitor.watch(&[subscription.into()]);
+        self.connect_wss(OptionsWebsocketAPI::Default.params(subscription))
+    }
+
+    pub fn connect_with_config(&mut self, subscription: &str, config: &Config) -> Result<()> {
+        self.stream_name = Some(subscription.into());
+        self.monitor.watch(&[subscription.into()]);
+        if config.proxy.is_some() {
+            self.proxy = config.proxy.clone();
+        }
+        self.connect_wss(
+            OptionsWebsocketAPI::Custom(config.options_ws_endpoint.clone()).params(subscription),
+        )
+    }
+
+    pub fn connect_multiple_streams(&mut self, endpoints: &[String]) -> Result<()> {
+        self.stream_name = None;
+        self.monitor.watch(endpoints);
+        self.connect_wss(OptionsWebsocketAPI::MultiStream.params(&endpoints.join("/")))
+    }
+
+    fn connect_wss(&mut self, wss: String) -> Result<()> {
+        let read_timeout = self.stale.read_timeout();
+        let (socket, response) = connect_socket(&wss, read_timeout, self.proxy.as_ref())?;
+        self.stop_handle.attach(&socket)?;
+        self.socket = Some((socket, response));
+        self.url = Some(wss);
+        Ok(())
+    }
+
+    fn check_stale_streams(&mut self) -> Result<()> {
+        if self.stale.check(&self.monitor)? {
+            if let Some(url) = self.url.clone() {
+                if let Some(ref mut socket) = self.socket {
+                    close_socket(&mut socket.0);
+                }
+                self.connect_wss(url)?;
+                self.monitor.rewatch();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn disconnect(&mut self) -> Result<()> {
+        if let Some(ref mut socket) = self.socket {
+            socket.0.close(None)?;
+            return Ok(());
+        }
+        bail!("Not able to close the connection");
+    }
+
+    pub fn test_handle_msg(&mut self, msg: &str) -> Result<()> {
+        self.handle_msg(msg)
+    }
+
+    fn handle_msg(&mut self, msg: &str) -> Result<()> {
+        let mut value: Value = serde_json::from_str(msg)?;
+
+        if let Some(data) = value.get_mut("data") {
+            let data = data.take();
+            let stream = match value.get("stream").and_then(|stream| stream.as_str()) {
+                Some(stream) => stream.to_owned(),
+                None => fallback_stream_name(&data),
+            };
+            self.monitor.record(&stream, event_time(&data));
+            return self.handle_event(data);
+        }
+
+        let stream = match self.stream_name {
+            Some(ref name) => name.clone(),
+            None => fallback_stream_name(&value),
+        };
+        self.monitor.record(&stream, event_time(&value));
+        self.handle_event(value)
+    }
+
+    fn handle_event(&mut self, value: Value) -> Result<()> {
+        if let Some(event) = options_event(value)? {
+            (self.handler)(event)?;
+        }
+        Ok(())
+    }
+
+    pub fn event_loop(&mut self, should_stop: &AtomicBool) -> Result<()> {
+        let mut ping_counter = 0;
+
+        while !should_stop.load(Ordering::Relaxed) && !self.stop_handle.is_stopped() {
+            if let Some(ref mut socket) = self.socket {
+                let message = socket.0.read_message();
+                match message {
+                    Ok(message) => match message {
+                        Message::Text(msg) => {
+                            if let Err(e) = self.handle_msg(&msg) {
+                                bail!(format!("Error on handling stream message: {}", e));
+                            }
+                        }
+                        Message::Ping(payload) => {
+                            socket.0.write_message(Message::Pong(payload)).unwrap();
+                        }
+                        Message::Pong(_) => {
+                            ping_counter = 0;
+                        }
+                        Message::Binary(_) => (),
+                        Message::Close(e) => bail!(format!("Disconnected {:?}", e)),
+                    },
+                    Err(_) if self.stop_handle.is_stopped() => break,
+                    Err(error) => {
+                        // Read timed out, ping the server and give up after 10 unanswered pings
+                        if let Err(err) = socket.0.write_message(Message::Ping(vec![])) {
+                            bail!(format!("Disconnected loop is dead {err:?} {error:?}"));
+                        };
+                        ping_counter += 1;
+
+                        if ping_counter >= 10 {
+                            bail!(format!("Disconnected loop is dead {error}"));
+                        }
+                    }
+                }
+            }
+            self.check_stale_streams()?;
+        }
+
+        if let Some(ref mut socket) = self.socket {
+            close_socket(&mut socket.0);
+        }
+        Ok(())
+    }
+}
+
+// Option events are decoded from their event type, the streams by underlying or
+// expiry send a list of events. Unknown events are ignored.
+fn options_event(value: Value) -> Result<Option<OptionsWebsocketEvent>> {
+    let event_type = match value {
+        Value::Array(ref items) => items.first().and_then(|item| item.get("e")),
+        _ => value.get("e"),
+    };
+    let event_type = match event_type.and_then(|event_type| event_type.as_str()) {
+        Some(event_type) => event_type.to_owned(),
+        None => return Ok(None),
+    };
+
+    let event = match (event_type.as_str(), value.is_array()) {
+        ("trade", false) => OptionsWebsocketEvent::Trade(serde_json::from_value(value)?),
+        ("index", false) => OptionsWebsocketEvent::IndexPrice(serde_json::from_value(value)?),
+        ("markPrice", true) => OptionsWebsocketEvent::MarkPriceAll(serde_json::from_value(value)?),
+        ("24hrTicker", false) => OptionsWebsocketEvent::Ticker(serde_json::from_value(value)?),
+        ("24hrTicker", true) => OptionsWebsocketEvent::TickerAll(serde_json::from_value(value)?),
+        ("kline", false) => OptionsWebsocketEvent::Kline(serde_json::from_value(value)?),
+        ("depth", false) => OptionsWebsocketEvent::DepthOrderBook(serde_json::from_value(value)?),
+        ("openInterest", true) => {
+            OptionsWebsocketEvent::OpenInterestAll(serde_json::from_value(value)?)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
@@ -0,0 +1,636 @@
+/*!
+Lifecycle of spot and USD-M futures orders.
+
+`OrderTracker` records the orders returned when placing them with `Account` or
+`FuturesAccount`, then follows them with the execution reports of the user data
+stream (`executionReport`, `ORDER_TRADE_UPDATE`): status transitions, fills and
+fees. Events lost while the stream was down are repaired by `reconcile`, which
+compares the tracked orders with the open orders and the status of the orders
+which are no longer open, and fetches the trades of the orders whose fills were
+missed.
+
+Status never goes back, a FILLED order stays FILLED when a late response or
+event still says NEW, and fills are counted once by trade id.
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+use crate::futures::model::{
+    Order as FuturesOrder, OrderTradeEvent as FuturesOrderTradeEvent,
+    Transaction as FuturesTransaction,
+};
+use crate::futures::websockets::FuturesWebsocketEvent;
+use crate::history::HistoryQuery;
+use crate::model::{number_to_f64, Order, OrderResponse, OrderTradeEvent, Transaction};
+use crate::symbol_registry::SymbolMarket;
+use crate::websockets::WebsocketEvent;
+
+// Executed quantity left over by the float sum of the fills
+const QTY_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderStatus {
+    New,
+    PartiallyFilled,
+    /// Spot only
+    PendingCancel,
+    Filled,
+    Canceled,
+    Rejected,
+    Expired,
+    /// Spot orders expired by self-trade prevention
+    ExpiredInMatch,
+    Other(String),
+}
+
+impl From<&str> for OrderStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "NEW" => OrderStatus::New,
+            "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+            "PENDING_CANCEL" => OrderStatus::PendingCancel,
+            "FILLED" => OrderStatus::Filled,
+            "CANCELED" => OrderStatus::Canceled,
+            "REJECTED" => OrderStatus::Rejected,
+            "EXPIRED" => OrderStatus::Expired,
+            "EXPIRED_IN_MATCH" => OrderStatus::ExpiredInMatch,
+            other => OrderStatus::Other(other.into()),
+        }
+    }
+}
+
+impl OrderStatus {
+    /// The order can't change anymore.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Filled
+                | OrderStatus::Canceled
+                | OrderStatus::Rejected
+                | OrderStatus::Expired
+                | OrderStatus::ExpiredInMatch
+        )
+    }
+
+    // Order of the statuses in the lifecycle
+    fn rank(&self) -> u8 {
+        match self {
+            OrderStatus::New | OrderStatus::Other(_) => 0,
+            OrderStatus::PartiallyFilled | OrderStatus::PendingCancel => 1,
+            _ => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackedFill {
+    /// None for the fills of the spot responses without a trade id
+    pub trade_id: Option<u64>,
+    pub price: f64,
+    pub qty: f64,
+    pub commission: f64,
+    pub commission_asset: Option<String>,
+    pub time: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackedOrder {
+    pub market: SymbolMarket,
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    /// Empty when the order is only known from an ACK response
+    pub side: String,
+    pub order_type: String,
+    pub price: f64,
+    pub orig_qty: f64,
+    pub executed_qty: f64,
+    /// Quote value of the executed quantity
+    pub cumulative_quote_qty: f64,
+    pub status: OrderStatus,
+    pub reject_reason: Option<String>,
+    pub fills: Vec<TrackedFill>,
+    /// Commission paid by asset
+    pub fees: HashMap<String, f64>,
+    pub update_time: u64,
+}
+
+impl TrackedOrder {
+    fn new(market: SymbolMarket, symbol: &str, order_id: u64) -> Self {
+        TrackedOrder {
+            market,
+            symbol: symbol.into(),
+            order_id,
+            client_order_id: String::new(),
+            side: String::new(),
+            order_type: String::new(),
+            price: 0.0,
+            orig_qty: 0.0,
+            executed_qty: 0.0,
+            cumulative_quote_qty: 0.0,
+            status: OrderStatus::New,
+            reject_reason: None,
+            fills: Vec::new(),
+            fees: HashMap::new(),
+            update_time: 0,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.status.is_final()
+    }
+
+    pub fn remaining_qty(&self) -> f64 {
+        (self.orig_qty - self.executed_qty).max(0.0)
+    }
+
+    /// Quantity of the tracked fills, below `executed_qty` when fills were missed.
+    pub fn filled_qty(&self) -> f64 {
+        self.fills.iter().map(|fill| fill.qty).sum()
+    }
+
+    /// Average price of the fills.
+    pub fn average_price(&self) -> Option<f64> {
+        if self.executed_qty > 0.0 && self.cumulative_quote_qty > 0.0 {
+            Some(self.cumulative_quote_qty / self.executed_qty)
+        } else {
+            None
+        }
+    }
+
+    // Static details, only set when still unknown
+    fn describe(
+        &mut self, client_order_id: &str, side: &str, order_type: &str, price: f64, qty: f64,
+    ) {
+        if self.client_order_id.is_empty() {
+            self.client_order_id = client_order_id.into();
+        }
+        if self.side.is_empty() {
+            self.side = side.into();
+        }
+        if self.order_type.is_empty() {
+            self.order_type = order_type.into();
+        }
+        if self.price == 0.0 {
+            self.price = price;
+        }
+        if self.orig_qty == 0.0 {
+            self.orig_qty = qty;
+        }
+    }
+
+    // Status and executed quantity reported by the exchange, returns whether the order changed
+    fn update(
+        &mut self, status: OrderStatus, executed_qty: f64, cumulative_quote_qty: f64,
+        update_time: u64,
+    ) -> bool {
+        let mut changed = false;
+        if status != self.status && !self.status.is_final() && status.rank() >= self.status.rank() {
+            self.status = status;
+            changed = true;
+        }
+        if executed_qty > self.executed_qty {
+            self.executed_qty = executed_qty;
+            changed = true;
+        }
+        if cumulative_quote_qty > self.cumulative_quote_qty {
+            self.cumulative_quote_qty = cumulative_quote_qty;
+            changed = true;
+        }
+        self.update_time = self.update_time.max(update_time);
+        changed
+    }
+
+    // Fills with a trade id are counted once
+    fn add_fill(&mut self, fill: TrackedFill) -> bool {
+        if fill.trade_id.is_some()
+            && self
+                .fills
+                .iter()
+                .any(|known| known.trade_id == fill.trade_id)
+        {
+            return false;
+        }
+        if let Some(ref asset) = fill.commission_asset {
+            *self.fees.entry(asset.clone()).or_insert(0.0) += fill.commission;
+        }
+        let quote_qty: f64 = self
+            .fills
+            .iter()
+            .map(|known| known.price * known.qty)
+            .sum::<f64>()
+            + fill.price * fill.qty;
+        self.cumulative_quote_qty = self.cumulative_quote_qty.max(quote_qty);
+        self.fills.push(fill);
+        true
+    }
+
+    // Trades of the order listed by the exchange, they replace the fills without a
+    // trade id, which they include. Returns whether a fill was added.
+    fn restore_fills(&mut self, trades: Vec<TrackedFill>) -> bool {
+        if self.fills.iter().any(|fill| fill.trade_id.is_none()) {
+            self.fills.clear();
+            self.fees.clear();
+        }
+        let mut added = false;
+        for trade in trades {
+            added |= self.add_fill(trade);
+        }
+        added
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OrderTracker {
+    orders: HashMap<(SymbolMarket, String, u64), TrackedOrder>,
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn order(
+        &self, market: SymbolMarket, symbol: &str, order_id: u64,
+    ) -> Option<&TrackedOrder> {
+        self.orders.get(&(market, symbol.to_string(), order_id))
+    }
+
+    pub fn order_by_client_id(
+        &self, market: SymbolMarket, client_order_id: &str,
+    ) -> Option<&TrackedOrder> {
+        self.orders
+            .values()
+            .find(|order| order.market == market && order.client_order_id == client_order_id)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values()
+    }
+
+    pub fn open_orders(&self, market: SymbolMarket) -> Vec<&TrackedOrder> {
+        self.orders
+            .values()
+            .filter(|order| order.market == market && order.is_open())
+            .collect()
+    }
+
+    /// Forget the orders which can't change anymore.
+    pub fn remove_closed(&mut self) {
+        self.orders.retain(|_, order| order.is_open());
+    }
+
+    fn entry(&mut self, market: SymbolMarket, symbol: &str, order_id: u64) -> &mut TrackedOrder {
+        self.orders
+            .entry((market, symbol.to_string(), order_id))
+            .or_insert_with(|| TrackedOrder::new(market, symbol, order_id))
+    }
+
+    /// Record a spot order from the response to placing it.
+    pub fn track_transaction(&mut self, transaction: &Transaction) {
+        let order = self.entry(
+            SymbolMarket::Spot,
+            &transaction.symbol,
+            transaction.order_id,
+        );
+        order.describe(
+            &transaction.client_order_id,
+            &transaction.side,
+            &transaction.type_name,
+            number_to_f64(transaction.price),
+            number_to_f64(transaction.orig_qty),
+        );
+        order.update(
+            transaction.status.as_str().into(),
+            number_to_f64(transaction.executed_qty),
+            number_to_f64(transaction.cummulative_quote_qty),
+            transaction.transact_time,
+        );
+        for fill in transaction.fills.iter().flatten() {
+            order.add_fill(TrackedFill {
+                trade_id: fill.trade_id,
+                price: number_to_f64(fill.price),
+                qty: number_to_f64(fill.qty),
+                commission: number_to_f64(fill.commission),
+                commission_asset: Some(fill.commission_asset.clone()),
+                time: transaction.transact_time,
+            });
+        }
+    }
+
+    /// Record a spot order from the response to `Account::place_order`.
+    pub fn track_order_response(&mut self, response: &OrderResponse) {
+        match response {
+            OrderResponse::Ack(ack) => {
+                let order = self.entry(SymbolMarket::Spot, &ack.symbol, ack.order_id);
+                order.describe(&ack.client_order_id, "", "", 0.0, 0.0);
+                order.update_time = order.update_time.max(ack.transact_time);
+            }
+            OrderResponse::Result(transaction) | OrderResponse::Full(transaction) => {
+                self.track_transaction(transaction)
+            }
+        }
+    }
+
+    /// Record or update a spot order from `get_open_orders` or `order_status`,
+    /// returns whether it was added or changed.
+    pub fn track_order(&mut self, order: &Order) -> bool {
+        let added = self
+            .order(SymbolMarket::Spot, &order.symbol, order.order_id)
+            .is_none();
+        let tracked = self.entry(SymbolMarket::Spot, &order.symbol, order.order_id);
+        tracked.describe(
+            &order.client_order_id,
+            &order.side,
+            &order.order_type,
+            number_to_f64(order.price),
+            number_to_f64(order.orig_qty),
+        );
+        tracked.update(
+            order.status.as_str().into(),
+            number_to_f64(order.executed_qty),
+            number_to_f64(order.cummulative_quote_qty),
+            order.update_time,
+        ) || added
+    }
+
+    /// Record a futures order from the response to placing it.
+    pub fn track_futures_transaction(&mut self, transaction: &FuturesTransaction) {
+        let order = self.entry(
+            SymbolMarket::Futures,
+            &transaction.symbol,
+            transaction.order_id,
+        );
+        order.describe(
+            &transaction.client_order_id,
+            &transaction.side,
+            &transaction.type_name,
+            transaction.price,
+            transaction.orig_qty,
+        );
+        order.update(
+            transaction.status.as_str().into(),
+            transaction.executed_qty,
+            transaction.cum_quote,
+            transaction.update_time,
+        );
+    }
+
+    /// Record or update a futures order from `get_all_open_orders` or
+    /// `get_order_status`, returns whether it was added or changed.
+    pub fn track_futures_order(&mut self, order: &FuturesOrder) -> bool {
+        let added = self
+            .order(SymbolMarket::Futures, &order.symbol, order.order_id)
+            .is_none();
+        let tracked = self.entry(SymbolMarket::Futures, &order.symbol, order.order_id);
+        tracked.describe(
+            &order.client_order_id,
+            &order.side,
+            &order.order_type,
+            order.price,
+            order.orig_qty,
+        );
+        tracked.update(
+            order.status.as_str().into(),
+            order.executed_qty,
+            order.cum_quote,
+            order.update_time,
+        ) || added
+    }
+
+    /// Apply the spot execution reports, other events are ignored.
+    pub fn handle_event(&mut self, event: &WebsocketEvent) -> Result<()> {
+        match event {
+            WebsocketEvent::OrderTrade(event) => self.on_order_trade(event),
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the futures execution reports, other events are ignored.
+    pub fn handle_futures_event(&mut self, event: &FuturesWebsocketEvent) -> Result<()> {
+        match event {
+            FuturesWebsocketEvent::OrderTrade(event) => self.on_futures_order_trade(event),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn on_order_trade(&mut self, event: &OrderTradeEvent) -> Result<()> {
+        let price = event.price.parse()?;
+        let qty = event.qty.parse()?;
+        let executed_qty = event.accumulated_qty_filled_trades.parse()?;
+        let fill = if event.execution_type == "TRADE" {
+            Some(TrackedFill {
+                trade_id: u64::try_from(event.trade_id).ok(),
+                price: event.price_last_filled_trade.parse()?,
+                qty: event.qty_last_filled_trade.parse()?,
+                commission: event.commission.parse()?,
+                commission_asset: event.asset_commisioned.clone(),
+                time: event.trade_order_time,
+            })
+        } else {
+            None
+        };
+
+        let order = self.entry(SymbolMarket::Spot, &event.symbol, event.order_id);
+        order.describe(
+            &event.new_client_order_id,
+            &event.side,
+            &event.order_type,
+            price,
+            qty,
+        );
+        if let Some(fill) = fill {
+            order.add_fill(fill);
+        }
+        order.update(
+            event.order_status.as_str().into(),
+            executed_qty,
+            0.0,
+            event.event_time,
+        );
+        if event.execution_type == "REJECTED" && event.order_reject_reason != "NONE" {
+            order.reject_reason = Some(event.order_reject_reason.clone());
+        }
+        Ok(())
+    }
+
+    pub fn on_futures_order_trade(&mut self, event: &FuturesOrderTradeEvent) -> Result<()> {
+        let update = &event.order;
+        let price = update.price.parse()?;
+        let qty = update.qty.parse()?;
+        let executed_qty = update.accumulated_qty_filled_trades.parse()?;
+        let fill = if update.execution_type == "TRADE" {
+            let commission = match update.commission {
+                Some(ref commission) => commission.parse()?,
+                None => 0.0,
+            };
+            Some(TrackedFill {
+                trade_id: u64::try_from(update.trade_id).ok(),
+                price: update.price_last_filled_trade.parse()?,
+                qty: update.qty_last_filled_trade.parse()?,
+                commission,
+                commission_asset: update.asset_commisioned.clone(),
+                time: update.trade_order_time,
+            })
+        } else {
+            None
+        };
+
+        let order = self.entry(SymbolMarket::Futures, &update.symbol, update.order_id);
+        order.describe(
+            &update.new_client_order_id,
+            &update.side,
+            &update.order_type,
+            price,
+            qty,
+        );
+        if let Some(fill) = fill {
+            order.add_fill(fill);
+        }
+        order.update(
+            update.order_status.as_str().into(),
+            executed_qty,
+            0.0,
+            event.event_time,
+        );
+        Ok(())
+    }
+
+    /// Repair the spot orders after missed events, e.g. once the user data stream
+    /// reconnected. Open orders unknown to the tracker are added, the orders which
+    /// are no longer open get their final status, and the fills missed are fetched
+    /// with `trade_history_query`. Returns the orders which changed.
+    pub fn reconcile(&mut self, account: &Account) -> Result<Vec<TrackedOrder>> {
+        let mut changed = Vec::new();
+        let mut open = HashSet::new();
+        for order in account.get_all_open_orders()? {
+            open.insert((order.symbol.clone(), order.order_id));
+            if self.track_order(&order) {
+                changed.push((order.symbol.clone(), order.order_id));
+            }
+        }
+
+        let missing = self.missing(SymbolMarket::Spot, &open);
+        for (symbol, order_id) in &missing {
+            let order = account.order_status(symbol.clone(), *order_id)?;
+            if self.track_order(&order) {
+                changed.push((symbol.clone(), *order_id));
+            }
+        }
+
+        let examined = open.into_iter().chain(missing).collect::<Vec<_>>();
+        self.restore_fills(SymbolMarket::Spot, examined, &mut changed, |query| {
+            account
+                .trade_history_query(query)?
+                .into_iter()
+                .map(|trade| {
+                    Ok(TrackedFill {
+                        trade_id: Some(trade.id),
+                        price: number_to_f64(trade.price),
+                        qty: number_to_f64(trade.qty),
+                        commission: trade.commission.parse()?,
+                        commission_asset: Some(trade.commission_asset),
+                        time: trade.time,
+                    })
+                })
+                .collect()
+        })?;
+
+        Ok(self.collect(SymbolMarket::Spot, changed))
+    }
+
+    /// Repair the futures orders after missed events, the open orders of the
+    /// symbols with tracked open orders are compared. Returns the orders which changed.
+    pub fn reconcile_futures(&mut self, account: &FuturesAccount) -> Result<Vec<TrackedOrder>> {
+        let mut symbols: Vec<String> = self
+            .open_orders(SymbolMarket::Futures)
+            .iter()
+            .map(|order| order.symbol.clone())
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+
+        let mut changed = Vec::new();
+        let mut open = HashSet::new();
+        for symbol in symbols {
+            for order in account.get_all_open_orders(symbol)? {
+                open.insert((order.symbol.clone(), order.order_id));
+                if self.track_futures_order(&order) {
+                    changed.push((order.symbol.clone(), order.order_id));
+                }
+            }
+        }
+
+        let missing = self.missing(SymbolMarket::Futures, &open);
+        for (symbol, order_id) in &missing {
+            let client_order_id = self.orders[&(SymbolMarket::Futures, symbol.clone(), *order_id)]
+                .client_order_id
+                .clone();
+            let order = account.get_order_status(symbol.clone(), client_order_id)?;
+            if self.track_futures_order(&order) {
+                changed.push((symbol.clone(), *order_id));
+            }
+        }
+
+        let examined = open.into_iter().chain(missing).collect::<Vec<_>>();
+        self.restore_fills(SymbolMarket::Futures, examined, &mut changed, |query| {
+            Ok(account
+                .trade_history_query(query)?
+                .into_iter()
+                .map(|trade| TrackedFill {
+                    trade_id: Some(trade.id),
+                    price: trade.price,
+                    qty: trade.qty,
+                    commission: trade.commission,
+                    commission_asset: Some(trade.commission_asset),
+                    time: trade.time,
+                })
+                .collect())
+        })?;
+
+        Ok(self.collect(SymbolMarket::Futures, changed))
+    }
+
+    // Fetch the trades of the orders executed beyond their tracked fills
+    fn restore_fills<F>(
+        &mut self, market: SymbolMarket, keys: Vec<(String, u64)>,
+        changed: &mut Vec<(String, u64)>, mut fetch: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&HistoryQuery) -> Result<Vec<TrackedFill>>,
+    {
+        for (symbol, order_id) in keys {
+            let order = match self.orders.get_mut(&(market, symbol.clone(), order_id)) {
+                Some(order) if order.executed_qty - order.filled_qty() > QTY_TOLERANCE => order,
+                _ => continue,
+            };
+            let query = HistoryQuery::new(symbol.clone())
+                .set_order_id(order_id)
+                .set_limit(1000);
+            if order.restore_fills(fetch(&query)?) && !changed.contains(&(symbol.clone(), order_id))
+            {
+                changed.push((symbol, order_id));
+            }
+        }
+        Ok(())
+    }
+
+    // Tracked open orders which the exchange no longer lists as open
+    fn missing(&self, market: SymbolMarket, open: &HashSet<(String, u64)>) -> Vec<(String, u64)> {
+        self.open_orders(market)
+            .iter()
+            .map(|order| (order.symbol.clone(), order.order_id))
+            .filter(|key| !open.contains(key))
+            .collect()
+    }
+
+    fn collect(&self, market: SymbolMarket, keys: Vec<(String, u64)>) -> Vec<TrackedOrder> {
+        keys.into_iter()
+            .filter_map(|(symbol, order_id)| self.order(market, &symbol, order_id).cloned())
+            .collect()
+    }
+}
@@ -0,0 +1,355 @@
+/*!
+Valuation of the spot, margin and USD-M futures wallets in one quote asset.
+
+A `PortfolioSnapshot` holds the balances of the wallets and the latest prices, as
+returned by `Account::get_account`, `MarginAccount::get_account` or
+`get_isolated_account`, `FuturesAccount::account_balance` and
+`Market::get_all_prices`. It is either fetched with `PortfolioSnapshot::fetch` or
+built from deserialized responses, and valued with `valuation`.
+
+An asset without a pair with the quote asset is priced through an intermediate
+asset, USDT, BTC, ETH, BNB, FDUSD or USDC unless changed with `set_intermediates`:
+ETH in EUR is ETHUSDT * EUR/USDT when neither ETHEUR nor EURETH is listed.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::account::Account;
+use crate::errors::*;
+use crate::futures::account::FuturesAccount;
+use crate::futures::model::AccountBalance;
+use crate::margin::account::MarginAccount;
+use crate::market::Market;
+use crate::model::{
+    number_to_f64, AccountInformation, IsoMarginAccountInformation, MarginAccountInformation,
+    MarginAsset, Prices,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Wallet {
+    Spot,
+    CrossMargin,
+    IsolatedMargin,
+    Futures,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioSnapshot {
+    pub spot: Option<AccountInformation>,
+    pub margin: Option<MarginAccountInformation>,
+    pub isolated_margin: Option<IsoMarginAccountInformation>,
+    pub futures: Option<Vec<AccountBalance>>,
+    /// Latest price by symbol
+    pub prices: HashMap<String, f64>,
+    intermediates: Vec<String>,
+}
+
+impl Default for PortfolioSnapshot {
+    fn default() -> Self {
+        PortfolioSnapshot {
+            spot: None,
+            margin: None,
+            isolated_margin: None,
+            futures: None,
+            prices: HashMap::new(),
+            intermediates: ["USDT", "BTC", "ETH", "BNB", "FDUSD", "USDC"]
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl PortfolioSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch the balances of the accounts given and the latest prices. The margin
+    /// account is the isolated one when `MarginAccount::is_isolated` is set.
+    pub fn fetch(
+        market: &Market, account: Option<&Account>, margin: Option<&MarginAccount>,
+        futures: Option<&FuturesAccount>,
+    ) -> Result<Self> {
+        let mut snapshot = PortfolioSnapshot::new().set_prices(market.get_all_prices()?);
+        if let Some(account) = account {
+            snapshot = snapshot.set_spot(account.get_account()?);
+        }
+        if let Some(margin) = margin {
+            snapshot = if margin.is_isolated {
+                snapshot.set_isolated_margin(margin.get_isolated_account()?)
+            } else {
+                snapshot.set_margin(margin.get_account()?)
+            };
+        }
+        if let Some(futures) = futures {
+            snapshot = snapshot.set_futures(futures.account_balance()?);
+        }
+        Ok(snapshot)
+    }
+
+    pub fn set_spot(mut self, spot: AccountInformation) -> Self {
+        self.spot = Some(spot);
+        self
+    }
+
+    pub fn set_margin(mut self, margin: MarginAccountInformation) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    pub fn set_isolated_margin(mut self, isolated_margin: IsoMarginAccountInformation) -> Self {
+        self.isolated_margin = Some(isolated_margin);
+        self
+    }
+
+    pub fn set_futures(mut self, futures: Vec<AccountBalance>) -> Self {
+        self.futures = Some(futures);
+        self
+    }
+
+    pub fn set_prices(mut self, prices: Prices) -> Self {
+        let Prices::AllPrices(prices) = prices;
+        self.prices = prices
+            .into_iter()
+            .map(|price| (price.symbol, number_to_f64(price.price)))
+            .collect();
+        self
+    }
+
+    /// Assets tried, in order, to price an asset without a pair with the quote asset.
+    pub fn set_intermediates(mut self, intermediates: Vec<String>) -> Self {
+        self.intermediates = intermediates;
+        self
+    }
+
+    /// Price of `asset` in `quote`, from their pair or through an intermediate asset.
+    pub fn price(&self, asset: &str, quote: &str) -> Option<f64> {
+        if let Some(price) = self.pair_price(asset, quote) {
+            return Some(price);
+        }
+        self.intermediates
+            .iter()
+            .filter(|intermediate| intermediate.as_str() != asset && intermediate.as_str() != quote)
+            .find_map(|intermediate| {
+                Some(self.pair_price(asset, intermediate)? * self.pair_price(intermediate, quote)?)
+            })
+    }
+
+    // Price from the symbol of the two assets, either way round
+    fn pair_price(&self, asset: &str, quote: &str) -> Option<f64> {
+        if asset == quote {
+            return Some(1.0);
+        }
+        if let Some(price) = self.prices.get(&format!("{}{}", asset, quote)) {
+            return Some(*price);
+        }
+        match self.prices.get(&format!("{}{}", quote, asset)) {
+            Some(price) if *price > 0.0 => Some(1.0 / price),
+            _ => None,
+        }
+    }
+
+    /// Balances of every wallet, in `quote`.
+    pub fn valuation<S>(&self, quote: S) -> Result<PortfolioValuation>
+    where
+        S: Into<String>,
+    {
+        let quote = quote.into();
+        let mut holdings = Vec::new();
+
+        if let Some(ref spot) = self.spot {
+            for balance in &spot.balances {
+                let quantity = balance.free.parse::<f64>()? + balance.locked.parse::<f64>()?;
+                holdings.push(Holding::new(
+                    Wallet::Spot,
+                    &balance.asset,
+                    None,
+                    quantity,
+                    0.0,
+                ));
+            }
+        }
+        if let Some(ref margin) = self.margin {
+            for asset in &margin.assets {
+                holdings.push(Holding::margin(Wallet::CrossMargin, asset, None));
+            }
+        }
+        if let Some(ref isolated_margin) = self.isolated_margin {
+            for pair in &isolated_margin.assets {
+                for asset in &[&pair.base_asset, &pair.quote_asset] {
+                    holdings.push(Holding::margin(
+                        Wallet::IsolatedMargin,
+                        asset,
+                        Some(pair.symbol.clone()),
+                    ));
+                }
+            }
+        }
+        if let Some(ref futures) = self.futures {
+            for balance in futures {
+                let quantity = balance.balance + balance.cross_unrealized_pnl;
+                holdings.push(Holding::new(
+                    Wallet::Futures,
+                    &balance.asset,
+                    None,
+                    quantity,
+                    0.0,
+                ));
+            }
+        }
+
+        holdings.retain(|holding| holding.quantity != 0.0 || holding.liability != 0.0);
+        for holding in holdings.iter_mut() {
+            holding.price = self.price(&holding.asset, &quote);
+        }
+
+        Ok(PortfolioValuation { quote, holdings })
+    }
+}
+
+/// Balance of one asset in one wallet.
+#[derive(Debug, Clone)]
+pub struct Holding {
+    pub wallet: Wallet,
+    pub asset: String,
+    /// Pair of the isolated margin balances
+    pub symbol: Option<String>,
+    /// Free and locked balance, wallet balance and unrealized PnL for futures
+    pub quantity: f64,
+    /// Borrowed and interest of the margin wallets
+    pub liability: f64,
+    /// Price in the quote asset, None without a route to it
+    pub price: Option<f64>,
+}
+
+impl Holding {
+    fn new(
+        wallet: Wallet, asset: &str, symbol: Option<String>, quantity: f64, liability: f64,
+    ) -> Self {
+        Holding {
+            wallet,
+            asset: asset.into(),
+            symbol,
+            quantity,
+            liability,
+            price: None,
+        }
+    }
+
+    fn margin(wallet: Wallet, asset: &MarginAsset, symbol: Option<String>) -> Self {
+        Holding::new(
+            wallet,
+            &asset.asset,
+            symbol,
+            asset.free + asset.locked,
+            asset.borrowed + asset.interest,
+        )
+    }
+
+    pub fn net_quantity(&self) -> f64 {
+        self.quantity - self.liability
+    }
+
+    /// Net value in the quote asset.
+    pub fn value(&self) -> Option<f64> {
+        self.price.map(|price| self.net_quantity() * price)
+    }
+}
+
+/// Holdings of one asset over the wallets.
+#[derive(Debug, Clone)]
+pub struct AssetValuation {
+    pub asset: String,
+    pub quantity: f64,
+    pub liability: f64,
+    pub price: Option<f64>,
+}
+
+impl AssetValuation {
+    pub fn net_quantity(&self) -> f64 {
+        self.quantity - self.liability
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.price.map(|price| self.net_quantity() * price)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioValuation {
+    pub quote: String,
+    pub holdings: Vec<Holding>,
+}
+
+impl PortfolioValuation {
+    /// Net value of the priced holdings.
+    pub fn total(&self) -> f64 {
+        self.holdings.iter().filter_map(Holding::value).sum()
+    }
+
+    /// Value of the priced assets, before the liabilities.
+    pub fn gross_assets(&self) -> f64 {
+        self.holdings
+            .iter()
+            .filter_map(|holding| holding.price.map(|price| holding.quantity * price))
+            .sum()
+    }
+
+    /// Value of the priced liabilities.
+    pub fn liabilities(&self) -> f64 {
+        self.holdings
+            .iter()
+            .filter_map(|holding| holding.price.map(|price| holding.liability * price))
+            .sum()
+    }
+
+    /// Net value of each wallet.
+    pub fn wallets(&self) -> BTreeMap<Wallet, f64> {
+        let mut wallets = BTreeMap::new();
+        for holding in &self.holdings {
+            *wallets.entry(holding.wallet).or_insert(0.0) += holding.value().unwrap_or(0.0);
+        }
+        wallets
+    }
+
+    /// Holdings added up by asset, sorted by asset.
+    pub fn assets(&self) -> Vec<AssetValuation> {
+        let mut assets: BTreeMap<&str, AssetValuation> = BTreeMap::new();
+        for holding in &self.holdings {
+            let asset = assets
+                .entry(holding.asset.as_str())
+                .or_insert_with(|| AssetValuation {
+                    asset: holding.asset.clone(),
+                    quantity: 0.0,
+                    liability: 0.0,
+                    price: holding.price,
+                });
+            asset.quantity += holding.quantity;
+            asset.liability += holding.liability;
+        }
+        assets.into_values().collect()
+    }
+
+    /// Net value held in other assets than the quote asset, exposed to their price.
+    pub fn net_exposure(&self) -> f64 {
+        self.holdings
+            .iter()
+            .filter(|holding| holding.asset != self.quote)
+            .filter_map(Holding::value)
+            .sum()
+    }
+
+    /// Assets without a route to the quote asset, left out of the totals.
+    pub fn unpriced(&self) -> Vec<&str> {
+        let mut unpriced: Vec<&str> = self
+            .holdings
+            .iter()
+            .filter(|holding| holding.price.is_none())
+            .map(|holding| holding.asset.as_str())
+            .collect();
+        unpriced.sort_unstable();
+        unpriced.dedup();
+        unpriced
+    }
+}
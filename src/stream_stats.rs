@@ -0,0 +1,308 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use crate::errors::*;
+
+// Number of recent messages kept per stream for percentiles and rates
+const SAMPLE_CAPACITY: usize = 1024;
+
+/// What to do when a stream has been quiet for longer than the configured threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StaleAction {
+    /// Keep the connection; the stream is reported again only after it resumes and goes quiet again.
+    Ignore,
+    /// Reopen the websocket on the same url.
+    Reconnect,
+    /// Leave the event loop with an error.
+    Disconnect,
+}
+
+/// Callback deciding what to do with a stale stream, see `StaleAction`.
+pub type StaleHandler<'a> = Box<dyn FnMut(&str, Duration) -> StaleAction + 'a>;
+
+// Read timeout of the websockets when no stale threshold asks for a shorter one
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stale threshold and handler shared by the spot, futures and options websockets.
+#[derive(Default)]
+pub(crate) struct StaleWatch<'a> {
+    threshold: Option<Duration>,
+    handler: Option<StaleHandler<'a>>,
+}
+
+impl<'a> StaleWatch<'a> {
+    pub(crate) fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = Some(threshold);
+    }
+
+    pub(crate) fn set_handler(&mut self, handler: StaleHandler<'a>) {
+        self.handler = Some(handler);
+    }
+
+    // Reads must return often enough to notice stale streams
+    pub(crate) fn read_timeout(&self) -> Duration {
+        match self.threshold {
+            Some(threshold) if threshold < DEFAULT_READ_TIMEOUT && !threshold.is_zero() => {
+                threshold
+            }
+            _ => DEFAULT_READ_TIMEOUT,
+        }
+    }
+
+    // Ask the handler about every newly stale stream, `true` means the socket must be reopened.
+    // Without a handler a stale stream reconnects.
+    pub(crate) fn check(&mut self, monitor: &StreamMonitor) -> Result<bool> {
+        let threshold = match self.threshold {
+            Some(threshold) => threshold,
+            None => return Ok(false),
+        };
+
+        let mut reconnect = false;
+        for (stream, age) in monitor.take_stale(threshold) {
+            let action = match self.handler {
+                Some(ref mut handler) => handler(&stream, age),
+                None => StaleAction::Reconnect,
+            };
+            match action {
+                StaleAction::Ignore => (),
+                StaleAction::Reconnect => reconnect = true,
+                StaleAction::Disconnect => {
+                    bail!(format!("Stream {} is stale, no message for {:?}", stream, age))
+                }
+            }
+        }
+        Ok(reconnect)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    received: Instant,
+    latency: Option<i64>,
+}
+
+/// Event-time-to-receive latency percentiles, in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub p50: i64,
+    pub p90: i64,
+    pub p99: i64,
+    pub max: i64,
+}
+
+/// Statistics for a single stream (`btcusdt@trade`, a listen key, ...).
+#[derive(Clone, Debug)]
+pub struct StreamStats {
+    pub messages: u64,
+    watched_since: Instant,
+    first_message: Option<Instant>,
+    last_message: Option<Instant>,
+    last_event_time: Option<u64>,
+    samples: VecDeque<Sample>,
+    reported_stale: bool,
+}
+
+impl StreamStats {
+    fn new(now: Instant) -> Self {
+        StreamStats {
+            messages: 0,
+            watched_since: now,
+            first_message: None,
+            last_message: None,
+            last_event_time: None,
+            samples: VecDeque::with_capacity(SAMPLE_CAPACITY),
+            reported_stale: false,
+        }
+    }
+
+    fn record(&mut self, now: Instant, event_time: Option<u64>, received_ms: u64) {
+        self.messages += 1;
+        self.first_message.get_or_insert(now);
+        self.last_message = Some(now);
+        self.reported_stale = false;
+
+        let latency = event_time.map(|event_time| received_ms as i64 - event_time as i64);
+        if event_time.is_some() {
+            self.last_event_time = event_time;
+        }
+
+        if self.samples.len() == SAMPLE_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample { received: now, latency });
+    }
+
+    /// Time since the last message, or since the stream was subscribed if nothing arrived yet.
+    pub fn last_message_age(&self) -> Duration {
+        self.last_message.unwrap_or(self.watched_since).elapsed()
+    }
+
+    /// Event time (`E`) of the most recent message that carried one.
+    pub fn last_event_time(&self) -> Option<u64> {
+        self.last_event_time
+    }
+
+    /// Latency percentile (0.0 - 100.0) over the recent messages, in milliseconds.
+    ///
+    /// Negative values mean the local clock is behind the exchange clock.
+    pub fn latency_percentile(&self, percentile: f64) -> Option<i64> {
+        let mut latencies = self.latencies();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+        Some(percentile_of(&latencies, percentile))
+    }
+
+    pub fn latency_summary(&self) -> Option<LatencySummary> {
+        let mut latencies = self.latencies();
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+        Some(LatencySummary {
+            p50: percentile_of(&latencies, 50.0),
+            p90: percentile_of(&latencies, 90.0),
+            p99: percentile_of(&latencies, 99.0),
+            max: latencies[latencies.len() - 1],
+        })
+    }
+
+    /// Messages per second over the recent messages.
+    pub fn message_rate(&self) -> f64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) if self.samples.len() > 1 => {
+                let span = last.received.duration_since(first.received).as_secs_f64();
+                if span > 0.0 {
+                    (self.samples.len() - 1) as f64 / span
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Messages per second since the first message of the stream.
+    pub fn lifetime_message_rate(&self) -> f64 {
+        match self.first_message {
+            Some(first) => {
+                let span = first.elapsed().as_secs_f64();
+                if span > 0.0 {
+                    self.messages as f64 / span
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        }
+    }
+
+    fn latencies(&self) -> Vec<i64> {
+        self.samples.iter().filter_map(|sample| sample.latency).collect()
+    }
+}
+
+fn percentile_of(sorted: &[i64], percentile: f64) -> i64 {
+    let percentile = percentile.clamp(0.0, 100.0);
+    let rank = (percentile / 100.0 * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank]
+}
+
+/// Cloneable handle to the statistics of every stream on a websocket connection.
+///
+/// The handle can be read from another thread while the event loop is running.
+#[derive(Clone, Default)]
+pub struct StreamMonitor {
+    streams: Arc<Mutex<HashMap<String, StreamStats>>>,
+}
+
+impl StreamMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of the statistics for ONE stream.
+    pub fn stats(&self, stream: &str) -> Option<StreamStats> {
+        self.streams.lock().unwrap().get(stream).cloned()
+    }
+
+    /// Snapshot of the statistics for ALL streams.
+    pub fn snapshot(&self) -> HashMap<String, StreamStats> {
+        self.streams.lock().unwrap().clone()
+    }
+
+    pub fn stream_names(&self) -> Vec<String> {
+        self.streams.lock().unwrap().keys().cloned().collect()
+    }
+
+    pub(crate) fn watch(&self, streams: &[String]) {
+        let now = Instant::now();
+        let mut map = self.streams.lock().unwrap();
+        for stream in streams {
+            map.entry(stream.clone())
+                .or_insert_with(|| StreamStats::new(now));
+        }
+    }
+
+    // Restart the quiet period of every stream, used after a reconnect
+    pub(crate) fn rewatch(&self) {
+        let now = Instant::now();
+        for stats in self.streams.lock().unwrap().values_mut() {
+            stats.watched_since = now;
+            stats.last_message = None;
+            stats.reported_stale = false;
+        }
+    }
+
+    pub(crate) fn record(&self, stream: &str, event_time: Option<u64>) {
+        let now = Instant::now();
+        let received_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        let mut map = self.streams.lock().unwrap();
+        map.entry(stream.into())
+            .or_insert_with(|| StreamStats::new(now))
+            .record(now, event_time, received_ms);
+    }
+
+    // Streams quiet for longer than `threshold` which were not reported yet.
+    // They are marked as reported until their next message.
+    pub(crate) fn take_stale(&self, threshold: Duration) -> Vec<(String, Duration)> {
+        let mut stale = Vec::new();
+        for (name, stats) in self.streams.lock().unwrap().iter_mut() {
+            let age = stats.last_message_age();
+            if !stats.reported_stale && age > threshold {
+                stats.reported_stale = true;
+                stale.push((name.clone(), age));
+            }
+        }
+        stale
+    }
+}
+
+/// Event time (`E`) of a decoded message, for arrays the first element is used.
+pub(crate) fn event_time(value: &Value) -> Option<u64> {
+    match value {
+        Value::Array(items) => items.first().and_then(event_time),
+        _ => value.get("E").and_then(Value::as_u64),
+    }
+}
+
+/// Stream name used when the payload does not come from a combined stream.
+pub(crate) fn fallback_stream_name(value: &Value) -> String {
+    let item = match value {
+        Value::Array(items) => items.first().unwrap_or(value),
+        _ => value,
+    };
+    let event_type = item.get("e").and_then(Value::as_str).unwrap_or("unknown");
+    match item.get("s").and_then(Value::as_str) {
+        Some(symbol) => format!("{}@{}", symbol.to_lowercase(), event_type),
+        None => event_type.into(),
+    }
+}
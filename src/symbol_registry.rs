@@ -0,0 +1,477 @@
+/*!
+Cached spot and USD-M futures symbol metadata.
+
+`SymbolRegistry` downloads `exchangeInfo` once per market and indexes the symbols
+by name and by base and quote asset, instead of downloading and scanning it on
+every `get_symbol_info`. The registry is cheap to clone and shared between
+threads, `refresh` reloads it on demand and `spawn_refresh` on a schedule.
+Every reload is compared with the previous one and the listed, halted and
+delisted symbols are sent to the `subscribe` receivers.
+*/
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::errors::*;
+use crate::filters::SymbolFilters;
+use crate::futures::general::FuturesGeneral;
+use crate::futures::model::{
+    ExchangeInformation as FuturesExchangeInformation, Symbol as FuturesSymbol,
+};
+use crate::general::General;
+use crate::model::{ExchangeInformation, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolMarket {
+    Spot,
+    /// USD-M futures
+    Futures,
+}
+
+/// Difference between two loads of the exchange information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolChange {
+    Added {
+        market: SymbolMarket,
+        symbol: String,
+        status: String,
+    },
+    /// Trading halted or resumed, "TRADING" to "BREAK", "HALT", "SETTLING"...
+    StatusChanged {
+        market: SymbolMarket,
+        symbol: String,
+        previous: String,
+        status: String,
+    },
+    /// Delisted
+    Removed {
+        market: SymbolMarket,
+        symbol: String,
+    },
+}
+
+impl SymbolChange {
+    pub fn market(&self) -> SymbolMarket {
+        match self {
+            SymbolChange::Added { market, .. }
+            | SymbolChange::StatusChanged { market, .. }
+            | SymbolChange::Removed { market, .. } => *market,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            SymbolChange::Added { symbol, .. }
+            | SymbolChange::StatusChanged { symbol, .. }
+            | SymbolChange::Removed { symbol, .. } => symbol,
+        }
+    }
+}
+
+// Symbols of one market indexed by name and assets
+#[derive(Debug)]
+struct Index<S> {
+    loaded: bool,
+    symbols: HashMap<String, S>,
+    by_base: HashMap<String, Vec<String>>,
+    by_quote: HashMap<String, Vec<String>>,
+    by_assets: HashMap<(String, String), Vec<String>>,
+}
+
+impl<S> Default for Index<S> {
+    fn default() -> Self {
+        Index {
+            loaded: false,
+            symbols: HashMap::new(),
+            by_base: HashMap::new(),
+            by_quote: HashMap::new(),
+            by_assets: HashMap::new(),
+        }
+    }
+}
+
+// Name, status, base and quote asset of a symbol of either market
+trait Listing {
+    fn name(&self) -> &str;
+    fn status(&self) -> &str;
+    fn base_asset(&self) -> &str;
+    fn quote_asset(&self) -> &str;
+}
+
+impl Listing for SpotListing {
+    fn name(&self) -> &str {
+        &self.symbol.symbol
+    }
+    fn status(&self) -> &str {
+        &self.symbol.status
+    }
+    fn base_asset(&self) -> &str {
+        &self.symbol.base_asset
+    }
+    fn quote_asset(&self) -> &str {
+        &self.symbol.quote_asset
+    }
+}
+
+impl Listing for FuturesSymbol {
+    fn name(&self) -> &str {
+        &self.symbol
+    }
+    fn status(&self) -> &str {
+        &self.status
+    }
+    fn base_asset(&self) -> &str {
+        &self.base_asset
+    }
+    fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+}
+
+impl<S: Listing> Index<S> {
+    // Replace the symbols, returns the changes since the previous load
+    fn load(&mut self, market: SymbolMarket, symbols: Vec<S>) -> Vec<SymbolChange> {
+        let mut index = Index {
+            loaded: true,
+            ..Index::default()
+        };
+        for symbol in symbols {
+            let name = symbol.name().to_string();
+            let base = symbol.base_asset().to_string();
+            let quote = symbol.quote_asset().to_string();
+            index
+                .by_base
+                .entry(base.clone())
+                .or_default()
+                .push(name.clone());
+            index
+                .by_quote
+                .entry(quote.clone())
+                .or_default()
+                .push(name.clone());
+            index
+                .by_assets
+                .entry((base, quote))
+                .or_default()
+                .push(name.clone());
+            index.symbols.insert(name, symbol);
+        }
+
+        let mut changes = Vec::new();
+        if self.loaded {
+            for (name, symbol) in &index.symbols {
+                match self.symbols.get(name) {
+                    None => changes.push(SymbolChange::Added {
+                        market,
+                        symbol: name.clone(),
+                        status: symbol.status().to_string(),
+                    }),
+                    Some(previous) if previous.status() != symbol.status() => {
+                        changes.push(SymbolChange::StatusChanged {
+                            market,
+                            symbol: name.clone(),
+                            previous: previous.status().to_string(),
+                            status: symbol.status().to_string(),
+                        })
+                    }
+                    Some(_) => (),
+                }
+            }
+            for name in self.symbols.keys() {
+                if !index.symbols.contains_key(name) {
+                    changes.push(SymbolChange::Removed {
+                        market,
+                        symbol: name.clone(),
+                    });
+                }
+            }
+            changes.sort_by(|a, b| a.symbol().cmp(b.symbol()));
+        }
+
+        *self = index;
+        changes
+    }
+
+    fn status(&self, symbol: &str) -> Option<String> {
+        self.symbols
+            .get(&symbol.to_uppercase())
+            .map(|symbol| symbol.status().to_string())
+    }
+}
+
+fn names(list: Option<&Vec<String>>) -> Vec<String> {
+    list.cloned().unwrap_or_default()
+}
+
+// Spot symbol with its filters parsed once
+#[derive(Debug, Clone)]
+struct SpotListing {
+    symbol: Symbol,
+    filters: SymbolFilters,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    spot: Index<SpotListing>,
+    futures: Index<FuturesSymbol>,
+}
+
+#[derive(Clone, Default)]
+pub struct SymbolRegistry {
+    spot_general: Option<General>,
+    futures_general: Option<FuturesGeneral>,
+    registry: Arc<RwLock<Registry>>,
+    subscribers: Arc<Mutex<Vec<Sender<SymbolChange>>>>,
+}
+
+impl SymbolRegistry {
+    /// Empty registry, filled with `refresh` or the `apply_*` methods.
+    pub fn new(spot_general: Option<General>, futures_general: Option<FuturesGeneral>) -> Self {
+        SymbolRegistry {
+            spot_general,
+            futures_general,
+            ..SymbolRegistry::default()
+        }
+    }
+
+    /// Registry loaded with the exchange information of the given markets.
+    pub fn load(
+        spot_general: Option<General>, futures_general: Option<FuturesGeneral>,
+    ) -> Result<Self> {
+        let registry = Self::new(spot_general, futures_general);
+        registry.refresh()?;
+        Ok(registry)
+    }
+
+    /// Reload the exchange information of every market the registry was created with.
+    pub fn refresh(&self) -> Result<Vec<SymbolChange>> {
+        let mut changes = Vec::new();
+        if let Some(ref general) = self.spot_general {
+            changes.extend(self.apply_spot_exchange_info(general.exchange_info()?)?);
+        }
+        if let Some(ref general) = self.futures_general {
+            changes.extend(self.apply_futures_exchange_info(general.exchange_info()?));
+        }
+        Ok(changes)
+    }
+
+    /// Reload the registry every `interval` from a background thread, until the
+    /// returned handle is dropped or stopped.
+    pub fn spawn_refresh(&self, interval: Duration) -> RegistryRefresh {
+        let last_error = Arc::new(Mutex::new(None));
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let thread = {
+            let registry = self.clone();
+            let last_error = last_error.clone();
+            thread::spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    let result = registry.refresh();
+                    *last_error.lock().unwrap() = result.err().map(|e| e.to_string());
+                }
+            })
+        };
+
+        RegistryRefresh {
+            last_error,
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Receiver of the changes found by the next reloads, the first load of a
+    /// market reports no changes.
+    pub fn subscribe(&self) -> Receiver<SymbolChange> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    pub fn apply_spot_exchange_info(&self, info: ExchangeInformation) -> Result<Vec<SymbolChange>> {
+        let mut listings = Vec::with_capacity(info.symbols.len());
+        for symbol in info.symbols {
+            let filters = SymbolFilters::new(&symbol)?;
+            listings.push(SpotListing { symbol, filters });
+        }
+        let changes = self
+            .registry
+            .write()
+            .unwrap()
+            .spot
+            .load(SymbolMarket::Spot, listings);
+        self.publish(&changes);
+        Ok(changes)
+    }
+
+    pub fn apply_futures_exchange_info(
+        &self, info: FuturesExchangeInformation,
+    ) -> Vec<SymbolChange> {
+        let changes = self
+            .registry
+            .write()
+            .unwrap()
+            .futures
+            .load(SymbolMarket::Futures, info.symbols);
+        self.publish(&changes);
+        changes
+    }
+
+    fn publish(&self, changes: &[SymbolChange]) {
+        if changes.is_empty() {
+            return;
+        }
+        // Receivers which were dropped are forgotten
+        self.subscribers.lock().unwrap().retain(|subscriber| {
+            changes
+                .iter()
+                .all(|change| subscriber.send(change.clone()).is_ok())
+        });
+    }
+
+    pub fn is_loaded(&self, market: SymbolMarket) -> bool {
+        let registry = self.registry.read().unwrap();
+        match market {
+            SymbolMarket::Spot => registry.spot.loaded,
+            SymbolMarket::Futures => registry.futures.loaded,
+        }
+    }
+
+    pub fn contains(&self, market: SymbolMarket, symbol: &str) -> bool {
+        self.status(market, symbol).is_some()
+    }
+
+    /// Trading status of the symbol ("TRADING", "BREAK", "SETTLING"...).
+    pub fn status(&self, market: SymbolMarket, symbol: &str) -> Option<String> {
+        let registry = self.registry.read().unwrap();
+        match market {
+            SymbolMarket::Spot => registry.spot.status(symbol),
+            SymbolMarket::Futures => registry.futures.status(symbol),
+        }
+    }
+
+    pub fn is_trading(&self, market: SymbolMarket, symbol: &str) -> bool {
+        self.status(market, symbol).as_deref() == Some("TRADING")
+    }
+
+    /// Names of every symbol of the market.
+    pub fn symbols(&self, market: SymbolMarket) -> Vec<String> {
+        let registry = self.registry.read().unwrap();
+        match market {
+            SymbolMarket::Spot => registry.spot.symbols.keys().cloned().collect(),
+            SymbolMarket::Futures => registry.futures.symbols.keys().cloned().collect(),
+        }
+    }
+
+    /// Symbols trading `asset` against any quote asset.
+    pub fn symbols_by_base(&self, market: SymbolMarket, asset: &str) -> Vec<String> {
+        let registry = self.registry.read().unwrap();
+        let asset = asset.to_uppercase();
+        match market {
+            SymbolMarket::Spot => names(registry.spot.by_base.get(&asset)),
+            SymbolMarket::Futures => names(registry.futures.by_base.get(&asset)),
+        }
+    }
+
+    /// Symbols quoted in `asset`.
+    pub fn symbols_by_quote(&self, market: SymbolMarket, asset: &str) -> Vec<String> {
+        let registry = self.registry.read().unwrap();
+        let asset = asset.to_uppercase();
+        match market {
+            SymbolMarket::Spot => names(registry.spot.by_quote.get(&asset)),
+            SymbolMarket::Futures => names(registry.futures.by_quote.get(&asset)),
+        }
+    }
+
+    /// Symbols trading `base` against `quote`, futures have one per contract type.
+    pub fn symbols_by_assets(&self, market: SymbolMarket, base: &str, quote: &str) -> Vec<String> {
+        let registry = self.registry.read().unwrap();
+        let assets = (base.to_uppercase(), quote.to_uppercase());
+        match market {
+            SymbolMarket::Spot => names(registry.spot.by_assets.get(&assets)),
+            SymbolMarket::Futures => names(registry.futures.by_assets.get(&assets)),
+        }
+    }
+
+    pub fn spot_symbol(&self, symbol: &str) -> Option<Symbol> {
+        let registry = self.registry.read().unwrap();
+        registry
+            .spot
+            .symbols
+            .get(&symbol.to_uppercase())
+            .map(|listing| listing.symbol.clone())
+    }
+
+    /// Spot symbol trading `base` against `quote`.
+    pub fn spot_symbol_by_assets(&self, base: &str, quote: &str) -> Option<Symbol> {
+        self.symbols_by_assets(SymbolMarket::Spot, base, quote)
+            .first()
+            .and_then(|symbol| self.spot_symbol(symbol))
+    }
+
+    /// Filters of the spot symbol, to validate and round orders.
+    pub fn spot_filters(&self, symbol: &str) -> Option<SymbolFilters> {
+        let registry = self.registry.read().unwrap();
+        registry
+            .spot
+            .symbols
+            .get(&symbol.to_uppercase())
+            .map(|listing| listing.filters.clone())
+    }
+
+    pub fn futures_symbol(&self, symbol: &str) -> Option<FuturesSymbol> {
+        let registry = self.registry.read().unwrap();
+        registry
+            .futures
+            .symbols
+            .get(&symbol.to_uppercase())
+            .cloned()
+    }
+
+    /// Decimals of the price and of the quantity of the symbol, from the tick and
+    /// step sizes for spot.
+    pub fn precision(&self, market: SymbolMarket, symbol: &str) -> Option<(usize, usize)> {
+        match market {
+            SymbolMarket::Spot => self.spot_filters(symbol).and_then(|filters| {
+                Some((filters.price_precision()?, filters.quantity_precision()?))
+            }),
+            SymbolMarket::Futures => self.futures_symbol(symbol).map(|symbol| {
+                (
+                    symbol.price_precision as usize,
+                    symbol.quantity_precision as usize,
+                )
+            }),
+        }
+    }
+}
+
+/// Background reload of a `SymbolRegistry`, stopped when dropped.
+pub struct RegistryRefresh {
+    last_error: Arc<Mutex<Option<String>>>,
+    // Dropping the sender wakes up and ends the thread
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RegistryRefresh {
+    /// Error of the last reload, None when it succeeded.
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Stop reloading and wait for a running reload to end.
+    pub fn stop(mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for RegistryRefresh {
+    fn drop(&mut self) {
+        self.stop.take();
+    }
+}
@@ -2,6 +2,7 @@ use crate::errors::*;
 use std::collections::BTreeMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 use serde_json::Value;
+use serde::de::DeserializeOwned;
 
 pub fn build_request(parameters: BTreeMap<String, String>) -> String {
     let mut request = String::new();
@@ -32,6 +33,14 @@ pub fn build_signed_request_custom(
     bail!("Failed to get timestamp")
 }
 
+// Some endpoints answer with a single object instead of a list when filtered by symbol
+pub(crate) fn one_or_many<T: DeserializeOwned>(data: Value) -> Result<Vec<T>> {
+    match data {
+        Value::Array(_) => Ok(serde_json::from_value(data)?),
+        _ => Ok(vec![serde_json::from_value(data)?]),
+    }
+}
+
 pub fn to_i64(v: &Value) -> i64 {
     v.as_i64().unwrap()
 }
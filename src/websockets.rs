@@ -1,18 +1,24 @@
-
 use crate::errors::*;
 use crate::config::*;
 use crate::model::*;
+use crate::proxy::{connect_tcp, Proxy};
+use crate::stream_stats::{event_time, fallback_stream_name, StaleAction, StaleWatch, StreamMonitor};
 use url::Url;
 use serde::{Deserialize, Serialize};
 
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::net::TcpStream;
-use std::time::Duration;
-use native_tls::{TlsConnector, TlsStream};
+use std::net::{Shutdown, TcpStream};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use native_tls::TlsConnector;
 use serde_json::json;
 use tungstenite::{client, Message};
 use tungstenite::protocol::WebSocket;
+use tungstenite::protocol::frame::{CloseFrame, Frame};
+use tungstenite::protocol::frame::coding::CloseCode;
 use tungstenite::handshake::client::Response;
+use tungstenite::stream::MaybeTlsStream;
 
 #[allow(clippy::all)]
 enum WebsocketAPI {
@@ -50,9 +56,96 @@ pub enum WebsocketEvent {
     BookTicker(BookTickerEvent),
 }
 
+/// Cloneable handle to stop an event loop from any thread.
+///
+/// `stop` interrupts a blocked read; the event loop then sends a close frame
+/// and returns `Ok(())`. A stopped handle stays stopped.
+#[derive(Clone, Default)]
+pub struct StopHandle {
+    stopped: Arc<AtomicBool>,
+    stream: Arc<Mutex<Option<TcpStream>>>,
+}
+
+impl StopHandle {
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        if let Some(ref stream) = *self.stream.lock().unwrap() {
+            let _ = stream.shutdown(Shutdown::Read);
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    // Keep a clone of the socket so that `stop` can wake up a blocked read
+    pub(crate) fn attach(&self, socket: &WebSocket<MaybeTlsStream<TcpStream>>) -> Result<()> {
+        let stream = match socket.get_ref() {
+            MaybeTlsStream::Plain(stream) => stream.try_clone()?,
+            MaybeTlsStream::NativeTls(stream) => stream.get_ref().try_clone()?,
+            _ => bail!("Unsupported websocket stream"),
+        };
+        if self.is_stopped() {
+            let _ = stream.shutdown(Shutdown::Read);
+        }
+        *self.stream.lock().unwrap() = Some(stream);
+        Ok(())
+    }
+}
+
+pub(crate) fn connect_socket(
+    wss: &str, read_timeout: Duration, proxy: Option<&Proxy>,
+) -> Result<(WebSocket<MaybeTlsStream<TcpStream>>, Response)> {
+    let url = Url::parse(wss)?;
+    let host = url.host_str().unwrap();
+    let port = url.port_or_known_default().unwrap_or(443);
+    let tcp_stream = connect_tcp(proxy, host, port)?;
+    tcp_stream.set_read_timeout(Some(read_timeout))?; // Установите желаемый таймаут
+    let stream = match url.scheme() {
+        "ws" => MaybeTlsStream::Plain(tcp_stream),
+        _ => {
+            let connector = TlsConnector::new()?;
+            MaybeTlsStream::NativeTls(connector.connect(host, tcp_stream)?)
+        }
+    };
+    Ok(client(url, stream)?)
+}
+
+// Send a close frame, also when the read side was already shut down by a `StopHandle`
+pub(crate) fn close_socket(socket: &mut WebSocket<MaybeTlsStream<TcpStream>>) {
+    if socket.can_write() {
+        let _ = socket.close(None);
+        let _ = socket.write_pending();
+        return;
+    }
+
+    let mut frame = Frame::close(Some(CloseFrame {
+        code: CloseCode::Normal,
+        reason: "".into(),
+    }));
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    frame.header_mut().mask = Some(nanos.to_le_bytes());
+
+    let mut bytes = Vec::new();
+    if frame.format(&mut bytes).is_ok() {
+        let stream = socket.get_mut();
+        let _ = stream.write_all(&bytes);
+        let _ = stream.flush();
+    }
+}
+
 pub struct WebSockets<'a> {
-    pub socket: Option<(WebSocket<TlsStream<TcpStream>>, Response)>,
+    pub socket: Option<(WebSocket<MaybeTlsStream<TcpStream>>, Response)>,
     handler: Box<dyn FnMut(WebsocketEvent) -> Result<()> + 'a>,
+    stop_handle: StopHandle,
+    monitor: StreamMonitor,
+    stream_name: Option<String>,
+    url: Option<String>,
+    proxy: Option<Proxy>,
+    stale: StaleWatch<'a>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -79,31 +172,89 @@ impl<'a> WebSockets<'a> {
         WebSockets {
             socket: None,
             handler: Box::new(handler),
+            stop_handle: StopHandle::default(),
+            monitor: StreamMonitor::new(),
+            stream_name: None,
+            url: None,
+            proxy: None,
+            stale: StaleWatch::default(),
         }
     }
 
+    /// Connect through `proxy`, a proxy set in the `Config` of `connect_with_config` takes precedence.
+    pub fn set_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Report streams which received nothing for longer than `threshold`.
+    ///
+    /// Without an `on_stale` callback a stale stream reconnects the websocket.
+    pub fn set_stale_threshold(mut self, threshold: Duration) -> Self {
+        self.stale.set_threshold(threshold);
+        self
+    }
+
+    /// Callback deciding what to do with a stale stream, it receives the stream name
+    /// and the time since its last message.
+    pub fn on_stale<Callback>(mut self, callback: Callback) -> Self
+    where
+        Callback: FnMut(&str, Duration) -> StaleAction + 'a,
+    {
+        self.stale.set_handler(Box::new(callback));
+        self
+    }
+
+    /// Handle to stop `event_loop` from another thread.
+    pub fn stop_handle(&self) -> StopHandle {
+        self.stop_handle.clone()
+    }
+
+    /// Per-stream latency, rate and last-message statistics.
+    pub fn monitor(&self) -> StreamMonitor {
+        self.monitor.clone()
+    }
+
     pub fn connect(&mut self, subscription: &str) -> Result<()> {
+        self.stream_name = Some(subscription.into());
+        self.monitor.watch(&[subscription.into()]);
         self.connect_wss(WebsocketAPI::Default.params(subscription))
     }
 
     pub fn connect_with_config(&mut self, subscription: &str, config: &Config) -> Result<()> {
+        self.stream_name = Some(subscription.into());
+        self.monitor.watch(&[subscription.into()]);
+        if config.proxy.is_some() {
+            self.proxy = config.proxy.clone();
+        }
         self.connect_wss(WebsocketAPI::Custom(config.ws_endpoint.clone()).params(subscription))
     }
 
     pub fn connect_multiple_streams(&mut self, endpoints: &[String]) -> Result<()> {
+        self.stream_name = None;
+        self.monitor.watch(endpoints);
         self.connect_wss(WebsocketAPI::MultiStream.params(&endpoints.join("/")))
     }
 
     fn connect_wss(&mut self, wss: String) -> Result<()> {
-        let url = Url::parse(&wss)?;
-        let host = url.host_str().unwrap();
-        let port = url.port().unwrap_or(443);
-        let connector = TlsConnector::new()?;
-        let tcp_stream = TcpStream::connect((host, port))?;
-        tcp_stream.set_read_timeout(Some(Duration::from_secs(10)))?; // Установите желаемый таймаут
-        let tls_stream = connector.connect(host, tcp_stream)?;
-        let (socket, response) = client(url, tls_stream)?;
+        let read_timeout = self.stale.read_timeout();
+        let (socket, response) = connect_socket(&wss, read_timeout, self.proxy.as_ref())?;
+        self.stop_handle.attach(&socket)?;
         self.socket = Some((socket, response));
+        self.url = Some(wss);
+        Ok(())
+    }
+
+    fn check_stale_streams(&mut self) -> Result<()> {
+        if self.stale.check(&self.monitor)? {
+            if let Some(url) = self.url.clone() {
+                if let Some(ref mut socket) = self.socket {
+                    close_socket(&mut socket.0);
+                }
+                self.connect_wss(url)?;
+                self.monitor.rewatch();
+            }
+        }
         Ok(())
     }
 
@@ -134,14 +285,23 @@ impl<'a> WebSockets<'a> {
                             let data_obj = data.as_object_mut().unwrap();
                             data_obj.insert("symbol".to_string(), json!(symbol));
                         }
-                        self.handle_msg(&data.to_string())?;
-                        return Ok(());
+                        let data = data.take();
+                        self.monitor.record(&stream, event_time(&data));
+                        return self.handle_event(data);
                     }
                 }
             }
         }
 
+        let stream = match self.stream_name {
+            Some(ref name) => name.clone(),
+            None => fallback_stream_name(&value),
+        };
+        self.monitor.record(&stream, event_time(&value));
+        self.handle_event(value)
+    }
 
+    fn handle_event(&mut self, value: serde_json::Value) -> Result<()> {
         if let Ok(events) = serde_json::from_value::<Events>(value) {
             let action = match events {
                 Events::Vec(v) => WebsocketEvent::DayTickerAll(v),
@@ -164,7 +324,7 @@ impl<'a> WebSockets<'a> {
     pub fn event_loop(&mut self, should_stop: &AtomicBool) -> Result<()> {
         let mut ping_counter = 0;
 
-        while !should_stop.load(Ordering::Relaxed) {
+        while !should_stop.load(Ordering::Relaxed) && !self.stop_handle.is_stopped() {
             if let Some(ref mut socket) = self.socket {
                 let message = socket.0.read_message();
                 match message {
@@ -183,6 +343,7 @@ impl<'a> WebSockets<'a> {
                         Message::Binary(_) => (),
                         Message::Close(e) => bail!(format!("Disconnected {:?}", e)),
                     },
+                    Err(_) if self.stop_handle.is_stopped() => break,
                     Err(error) => {
                         // Таймаут истек; вы можете обработать эту ситуацию, например, закрыть соединение
                         // отправляем 3 пинга если нет ответа - ошибка
@@ -197,7 +358,12 @@ impl<'a> WebSockets<'a> {
                     }
                 }
             }
+            self.check_stale_streams()?;
         }
-        bail!("running loop closed");
+
+        if let Some(ref mut socket) = self.socket {
+            close_socket(&mut socket.0);
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,506 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::net::TcpStream;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer as _};
+use hex::encode as hex_encode;
+use hmac::{Hmac, Mac, NewMac};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use sha2::Sha256;
+use tungstenite::protocol::WebSocket;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+
+use crate::account::{build_order, OrderRequest, OrderSide, OrderType, TimeInForce};
+use crate::config::Config;
+use crate::errors::*;
+use crate::model::*;
+use crate::websockets::{close_socket, connect_socket};
+
+// Time to wait for the response of one request
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Parameters sent as JSON numbers, everything else is sent as a string
+const NUMERIC_PARAMS: &[&str] = &[
+    "cancelOrderId", "endTime", "fromId", "limit", "orderId", "orderListId", "recvWindow",
+    "startTime", "strategyId", "strategyType", "timestamp", "trailingDelta",
+];
+
+// PKCS#8 prefix of an Ed25519 private key, followed by the 32 byte seed
+const ED25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+enum Signer {
+    Hmac(String),
+    Ed25519(Box<Keypair>),
+}
+
+impl Signer {
+    fn sign(&self, payload: &str) -> String {
+        match self {
+            Signer::Hmac(secret_key) => {
+                let mut signed_key = Hmac::<Sha256>::new_from_slice(secret_key.as_bytes()).unwrap();
+                signed_key.update(payload.as_bytes());
+                hex_encode(signed_key.finalize().into_bytes())
+            }
+            Signer::Ed25519(keypair) => base64::encode(keypair.sign(payload.as_bytes()).to_bytes()),
+        }
+    }
+}
+
+/// Request/response client for the Binance WebSocket API.
+///
+/// Requests are sent one at a time over a single connection, messages that do not
+/// answer the current request (late responses, user data events) are kept and can
+/// be read with `take_unsolicited`.
+///
+/// ```no_run
+/// use binance::config::Config;
+/// use binance::ws_api::WsApi;
+///
+/// let mut ws_api = WsApi::connect(
+///     Some("YOUR_API_KEY".into()), Some("YOUR_SECRET_KEY".into()), &Config::default(),
+/// ).unwrap();
+/// let account = ws_api.get_account().unwrap();
+/// println!("{:?} {:?}", account, ws_api.rate_limits());
+/// ```
+pub struct WsApi {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    api_key: String,
+    signer: Option<Signer>,
+    logged_on: bool,
+    recv_window: u64,
+    next_id: u64,
+    rate_limits: Vec<RateLimitUsage>,
+    unsolicited: VecDeque<Value>,
+}
+
+impl WsApi {
+    /// Connect with an HMAC key, every signed request carries its own signature.
+    pub fn connect(
+        api_key: Option<String>, secret_key: Option<String>, config: &Config,
+    ) -> Result<WsApi> {
+        Self::connect_with_signer(api_key, secret_key.map(Signer::Hmac), config)
+    }
+
+    /// Connect with an Ed25519 key given as PKCS#8 PEM, as generated for the Binance API.
+    ///
+    /// Call `logon` to authenticate the session once instead of signing every request.
+    pub fn connect_ed25519(api_key: String, private_key_pem: &str, config: &Config) -> Result<WsApi> {
+        let keypair = ed25519_keypair(private_key_pem)?;
+        Self::connect_with_signer(Some(api_key), Some(Signer::Ed25519(Box::new(keypair))), config)
+    }
+
+    fn connect_with_signer(
+        api_key: Option<String>, signer: Option<Signer>, config: &Config,
+    ) -> Result<WsApi> {
+        let (socket, _) =
+            connect_socket(&config.ws_api_endpoint, RESPONSE_TIMEOUT, config.proxy.as_ref())?;
+        Ok(WsApi {
+            socket,
+            api_key: api_key.unwrap_or_default(),
+            signer,
+            logged_on: false,
+            recv_window: config.recv_window,
+            next_id: 1,
+            rate_limits: Vec::new(),
+            unsolicited: VecDeque::new(),
+        })
+    }
+
+    /// Time to wait for a response before failing the request.
+    pub fn set_response_timeout(&mut self, timeout: Duration) -> Result<()> {
+        match self.socket.get_mut() {
+            MaybeTlsStream::Plain(stream) => stream.set_read_timeout(Some(timeout))?,
+            MaybeTlsStream::NativeTls(stream) => stream.get_mut().set_read_timeout(Some(timeout))?,
+            _ => bail!("Unsupported websocket stream"),
+        }
+        Ok(())
+    }
+
+    /// Rate limits reported with the last response.
+    pub fn rate_limits(&self) -> &[RateLimitUsage] {
+        &self.rate_limits
+    }
+
+    /// Messages received while waiting for responses which did not answer them.
+    pub fn take_unsolicited(&mut self) -> Vec<Value> {
+        self.unsolicited.drain(..).collect()
+    }
+
+    /// Send any WebSocket API method and decode its `result`.
+    pub fn request<T: DeserializeOwned>(
+        &mut self, method: &str, parameters: BTreeMap<String, String>, signed: bool,
+    ) -> Result<T> {
+        let params = if signed {
+            self.signed_params(parameters)?
+        } else {
+            to_json_params(&parameters)
+        };
+        let result = self.send(method, params)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    pub fn ping(&mut self) -> Result<()> {
+        self.request::<Empty>("ping", BTreeMap::new(), false).map(|_| ())
+    }
+
+    pub fn get_server_time(&mut self) -> Result<ServerTime> {
+        self.request("time", BTreeMap::new(), false)
+    }
+
+    /// Authenticate the session with the Ed25519 key (`session.logon`).
+    pub fn logon(&mut self) -> Result<SessionStatus> {
+        match self.signer {
+            Some(Signer::Ed25519(_)) => (),
+            _ => bail!("session.logon requires an Ed25519 key"),
+        }
+        let mut parameters = BTreeMap::new();
+        parameters.insert("apiKey".into(), self.api_key.clone());
+        parameters.insert("timestamp".into(), timestamp()?.to_string());
+        let status: SessionStatus = self.request_signed_params("session.logon", parameters)?;
+        self.logged_on = true;
+        Ok(status)
+    }
+
+    pub fn session_status(&mut self) -> Result<SessionStatus> {
+        self.request("session.status", BTreeMap::new(), false)
+    }
+
+    pub fn logout(&mut self) -> Result<SessionStatus> {
+        let status = self.request("session.logout", BTreeMap::new(), false)?;
+        self.logged_on = false;
+        Ok(status)
+    }
+
+    // Account Information
+    pub fn get_account(&mut self) -> Result<AccountInformation> {
+        self.request("account.status", BTreeMap::new(), true)
+    }
+
+    // Current open orders for ONE symbol
+    pub fn get_open_orders<S>(&mut self, symbol: S) -> Result<Vec<Order>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.request("openOrders.status", parameters, true)
+    }
+
+    // All current open orders
+    pub fn get_all_open_orders(&mut self) -> Result<Vec<Order>> {
+        self.request("openOrders.status", BTreeMap::new(), true)
+    }
+
+    // Check an order's status
+    pub fn order_status<S>(&mut self, symbol: S, order_id: u64) -> Result<Order>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+        self.request("order.status", parameters, true)
+    }
+
+    pub fn limit_buy<S, F>(&mut self, symbol: S, qty: F, price: Number) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        self.custom_order(
+            symbol, qty, price, None, OrderSide::Buy, OrderType::Limit, TimeInForce::GTC, None,
+            None,
+        )
+    }
+
+    pub fn limit_sell<S, F>(&mut self, symbol: S, qty: F, price: Number) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        self.custom_order(
+            symbol, qty, price, None, OrderSide::Sell, OrderType::Limit, TimeInForce::GTC, None,
+            None,
+        )
+    }
+
+    pub fn market_buy<S, F>(&mut self, symbol: S, qty: F) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        self.custom_order(
+            symbol, qty, Number::default(), None, OrderSide::Buy, OrderType::Market, TimeInForce::GTC, None,
+            None,
+        )
+    }
+
+    pub fn market_sell<S, F>(&mut self, symbol: S, qty: F) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        self.custom_order(
+            symbol, qty, Number::default(), None, OrderSide::Sell, OrderType::Market, TimeInForce::GTC, None,
+            None,
+        )
+    }
+
+    /// Place a custom order (`order.place`)
+    #[allow(clippy::too_many_arguments)]
+    pub fn custom_order<S, F>(
+        &mut self, symbol: S, qty: F, price: Number, stop_price: Option<Number>, order_side: OrderSide,
+        order_type: OrderType, time_in_force: TimeInForce, iceberg_qty: Option<Number>,
+        new_client_order_id: Option<String>,
+    ) -> Result<Transaction>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        let order = build_order(OrderRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            order_side,
+            order_type,
+            time_in_force,
+            new_client_order_id,
+            iceberg_qty,
+        });
+        self.request("order.place", order, true)
+    }
+
+    /// Place a test custom order (`order.test`)
+    ///
+    /// This order is sandboxed: it is validated, but not sent to the matching engine.
+    #[allow(clippy::too_many_arguments)]
+    pub fn test_custom_order<S, F>(
+        &mut self, symbol: S, qty: F, price: Number, stop_price: Option<Number>, order_side: OrderSide,
+        order_type: OrderType, time_in_force: TimeInForce, new_client_order_id: Option<String>,
+    ) -> Result<()>
+    where
+        S: Into<String>,
+        F: Into<Number>,
+    {
+        let order = build_order(OrderRequest {
+            symbol: symbol.into(),
+            qty: qty.into(),
+            price,
+            stop_price,
+            order_side,
+            order_type,
+            time_in_force,
+            new_client_order_id,
+            iceberg_qty: None,
+        });
+        self.request::<Empty>("order.test", order, true).map(|_| ())
+    }
+
+    /// Cancel - Replace a exist order (`order.cancelReplace`)
+    #[allow(clippy::too_many_arguments)]
+    pub fn cancel_replace<S>(
+        &mut self, symbol: S, qty: Number, price: Number, order_side: OrderSide,
+        order_type: OrderType, time_in_force: TimeInForce, new_client_order_id: S,
+        cancel_order_id: S,
+    ) -> Result<CancelReplace>
+    where
+        S: Into<String>,
+    {
+        let mut params: BTreeMap<String, String> = BTreeMap::new();
+        params.insert("symbol".into(), symbol.into());
+        params.insert("side".into(), order_side.into());
+        params.insert("type".into(), order_type.into());
+        params.insert("quantity".into(), qty.to_string());
+
+        if price != Number::default() {
+            params.insert("price".into(), price.to_string());
+            params.insert("timeInForce".into(), time_in_force.into());
+        }
+        params.insert("newClientOrderId".into(), new_client_order_id.into());
+        params.insert("cancelOrigClientOrderId".into(), cancel_order_id.into());
+        params.insert("cancelReplaceMode".into(), "STOP_ON_FAILURE".to_string());
+        params.insert("cancelRestrictions".into(), "ONLY_NEW".to_string());
+
+        self.request("order.cancelReplace", params, true)
+    }
+
+    pub fn cancel_order<S>(&mut self, symbol: S, order_id: u64) -> Result<OrderCanceled>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("orderId".into(), order_id.to_string());
+        self.request("order.cancel", parameters, true)
+    }
+
+    pub fn cancel_order_with_client_id<S>(
+        &mut self, symbol: S, orig_client_order_id: String,
+    ) -> Result<OrderCanceled>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        parameters.insert("origClientOrderId".into(), orig_client_order_id);
+        self.request("order.cancel", parameters, true)
+    }
+
+    pub fn cancel_all_open_orders<S>(&mut self, symbol: S) -> Result<Vec<OpenOrderCanceled>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.request("openOrders.cancelAll", parameters, true)
+    }
+
+    // Trade history
+    pub fn trade_history<S>(&mut self, symbol: S) -> Result<Vec<TradeHistory>>
+    where
+        S: Into<String>,
+    {
+        let mut parameters: BTreeMap<String, String> = BTreeMap::new();
+        parameters.insert("symbol".into(), symbol.into());
+        self.request("myTrades", parameters, true)
+    }
+
+    /// Close the connection.
+    pub fn disconnect(&mut self) {
+        close_socket(&mut self.socket);
+    }
+
+    // A logged on session only needs the timestamp
+    fn signed_params(&self, mut parameters: BTreeMap<String, String>) -> Result<Value> {
+        if self.recv_window > 0 {
+            parameters.insert("recvWindow".into(), self.recv_window.to_string());
+        }
+        parameters.insert("timestamp".into(), timestamp()?.to_string());
+        if self.logged_on {
+            return Ok(to_json_params(&parameters));
+        }
+        parameters.insert("apiKey".into(), self.api_key.clone());
+        self.sign(parameters)
+    }
+
+    fn request_signed_params<T: DeserializeOwned>(
+        &mut self, method: &str, parameters: BTreeMap<String, String>,
+    ) -> Result<T> {
+        let params = self.sign(parameters)?;
+        let result = self.send(method, params)?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    // The signature payload is the alphabetically sorted query string
+    fn sign(&self, parameters: BTreeMap<String, String>) -> Result<Value> {
+        let signer = match self.signer {
+            Some(ref signer) => signer,
+            None => bail!("Signed request without a secret key"),
+        };
+        let payload = crate::util::build_request(parameters.clone());
+        let mut params = to_json_params(&parameters);
+        params["signature"] = Value::String(signer.sign(&payload));
+        Ok(params)
+    }
+
+    fn send(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut request = json!({ "id": id, "method": method });
+        if params.as_object().is_some_and(|params| !params.is_empty()) {
+            request["params"] = params;
+        }
+        self.socket.write_message(Message::Text(request.to_string()))?;
+
+        loop {
+            let message = match self.socket.read_message() {
+                Ok(message) => message,
+                Err(tungstenite::Error::Io(ref e))
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    bail!(format!("No response to {} (id {})", method, id))
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Binary(data) => String::from_utf8_lossy(&data).into_owned(),
+                Message::Close(frame) => bail!(format!("Disconnected {:?}", frame)),
+                _ => continue,
+            };
+
+            let mut response: Value = serde_json::from_str(&text)?;
+            if response.get("id").and_then(Value::as_u64) != Some(id) {
+                self.unsolicited.push_back(response);
+                continue;
+            }
+
+            if let Some(rate_limits) = response.get_mut("rateLimits") {
+                self.rate_limits = serde_json::from_value(rate_limits.take())?;
+            }
+            if let Some(error) = response.get_mut("error") {
+                let error: BinanceContent = serde_json::from_value(error.take())?;
+                return match error {
+                    BinanceContent::CancelReplace { data, .. } => {
+                        Err(ErrorKind::CancelReplaceError(data).into())
+                    }
+                    BinanceContent::Error(bin_error) => {
+                        Err(ErrorKind::BinanceError(bin_error).into())
+                    }
+                };
+            }
+            return Ok(response.get_mut("result").map(Value::take).unwrap_or(Value::Null));
+        }
+    }
+}
+
+impl Drop for WsApi {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
+
+fn to_json_params(parameters: &BTreeMap<String, String>) -> Value {
+    let mut params = Map::new();
+    for (key, value) in parameters {
+        let value = match value.parse::<u64>() {
+            Ok(number) if NUMERIC_PARAMS.contains(&key.as_str()) => Value::from(number),
+            _ => Value::String(value.clone()),
+        };
+        params.insert(key.clone(), value);
+    }
+    Value::Object(params)
+}
+
+fn timestamp() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64)
+}
+
+fn ed25519_keypair(private_key_pem: &str) -> Result<Keypair> {
+    let body: String = private_key_pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .map(str::trim)
+        .collect();
+    let der = match base64::decode(body) {
+        Ok(der) => der,
+        Err(_) => bail!("Invalid Ed25519 private key PEM"),
+    };
+    if der.len() != 48 || der[..16] != ED25519_PKCS8_PREFIX {
+        bail!("Expected an Ed25519 private key in PKCS#8 format");
+    }
+
+    let secret = match SecretKey::from_bytes(&der[16..]) {
+        Ok(secret) => secret,
+        Err(_) => bail!("Invalid Ed25519 private key"),
+    };
+    let public = PublicKey::from(&secret);
+    Ok(Keypair { secret, public })
+}
@@ -2,6 +2,7 @@ use binance::api::*;
 use binance::config::*;
 use binance::account::*;
 use binance::model::*;
+use binance::history::HistoryQuery;
 
 #[cfg(test)]
 mod tests {
@@ -99,16 +100,16 @@ mod tests {
         assert_eq!(open_order.order_id, 1);
         assert_eq!(open_order.order_list_id.unwrap(), -1);
         assert_eq!(open_order.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, open_order.price, 0.1, ulps = 2));
-        assert_eq!(open_order.orig_qty, 1.0);
-        assert_eq!(open_order.executed_qty, 0.0);
-        assert_eq!(open_order.cummulative_quote_qty, 0.0);
+        assert_eq!(open_order.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(open_order.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(open_order.executed_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(open_order.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(open_order.status, "NEW");
         assert_eq!(open_order.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(open_order.order_type, "LIMIT");
         assert_eq!(open_order.side, "BUY");
-        assert!(approx_eq!(f64, open_order.stop_price, 0.0, ulps = 2));
-        assert_eq!(open_order.iceberg_qty, 0.0);
+        assert_eq!(open_order.stop_price, "0".parse::<Number>().unwrap());
+        assert_eq!(open_order.iceberg_qty, "0".parse::<Number>().unwrap());
         assert_eq!(open_order.time, 1499827319559);
         assert_eq!(open_order.update_time, 1499827319559);
         assert_eq!(open_order.is_working, true);
@@ -139,16 +140,16 @@ mod tests {
         assert_eq!(open_order.order_id, 1);
         assert_eq!(open_order.order_list_id.unwrap(), -1);
         assert_eq!(open_order.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, open_order.price, 0.1, ulps = 2));
-        assert_eq!(open_order.orig_qty, 1.0);
-        assert_eq!(open_order.executed_qty, 0.0);
-        assert_eq!(open_order.cummulative_quote_qty, 0.0);
+        assert_eq!(open_order.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(open_order.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(open_order.executed_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(open_order.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(open_order.status, "NEW");
         assert_eq!(open_order.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(open_order.order_type, "LIMIT");
         assert_eq!(open_order.side, "BUY");
-        assert!(approx_eq!(f64, open_order.stop_price, 0.0, ulps = 2));
-        assert_eq!(open_order.iceberg_qty, 0.0);
+        assert_eq!(open_order.stop_price, "0".parse::<Number>().unwrap());
+        assert_eq!(open_order.iceberg_qty, "0".parse::<Number>().unwrap());
         assert_eq!(open_order.time, 1499827319559);
         assert_eq!(open_order.update_time, 1499827319559);
         assert_eq!(open_order.is_working, true);
@@ -176,29 +177,37 @@ mod tests {
 
         assert!(cancel_all_open_orders.len() == 3);
 
-        let first_order_cancelled: OrderCanceled = cancel_all_open_orders[0].clone();
-        assert_eq!(first_order_cancelled.symbol, "BTCUSDT");
-        assert_eq!(
-            first_order_cancelled.orig_client_order_id.unwrap(),
-            "E6APeyTJvkMvLMYMqu1KQ4"
-        );
-        //assert_eq!(first_order_cancelled.order_id, 11);
-        assert_eq!(
-            first_order_cancelled.client_order_id.unwrap(),
-            "pXLV6Hz6mprAcVYpVMTGgx"
-        );
-
-        let second_order_cancelled: OrderCanceled = cancel_all_open_orders[1].clone();
-        assert_eq!(second_order_cancelled.symbol, "BTCUSDT");
-        assert_eq!(
-            second_order_cancelled.orig_client_order_id.unwrap(),
-            "A3EF2HCwxgZPFMrfwbgrhv"
-        );
-        //assert_eq!(second_order_cancelled.order_id, 13);
-        assert_eq!(
-            second_order_cancelled.client_order_id.unwrap(),
-            "pXLV6Hz6mprAcVYpVMTGgx"
-        );
+        match &cancel_all_open_orders[..] {
+            [OpenOrderCanceled::Order(first_order_cancelled), OpenOrderCanceled::Order(second_order_cancelled), OpenOrderCanceled::OrderList(order_list)] =>
+            {
+                assert_eq!(first_order_cancelled.symbol, "BTCUSDT");
+                assert_eq!(
+                    first_order_cancelled.orig_client_order_id,
+                    "E6APeyTJvkMvLMYMqu1KQ4"
+                );
+                assert_eq!(
+                    first_order_cancelled.client_order_id,
+                    "pXLV6Hz6mprAcVYpVMTGgx"
+                );
+
+                assert_eq!(second_order_cancelled.symbol, "BTCUSDT");
+                assert_eq!(
+                    second_order_cancelled.orig_client_order_id,
+                    "A3EF2HCwxgZPFMrfwbgrhv"
+                );
+                assert_eq!(
+                    second_order_cancelled.client_order_id,
+                    "pXLV6Hz6mprAcVYpVMTGgx"
+                );
+
+                assert_eq!(order_list.order_list_id, 1929);
+                assert_eq!(order_list.contingency_type, "OCO");
+                assert_eq!(order_list.orders.len(), 2);
+                assert_eq!(order_list.order_reports.len(), 2);
+                assert_eq!(order_list.order_reports[0].status, "CANCELED");
+            }
+            other => panic!("unexpected cancellations {:?}", other),
+        }
     }
 
     #[test]
@@ -224,16 +233,16 @@ mod tests {
         assert_eq!(order_status.order_id, 1);
         assert_eq!(order_status.order_list_id.unwrap(), -1);
         assert_eq!(order_status.client_order_id, "myOrder1");
-        assert!(approx_eq!(f64, order_status.price, 0.1, ulps = 2));
-        assert_eq!(order_status.orig_qty, 1.0);
-        assert_eq!(order_status.executed_qty, 0.0);
-        assert_eq!(order_status.cummulative_quote_qty, 0.0);
+        assert_eq!(order_status.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(order_status.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(order_status.executed_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(order_status.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(order_status.status, "NEW");
         assert_eq!(order_status.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(order_status.order_type, "LIMIT");
         assert_eq!(order_status.side, "BUY");
-        assert!(approx_eq!(f64, order_status.stop_price, 0.0, ulps = 2));
-        assert_eq!(order_status.iceberg_qty, 0.0);
+        assert_eq!(order_status.stop_price, "0".parse::<Number>().unwrap());
+        assert_eq!(order_status.iceberg_qty, "0".parse::<Number>().unwrap());
         assert_eq!(order_status.time, 1499827319559);
         assert_eq!(order_status.update_time, 1499827319559);
         assert_eq!(order_status.is_working, true);
@@ -273,7 +282,9 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.limit_buy("LTCBTC", 1, 0.1).unwrap();
+        let transaction: Transaction = account
+            .limit_buy("LTCBTC", 1, "0.1".parse().unwrap())
+            .unwrap();
 
         mock_limit_buy.assert();
 
@@ -282,15 +293,10 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "LIMIT");
@@ -310,7 +316,7 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        account.test_limit_buy("LTCBTC", 1, 0.1).unwrap();
+        account.test_limit_buy("LTCBTC", 1, "0.1".parse::<Number>().unwrap()).unwrap();
 
         mock_test_limit_buy.assert();
     }
@@ -328,7 +334,9 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        let transaction: Transaction = account.limit_sell("LTCBTC", 1, 0.1).unwrap();
+        let transaction: Transaction = account
+            .limit_sell("LTCBTC", 1, "0.1".parse().unwrap())
+            .unwrap();
 
         mock_limit_sell.assert();
 
@@ -337,15 +345,10 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "LIMIT");
@@ -365,7 +368,7 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        account.test_limit_sell("LTCBTC", 1, 0.1).unwrap();
+        account.test_limit_sell("LTCBTC", 1, "0.1".parse::<Number>().unwrap()).unwrap();
 
         mock_test_limit_sell.assert();
     }
@@ -395,15 +398,10 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "MARKET");
@@ -444,7 +442,8 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        match account.market_buy_using_quote_quantity("BNBBTC", 0.002) {
+        let quote_quantity = "0.002".parse::<Number>().unwrap();
+        match account.market_buy_using_quote_quantity("BNBBTC", quote_quantity) {
             Ok(answer) => {
                 assert!(answer.order_id == 1);
             }
@@ -468,7 +467,7 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         account
-            .test_market_buy_using_quote_quantity("BNBBTC", 0.002)
+            .test_market_buy_using_quote_quantity("BNBBTC", "0.002".parse::<Number>().unwrap())
             .unwrap();
 
         mock_test_market_buy_using_quote_quantity.assert();
@@ -499,15 +498,10 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "MARKET");
@@ -548,7 +542,8 @@ mod tests {
             .set_recv_window(1234);
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
-        match account.market_sell_using_quote_quantity("BNBBTC", 0.002) {
+        let quote_quantity = "0.002".parse::<Number>().unwrap();
+        match account.market_sell_using_quote_quantity("BNBBTC", quote_quantity) {
             Ok(answer) => {
                 assert!(answer.order_id == 1);
             }
@@ -572,7 +567,7 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         account
-            .test_market_sell_using_quote_quantity("BNBBTC", 0.002)
+            .test_market_sell_using_quote_quantity("BNBBTC", "0.002".parse::<Number>().unwrap())
             .unwrap();
 
         mock_test_market_sell_using_quote_quantity.assert();
@@ -592,7 +587,13 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         let transaction: Transaction = account
-            .stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC)
+            .stop_limit_buy_order(
+                "LTCBTC",
+                1,
+                "0.1".parse().unwrap(),
+                "0.09".parse().unwrap(),
+                TimeInForce::GTC,
+            )
             .unwrap();
 
         mock_stop_limit_buy_order.assert();
@@ -602,16 +603,11 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(transaction.stop_price, "0.09".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
@@ -632,7 +628,13 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         let _ = account
-            .test_stop_limit_buy_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC)
+            .test_stop_limit_buy_order(
+                "LTCBTC",
+                1,
+                "0.1".parse().unwrap(),
+                "0.09".parse().unwrap(),
+                TimeInForce::GTC,
+            )
             .unwrap();
 
         mock_test_stop_limit_buy_order.assert();
@@ -652,7 +654,13 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         let transaction: Transaction = account
-            .stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC)
+            .stop_limit_sell_order(
+                "LTCBTC",
+                1,
+                "0.1".parse().unwrap(),
+                "0.09".parse().unwrap(),
+                TimeInForce::GTC,
+            )
             .unwrap();
 
         mock_stop_limit_sell_order.assert();
@@ -662,16 +670,11 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(transaction.stop_price, "0.09".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
@@ -692,7 +695,13 @@ mod tests {
         let account: Account = Binance::new_with_config(None, None, &config);
         let _ = env_logger::try_init();
         let _ = account
-            .test_stop_limit_sell_order("LTCBTC", 1, 0.1, 0.09, TimeInForce::GTC)
+            .test_stop_limit_sell_order(
+                "LTCBTC",
+                1,
+                "0.1".parse().unwrap(),
+                "0.09".parse().unwrap(),
+                TimeInForce::GTC,
+            )
             .unwrap();
 
         mock_test_stop_limit_sell_order.assert();
@@ -715,13 +724,13 @@ mod tests {
             .custom_order(
                 "LTCBTC",
                 1,
-                0.1,
+                "0.1".parse::<Number>().unwrap(),
                 None,
                 OrderSide::Buy,
                 OrderType::Market,
                 TimeInForce::GTC,
-                Some("6gCrw2kRUAF9CvJDGP16IP".into()),
-                None
+                None,
+                Some("6gCrw2kRUAF9CvJDGP16IP".into())
             )
             .unwrap();
 
@@ -732,16 +741,11 @@ mod tests {
         assert_eq!(transaction.order_list_id.unwrap(), -1);
         assert_eq!(transaction.client_order_id, "6gCrw2kRUAF9CvJDGP16IP");
         assert_eq!(transaction.transact_time, 1507725176595);
-        assert!(approx_eq!(f64, transaction.price, 0.1, ulps = 2));
-        assert!(approx_eq!(f64, transaction.orig_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(f64, transaction.executed_qty, 1.0, ulps = 2));
-        assert!(approx_eq!(
-            f64,
-            transaction.cummulative_quote_qty,
-            0.0,
-            ulps = 2
-        ));
-        assert!(approx_eq!(f64, transaction.stop_price, 0.09, ulps = 2));
+        assert_eq!(transaction.price, "0.1".parse::<Number>().unwrap());
+        assert_eq!(transaction.orig_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.executed_qty, "1".parse::<Number>().unwrap());
+        assert_eq!(transaction.cummulative_quote_qty, "0".parse::<Number>().unwrap());
+        assert_eq!(transaction.stop_price, "0.09".parse::<Number>().unwrap());
         assert_eq!(transaction.status, "NEW");
         assert_eq!(transaction.time_in_force, "GTC"); //Migrate to TimeInForce enum
         assert_eq!(transaction.type_name, "STOP_LOSS_LIMIT");
@@ -765,7 +769,7 @@ mod tests {
             .test_custom_order(
                 "LTCBTC",
                 1,
-                0.1,
+                "0.1".parse::<Number>().unwrap(),
                 None,
                 OrderSide::Buy,
                 OrderType::Market,
@@ -797,9 +801,9 @@ mod tests {
         mock_cancel_order.assert();
 
         assert_eq!(cancelled_order.symbol, "LTCBTC");
-        assert_eq!(cancelled_order.orig_client_order_id.unwrap(), "myOrder1");
+        assert_eq!(cancelled_order.orig_client_order_id, "myOrder1");
         //assert_eq!(cancelled_order.order_id, 4);
-        assert_eq!(cancelled_order.client_order_id.unwrap(), "cancelMyOrder1");
+        assert_eq!(cancelled_order.client_order_id, "cancelMyOrder1");
     }
 
     #[test]
@@ -846,8 +850,8 @@ mod tests {
         let history: TradeHistory = histories[0].clone();
 
         assert_eq!(history.id, 28457);
-        assert!(approx_eq!(f64, history.price, 4.00000100, ulps = 2));
-        assert!(approx_eq!(f64, history.qty, 12.00000000, ulps = 2));
+        assert_eq!(history.price, "4.00000100".parse::<Number>().unwrap());
+        assert_eq!(history.qty, "12.00000000".parse::<Number>().unwrap());
         assert_eq!(history.commission, "10.10000000");
         assert_eq!(history.commission_asset, "BNB");
         assert_eq!(history.time, 1499865549590);
@@ -855,4 +859,542 @@ mod tests {
         assert_eq!(history.is_maker, false);
         assert_eq!(history.is_best_match, true);
     }
+
+    #[test]
+    fn place_order_full() {
+        let mock_place_order = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("quantity=1&recvWindow=1234&side=BUY&symbol=BTCUSDT&timestamp=\\d+&type=MARKET&signature=.*".into()))
+            .with_body_from_file("tests/mocks/account/new_order_full.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let response = account
+            .place_order(NewOrder::market("BTCUSDT", OrderSide::Buy, "1".parse().unwrap()))
+            .unwrap();
+
+        mock_place_order.assert();
+
+        match response {
+            OrderResponse::Full(transaction) => {
+                assert_eq!(transaction.order_id, 28);
+                assert_eq!(transaction.status, "FILLED");
+                assert_eq!(transaction.self_trade_prevention_mode.as_deref(), Some("NONE"));
+                let fills = transaction.fills.unwrap();
+                assert_eq!(fills.len(), 2);
+                assert_eq!(fills[1].price, "30001".parse::<Number>().unwrap());
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+    }
+
+    #[test]
+    fn place_order_result() {
+        let mock_place_order = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("type".into(), "TAKE_PROFIT_LIMIT".into()),
+                Matcher::UrlEncoded("timeInForce".into(), "GTC".into()),
+                Matcher::UrlEncoded("quantity".into(), "0.5".into()),
+                Matcher::UrlEncoded("price".into(), "31000".into()),
+                Matcher::UrlEncoded("stopPrice".into(), "30900".into()),
+                Matcher::UrlEncoded("icebergQty".into(), "0.1".into()),
+                Matcher::UrlEncoded("newClientOrderId".into(), "take-profit-1".into()),
+                Matcher::UrlEncoded("strategyId".into(), "37463720".into()),
+                Matcher::UrlEncoded("strategyType".into(), "1000000".into()),
+                Matcher::UrlEncoded("selfTradePreventionMode".into(), "EXPIRE_TAKER".into()),
+                Matcher::UrlEncoded("newOrderRespType".into(), "RESULT".into()),
+            ]))
+            .with_body_from_file("tests/mocks/account/new_order_result.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = NewOrder::new("BTCUSDT", OrderSide::Sell, OrderType::TakeProfitLimit)
+            .set_quantity("0.5".parse::<Number>().unwrap())
+            .set_price("31000".parse::<Number>().unwrap())
+            .set_stop_price("30900".parse::<Number>().unwrap())
+            .set_iceberg_qty("0.1".parse::<Number>().unwrap())
+            .set_new_client_order_id("take-profit-1")
+            .set_strategy(37463720, 1000000)
+            .set_self_trade_prevention_mode(SelfTradePreventionMode::ExpireTaker)
+            .set_new_order_resp_type(NewOrderResponseType::Result);
+        let response = account.place_order(order).unwrap();
+
+        mock_place_order.assert();
+
+        assert_eq!(response.order_id(), 29);
+        assert_eq!(response.client_order_id(), "take-profit-1");
+        match response {
+            OrderResponse::Result(transaction) => {
+                assert!(transaction.fills.is_none());
+                assert_eq!(transaction.working_time, Some(-1));
+                assert_eq!(transaction.strategy_type, Some(1000000));
+                assert_eq!(transaction.iceberg_qty, Some("0.1".parse::<Number>().unwrap()));
+                assert_eq!(transaction.stop_price, "30900".parse::<Number>().unwrap());
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+    }
+
+    #[test]
+    fn place_order_ack() {
+        let mock_place_order = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("newClientOrderId=stop-1&quantity=1&recvWindow=1234&side=SELL&symbol=BTCUSDT&timestamp=\\d+&trailingDelta=100&type=STOP_LOSS".into()))
+            .with_body_from_file("tests/mocks/account/new_order_ack.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = NewOrder::new("BTCUSDT", OrderSide::Sell, OrderType::StopLoss)
+            .set_quantity("1".parse::<Number>().unwrap())
+            .set_trailing_delta(100)
+            .set_new_client_order_id("stop-1");
+        let response = account.place_order(order).unwrap();
+
+        mock_place_order.assert();
+
+        match response {
+            OrderResponse::Ack(ack) => {
+                assert_eq!(ack.order_id, 30);
+                assert_eq!(ack.order_list_id, -1);
+                assert_eq!(ack.transact_time, 1507725176595);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_place_order() {
+        let mock_test_order = mock("POST", "/api/v3/order/test")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("pegOffsetType=PRICE_LEVEL&pegOffsetValue=2&pegPriceType=PRIMARY_PEG&quantity=1&recvWindow=1234&side=BUY&symbol=BTCUSDT&timestamp=\\d+&type=LIMIT_MAKER".into()))
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::LimitMaker)
+            .set_quantity("1".parse::<Number>().unwrap())
+            .set_peg(PegPriceType::PrimaryPeg, Some(2));
+        account.test_place_order(order).unwrap();
+
+        mock_test_order.assert();
+    }
+
+    #[test]
+    fn new_order_validation() {
+        let limit = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::Limit)
+            .set_quantity("1".parse().unwrap());
+        assert!(limit.validate().is_err());
+        assert!(limit.clone().set_price("100".parse::<Number>().unwrap()).validate().is_ok());
+        assert!(limit
+            .clone()
+            .set_price("100".parse::<Number>().unwrap())
+            .set_quote_order_qty("100".parse::<Number>().unwrap())
+            .validate()
+            .is_err());
+        assert!(limit
+            .clone()
+            .set_price("100".parse::<Number>().unwrap())
+            .set_iceberg_qty("0.1".parse::<Number>().unwrap())
+            .set_time_in_force(TimeInForce::IOC)
+            .validate()
+            .is_err());
+        assert!(limit
+            .set_price("100".parse::<Number>().unwrap())
+            .set_strategy(1, 999)
+            .validate()
+            .is_err());
+
+        let market = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::Market);
+        assert!(market.clone().set_quote_order_qty("100".parse().unwrap()).validate().is_ok());
+        assert!(market
+            .clone()
+            .set_quantity("1".parse().unwrap())
+            .set_price("100".parse().unwrap())
+            .validate()
+            .is_err());
+        assert!(market
+            .set_quantity("1".parse::<Number>().unwrap())
+            .set_time_in_force(TimeInForce::GTC)
+            .validate()
+            .is_err());
+
+        let stop_loss = NewOrder::new("BTCUSDT", OrderSide::Sell, OrderType::StopLossLimit)
+            .set_quantity("1".parse::<Number>().unwrap())
+            .set_price("100".parse::<Number>().unwrap());
+        assert!(stop_loss.validate().is_err());
+        assert!(stop_loss.set_stop_price("101".parse::<Number>().unwrap()).validate().is_ok());
+
+        let limit_maker = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::LimitMaker)
+            .set_quantity("1".parse::<Number>().unwrap())
+            .set_price("100".parse::<Number>().unwrap());
+        assert!(limit_maker.clone().validate().is_ok());
+        assert!(limit_maker
+            .set_time_in_force(TimeInForce::GTC)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn cancel_replace_order() {
+        let mock_cancel_replace = mock("POST", "/api/v3/order/cancelReplace")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("cancelOrderId=9&cancelReplaceMode=ALLOW_FAILURE&cancelRestrictions=ONLY_NEW&newOrderRespType=ACK&price=0.02&quantity=0.0001&recvWindow=1234&side=BUY&symbol=BTCUSDT&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/cancel_replace_order.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let cancel = CancelReplaceOrder::new(CancelReplaceMode::AllowFailure, 9)
+            .set_cancel_restrictions(CancelRestrictions::OnlyNew);
+        let order = NewOrder::limit(
+            "BTCUSDT",
+            OrderSide::Buy,
+            "0.0001".parse().unwrap(),
+            "0.02".parse().unwrap(),
+        )
+        .set_new_order_resp_type(NewOrderResponseType::Ack);
+        let cancel_replace = account.cancel_replace_order(cancel, order).unwrap();
+
+        mock_cancel_replace.assert();
+
+        assert_eq!(cancel_replace.success_cancel().unwrap().status, "CANCELED");
+        assert!(cancel_replace.success_submit().is_none());
+        assert!(cancel_replace.failed_submit().is_none());
+        assert_eq!(cancel_replace.acknowledged_submit().unwrap().order_id, 10);
+    }
+
+    #[test]
+    fn place_oco() {
+        let mock_oco = mock("POST", "/api/v3/orderList/oco")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("aboveClientOrderId=take-profit&abovePrice=31000&aboveType=LIMIT_MAKER&belowPrice=29000&belowStopPrice=29100&belowTimeInForce=GTC&belowType=STOP_LOSS_LIMIT&quantity=1&recvWindow=1234&side=SELL&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/order_list_oco.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order_list = account
+            .place_oco(OcoOrderList {
+                symbol: "BTCUSDT".into(),
+                side: OrderSide::Sell,
+                quantity: "1".parse::<Number>().unwrap(),
+                above: ListLeg::limit_maker("31000".parse().unwrap())
+                    .set_client_order_id("take-profit"),
+                below: ListLeg::stop_loss_limit(
+                    "29000".parse().unwrap(),
+                    "29100".parse().unwrap(),
+                ),
+                list_client_order_id: None,
+                self_trade_prevention_mode: None,
+            })
+            .unwrap();
+
+        mock_oco.assert();
+
+        assert_eq!(order_list.order_list_id, 1);
+        assert_eq!(order_list.contingency_type, "OCO");
+        assert_eq!(order_list.list_order_status, "EXECUTING");
+        assert_eq!(order_list.orders.len(), 2);
+        assert_eq!(order_list.order_reports[0].type_name, "STOP_LOSS_LIMIT");
+        assert_eq!(
+            order_list.order_reports[0].stop_price,
+            Some("29100".parse::<Number>().unwrap())
+        );
+        assert_eq!(order_list.order_reports[1].stop_price, None);
+
+        let limit_above = OcoOrderList {
+            symbol: "BTCUSDT".into(),
+            side: OrderSide::Sell,
+            quantity: "1".parse::<Number>().unwrap(),
+            above: ListLeg::limit("31000".parse::<Number>().unwrap()),
+            below: ListLeg::stop_loss("29000".parse::<Number>().unwrap()),
+            list_client_order_id: None,
+            self_trade_prevention_mode: None,
+        };
+        assert!(account.place_oco(limit_above).is_err());
+    }
+
+    #[test]
+    fn place_legacy_oco() {
+        let mock_oco = mock("POST", "/api/v3/order/oco")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("listClientOrderId=list-1&price=31000&quantity=1&recvWindow=1234&side=SELL&stopLimitPrice=29000&stopLimitTimeInForce=GTC&stopPrice=29100&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/order_list_oco.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let order_list = account
+            .place_legacy_oco(LegacyOcoOrder {
+                symbol: "BTCUSDT".into(),
+                side: OrderSide::Sell,
+                quantity: "1".parse::<Number>().unwrap(),
+                price: "31000".parse::<Number>().unwrap(),
+                stop_price: "29100".parse::<Number>().unwrap(),
+                stop_limit_price: Some("29000".parse::<Number>().unwrap()),
+                stop_limit_time_in_force: None,
+                list_client_order_id: Some("list-1".into()),
+                limit_client_order_id: None,
+                stop_client_order_id: None,
+            })
+            .unwrap();
+
+        mock_oco.assert();
+
+        assert_eq!(order_list.order_reports.len(), 2);
+    }
+
+    #[test]
+    fn place_oto_and_otoco() {
+        let mock_oto = mock("POST", "/api/v3/orderList/oto")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("pendingQuantity=0.5&pendingSide=SELL&pendingType=MARKET&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+&workingPrice=30000&workingQuantity=0.5&workingSide=BUY&workingType=LIMIT_MAKER".into()))
+            .with_body_from_file("tests/mocks/account/order_list_otoco.json")
+            .create();
+        let mock_otoco = mock("POST", "/api/v3/orderList/otoco")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("listClientOrderId=bracket-1&pendingAbovePrice=31000&pendingAboveType=LIMIT_MAKER&pendingBelowStopPrice=29000&pendingBelowType=STOP_LOSS&pendingQuantity=0.5&pendingSide=SELL&recvWindow=1234&selfTradePreventionMode=EXPIRE_BOTH&symbol=BTCUSDT&timestamp=\\d+&workingClientOrderId=entry-1&workingPrice=30000&workingQuantity=0.5&workingSide=BUY&workingTimeInForce=GTC&workingType=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/order_list_otoco.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        account
+            .place_oto(OtoOrderList {
+                symbol: "BTCUSDT".into(),
+                working: ListLeg::limit_maker("30000".parse::<Number>().unwrap()),
+                working_side: OrderSide::Buy,
+                working_quantity: "0.5".parse::<Number>().unwrap(),
+                pending: ListLeg::new(OrderType::Market),
+                pending_side: OrderSide::Sell,
+                pending_quantity: "0.5".parse::<Number>().unwrap(),
+                list_client_order_id: None,
+                self_trade_prevention_mode: None,
+            })
+            .unwrap();
+        let order_list = account
+            .place_otoco(OtocoOrderList {
+                symbol: "BTCUSDT".into(),
+                working: ListLeg::limit("30000".parse().unwrap()).set_client_order_id("entry-1"),
+                working_side: OrderSide::Buy,
+                working_quantity: "0.5".parse::<Number>().unwrap(),
+                pending_side: OrderSide::Sell,
+                pending_quantity: "0.5".parse::<Number>().unwrap(),
+                pending_above: ListLeg::limit_maker("31000".parse::<Number>().unwrap()),
+                pending_below: Some(ListLeg::stop_loss("29000".parse::<Number>().unwrap())),
+                list_client_order_id: Some("bracket-1".into()),
+                self_trade_prevention_mode: Some(SelfTradePreventionMode::ExpireBoth),
+            })
+            .unwrap();
+
+        mock_oto.assert();
+        mock_otoco.assert();
+
+        assert_eq!(order_list.contingency_type, "OTO");
+        assert_eq!(order_list.orders.len(), 3);
+        assert_eq!(order_list.order_reports[1].status, "PENDING_NEW");
+    }
+
+    #[test]
+    fn cancel_and_query_order_lists() {
+        let mock_cancel = mock("DELETE", "/api/v3/orderList")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "orderListId=1&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/account/cancel_order_list.json")
+            .create();
+        let mock_all = mock("GET", "/api/v3/allOrderList")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("fromId=1&limit=10&recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/all_order_list.json")
+            .create();
+        let mock_open = mock("GET", "/api/v3/openOrderList")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body("[]")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let cancelled = account.cancel_order_list("BTCUSDT", 1).unwrap();
+        let order_lists = account.get_all_order_lists(1, None, None, 10).unwrap();
+        let open_order_lists = account.get_open_order_lists().unwrap();
+
+        mock_cancel.assert();
+        mock_all.assert();
+        mock_open.assert();
+
+        assert_eq!(cancelled.list_status_type, "ALL_DONE");
+        assert_eq!(
+            cancelled.order_reports[0].orig_client_order_id.as_deref(),
+            Some("44nZvqpemY7sVYgPYbvPih")
+        );
+        assert_eq!(cancelled.order_reports[1].status, "CANCELED");
+        assert_eq!(order_lists.len(), 2);
+        assert!(order_lists[1].order_reports.is_empty());
+        assert_eq!(order_lists[1].orders[0].client_order_id, "entry-1");
+        assert!(open_order_lists.is_empty());
+    }
+
+    #[test]
+    fn commission() {
+        let mock_commission = mock("GET", "/api/v3/account/commission")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/commission.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let commission = account.get_commission("BTCUSDT").unwrap();
+
+        mock_commission.assert();
+
+        assert_eq!(commission.symbol, "BTCUSDT");
+        assert!(commission.special_commission.is_none());
+        assert_eq!(commission.discount.discount_asset, "BNB");
+        assert_eq!(commission.discount.discount, "0.75".parse::<Number>().unwrap());
+        let tolerance = "0.000000000001".parse::<Number>().unwrap();
+        // Taker buy: standard taker and buyer plus tax taker and buyer
+        // 0.0000002 + 0.0000003 + 0.00000114 + 0.00000118
+        let rate = commission.rate(true, false) - "0.00000282".parse::<Number>().unwrap();
+        assert!(rate.abs() < tolerance);
+        // Maker sell of 1000: standard maker and seller plus tax maker and seller
+        // 1000 * (0.0000001 + 0.0000004 + 0.00000112 + 0.00000116)
+        let fee = commission.commission("1000".parse().unwrap(), false, true)
+            - "0.00278".parse::<Number>().unwrap();
+        assert!(fee.abs() < tolerance);
+    }
+
+    #[test]
+    fn order_rate_limits() {
+        let mock_rate_limits = mock("GET", "/api/v3/rateLimit/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/rate_limit_order.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let rate_limits = account.get_order_rate_limits().unwrap();
+
+        mock_rate_limits.assert();
+
+        assert_eq!(rate_limits.len(), 2);
+        assert_eq!(rate_limits[1].interval, "DAY");
+        assert_eq!(rate_limits[1].limit - rate_limits[1].count, 159988);
+    }
+
+    #[test]
+    fn prevented_matches_and_allocations() {
+        let mock_prevented_matches = mock("GET", "/api/v3/myPreventedMatches")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("fromPreventedMatchId=1&limit=10&orderId=5&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/prevented_matches.json")
+            .create();
+        let mock_allocations = mock("GET", "/api/v3/myAllocations")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("fromAllocationId=0&orderId=1&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/allocations.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+
+        assert!(account
+            .get_prevented_matches("BTCUSDT", None, None, None, None)
+            .is_err());
+        let prevented = account
+            .get_prevented_matches("BTCUSDT", None, 5, 1, 10)
+            .unwrap();
+        let query = HistoryQuery::new("BTCUSDT").set_order_id(1).set_from_id(0);
+        let allocations = account.get_allocations(&query).unwrap();
+
+        mock_prevented_matches.assert();
+        mock_allocations.assert();
+
+        assert_eq!(prevented[0].self_trade_prevention_mode, "EXPIRE_MAKER");
+        assert_eq!(prevented[0].maker_order_id, 3);
+        assert_eq!(prevented[0].maker_prevented_quantity, "1.3".parse::<Number>().unwrap());
+        assert_eq!(allocations[0].allocation_type, "SOR");
+        assert_eq!(allocations[0].quote_qty, "5".parse::<Number>().unwrap());
+        assert!(!allocations[0].is_allocator);
+    }
+
+    #[test]
+    fn place_sor_order() {
+        let mock_sor_order = mock("POST", "/api/v3/sor/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=31000&quantity=0.5&recvWindow=1234&side=BUY&symbol=BTCUSDT&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/sor_order_full.json")
+            .create();
+        let mock_sor_order_test = mock("POST", "/api/v3/sor/order/test")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("quantity=0.5&recvWindow=1234&side=SELL&symbol=BTCUSDT&timestamp=\\d+&type=MARKET".into()))
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+
+        let response = account
+            .place_sor_order(NewOrder::limit(
+                "BTCUSDT",
+                OrderSide::Buy,
+                "0.5".parse().unwrap(),
+                "31000".parse().unwrap(),
+            ))
+            .unwrap();
+        account
+            .test_sor_order(NewOrder::market("BTCUSDT", OrderSide::Sell, "0.5".parse().unwrap()))
+            .unwrap();
+        let stop_loss = NewOrder::new("BTCUSDT", OrderSide::Sell, OrderType::StopLoss)
+            .set_quantity("0.5".parse::<Number>().unwrap())
+            .set_stop_price("29000".parse::<Number>().unwrap());
+        assert!(account.place_sor_order(stop_loss).is_err());
+
+        mock_sor_order.assert();
+        mock_sor_order_test.assert();
+
+        let transaction = response.transaction().unwrap();
+        assert_eq!(transaction.used_sor, Some(true));
+        assert_eq!(transaction.working_floor.as_deref(), Some("SOR"));
+        let fills = transaction.fills.as_ref().unwrap();
+        assert_eq!(fills[0].trade_id, None);
+        assert_eq!(fills[0].alloc_id, Some(0));
+        assert_eq!(fills[0].match_type.as_deref(), Some("ONE_PARTY_TRADE_REPORT"));
+        assert_eq!(fills[0].price, "28000".parse::<Number>().unwrap());
+    }
 }
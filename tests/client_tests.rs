@@ -0,0 +1,83 @@
+use binance::api::*;
+use binance::config::*;
+use binance::general::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    // Answers every ping after `delay`
+    fn slow_server(delay: Duration) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                thread::sleep(delay);
+                let _ = reader.into_inner().write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+                );
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn user_agent_and_extra_headers() {
+        let mock_ping = mock("GET", "/api/v3/ping")
+            .match_header("user-agent", "desk-7/1.0")
+            .match_header("x-desk", "alpha")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body("{}")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_user_agent("desk-7/1.0")
+            .add_header("X-Desk", "alpha");
+        let general: General = Binance::new_with_config(None, None, &config);
+
+        assert_eq!(general.ping().unwrap(), "pong");
+        mock_ping.assert();
+    }
+
+    #[test]
+    fn invalid_extra_header() {
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .add_header("bad header", "alpha");
+        let general: General = Binance::new_with_config(None, None, &config);
+
+        assert!(general.ping().is_err());
+    }
+
+    #[test]
+    fn configured_and_per_request_timeout() {
+        let url = slow_server(Duration::from_millis(600));
+
+        let config = Config::default()
+            .set_rest_api_endpoint(url)
+            .set_timeout(Duration::from_millis(200))
+            .set_connect_timeout(Duration::from_secs(1))
+            .set_pool_max_idle_per_host(0)
+            .set_tcp_nodelay(false);
+        let mut general: General = Binance::new_with_config(None, None, &config);
+        assert!(general.ping().is_err());
+
+        general.client = general.client.with_timeout(Duration::from_secs(5));
+        assert_eq!(general.ping().unwrap(), "pong");
+
+        let config = config.set_timeout(None);
+        let general: General = Binance::new_with_config(None, None, &config);
+        assert_eq!(general.ping().unwrap(), "pong");
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "decimal")]
+
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::market::*;
+use binance::rust_decimal::Decimal;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+
+    #[test]
+    fn exact_prices_and_quantities() {
+        let mock_get_price = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body_from_file("tests/mocks/market/get_price.json")
+            .create();
+        let mock_get_depth = mock("GET", "/api/v3/depth")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=LTCBTC".into()))
+            .with_body_from_file("tests/mocks/market/get_depth.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let market: Market = Binance::new_with_config(None, None, &config);
+
+        let price = market.get_price("LTCBTC").unwrap();
+        let depth = market.get_depth("LTCBTC").unwrap();
+
+        mock_get_price.assert();
+        mock_get_depth.assert();
+
+        assert_eq!(price.price, Decimal::new(400000200, 8));
+        assert_eq!(depth.asks[0].price - depth.bids[0].price, Decimal::new(2, 6));
+        // The exchange representation is kept
+        assert_eq!(
+            serde_json::to_string(&depth.bids[0]).unwrap(),
+            r#"{"price":"4.00000000","qty":"431.00000000"}"#
+        );
+    }
+
+    #[test]
+    fn order_parameters_without_float_noise() {
+        let mock_limit_buy = mock("POST", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=0.3&quantity=0.0000001&recvWindow=1234&side=BUY&symbol=LTCBTC&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/account/limit_buy.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+
+        let price = Decimal::new(1, 1) + Decimal::new(2, 1);
+        let transaction = account
+            .limit_buy("LTCBTC", Decimal::new(1, 7), price)
+            .unwrap();
+
+        mock_limit_buy.assert();
+
+        assert_eq!(transaction.price, Decimal::new(1, 1));
+        assert_eq!(transaction.orig_qty, Decimal::ONE);
+    }
+}
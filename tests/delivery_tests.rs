@@ -0,0 +1,160 @@
+use binance::api::*;
+use binance::config::*;
+use binance::delivery::account::*;
+use binance::delivery::general::*;
+use binance::delivery::market::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+    use binance::account::TimeInForce;
+    use binance::delivery::model::MarkPrices;
+
+    #[test]
+    fn exchange_info() {
+        let mock_exchange_info = mock("GET", "/dapi/v1/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/delivery/general/exchange_info.json")
+            .create();
+
+        let config = Config::default().set_delivery_rest_api_endpoint(mockito::server_url());
+        let general: DeliveryGeneral = Binance::new_with_config(None, None, &config);
+
+        let symbol = general.get_symbol_info("BTCUSD_200925").unwrap();
+
+        mock_exchange_info.assert();
+
+        assert_eq!(symbol.pair, "BTCUSD");
+        assert_eq!(symbol.contract_type, "CURRENT_QUARTER");
+        assert_eq!(symbol.margin_asset, "BTC");
+        assert_eq!(symbol.contract_size, 100);
+        assert!(approx_eq!(f64, symbol.notional_value(3.0), 300.0, ulps = 2));
+        assert!(approx_eq!(
+            f64,
+            symbol.base_quantity(10.0, 20000.0),
+            0.05,
+            ulps = 2
+        ));
+        assert!(approx_eq!(
+            f64,
+            symbol.contracts_for(0.0512, 20000.0),
+            10.0,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn limit_buy() {
+        let mock_limit_buy = mock("POST", "/dapi/v1/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=9000&quantity=10&recvWindow=1234&side=BUY&symbol=BTCUSD_200925&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/delivery/account/limit_buy.json")
+            .create();
+
+        let config = Config::default()
+            .set_delivery_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: DeliveryAccount = Binance::new_with_config(None, None, &config);
+        let _ = env_logger::try_init();
+        let order = account
+            .limit_buy("BTCUSD_200925", 10, 9000.0, TimeInForce::GTC)
+            .unwrap();
+
+        mock_limit_buy.assert();
+
+        assert_eq!(order.symbol, "BTCUSD_200925");
+        assert_eq!(order.pair, "BTCUSD");
+        assert_eq!(order.side, "BUY");
+        assert!(approx_eq!(f64, order.orig_qty, 10.0, ulps = 2));
+        assert!(approx_eq!(f64, order.cum_base, 0.0, ulps = 2));
+    }
+
+    #[test]
+    fn account_information() {
+        let mock_account_information = mock("GET", "/dapi/v1/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "recvWindow=1234&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/delivery/account/account_information.json")
+            .create();
+
+        let config = Config::default()
+            .set_delivery_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: DeliveryAccount = Binance::new_with_config(None, None, &config);
+        let information = account.account_information().unwrap();
+
+        mock_account_information.assert();
+
+        assert_eq!(information.fee_tier, 2);
+        assert_eq!(information.assets[0].asset, "BTC");
+        assert!(approx_eq!(
+            f64,
+            information.assets[0].wallet_balance,
+            0.00241969,
+            ulps = 2
+        ));
+        assert_eq!(information.positions[0].symbol, "BTCUSD_201225");
+        assert!(approx_eq!(f64, information.positions[0].max_qty, 50.0, ulps = 2));
+    }
+
+    #[test]
+    fn position_information() {
+        let mock_position_information = mock("GET", "/dapi/v1/positionRisk")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "pair=BTCUSD&recvWindow=1234&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/delivery/account/position_information.json")
+            .create();
+
+        let config = Config::default()
+            .set_delivery_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: DeliveryAccount = Binance::new_with_config(None, None, &config);
+        let positions = account.position_information("BTCUSD").unwrap();
+
+        mock_position_information.assert();
+
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "BTCUSD_201225");
+        assert!(!positions[0].is_auto_add_margin);
+        assert!(approx_eq!(f64, positions[0].position_amount, 2.0, ulps = 2));
+        assert!(approx_eq!(
+            f64,
+            positions[0].notional_value,
+            0.01818182,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn pair_mark_prices() {
+        let mock_mark_prices = mock("GET", "/dapi/v1/premiumIndex")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("pair=BTCUSD".into()))
+            .with_body_from_file("tests/mocks/delivery/market/mark_prices.json")
+            .create();
+
+        let config = Config::default().set_delivery_rest_api_endpoint(mockito::server_url());
+        let market: DeliveryMarket = Binance::new_with_config(None, None, &config);
+
+        let MarkPrices::AllMarkPrices(mark_prices) = market.get_pair_mark_prices("BTCUSD").unwrap();
+
+        mock_mark_prices.assert();
+
+        assert_eq!(mark_prices.len(), 2);
+        assert_eq!(mark_prices[0].symbol, "BTCUSD_PERP");
+        assert_eq!(mark_prices[0].last_funding_rate, "0.00071003");
+        assert!(approx_eq!(
+            f64,
+            mark_prices[1].mark_price,
+            12077.0134375,
+            ulps = 2
+        ));
+        assert_eq!(mark_prices[1].last_funding_rate, "");
+    }
+}
@@ -0,0 +1,180 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::filters::*;
+use binance::general::*;
+use binance::model::Number;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+
+    fn btcusdt_filters() -> SymbolFilters {
+        let mock_exchange_info = mock("GET", "/api/v3/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/general/exchange_info_filters.json")
+            .create();
+
+        let config = Config::default().set_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config);
+
+        let symbol = general.get_symbol_info("BTCUSDT").unwrap();
+        mock_exchange_info.assert();
+
+        SymbolFilters::new(&symbol).unwrap()
+    }
+
+    #[test]
+    fn valid_orders() {
+        let filters = btcusdt_filters();
+
+        let limit = NewOrder::limit(
+            "BTCUSDT",
+            OrderSide::Buy,
+            "0.001".parse().unwrap(),
+            "30000".parse().unwrap(),
+        );
+        let market =
+            NewOrder::market("BTCUSDT", OrderSide::Sell, "0.5".parse::<Number>().unwrap());
+        let quote_market = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::Market)
+            .set_quote_order_qty("100".parse::<Number>().unwrap());
+
+        assert!(filters.violations(&limit, "31000".parse::<Number>().unwrap()).is_empty());
+        assert!(filters.violations(&market, "31000".parse::<Number>().unwrap()).is_empty());
+        assert!(filters.violations(&quote_market, "31000".parse::<Number>().unwrap()).is_empty());
+        assert!(filters.validate(&limit, "31000".parse::<Number>().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn price_and_lot_size_violations() {
+        let filters = btcusdt_filters();
+
+        let order = NewOrder::limit(
+            "BTCUSDT",
+            OrderSide::Buy,
+            "0.000015".parse().unwrap(),
+            "30000.005".parse().unwrap(),
+        );
+        let violations = filters.violations(&order, "30000".parse::<Number>().unwrap());
+
+        assert_eq!(
+            violations,
+            vec![
+                FilterViolation::Price {
+                    parameter: "price",
+                    value: "30000.005".parse::<Number>().unwrap(),
+                    bound: Bound::NotOnStep,
+                    limit: "0.01".parse::<Number>().unwrap(),
+                },
+                FilterViolation::LotSize {
+                    value: "0.000015".parse::<Number>().unwrap(),
+                    bound: Bound::NotOnStep,
+                    limit: "0.00001".parse::<Number>().unwrap(),
+                },
+                FilterViolation::Notional {
+                    notional: "0.000015".parse::<Number>().unwrap()
+                        * "30000.005".parse::<Number>().unwrap(),
+                    bound: Bound::BelowMin,
+                    limit: "5".parse::<Number>().unwrap(),
+                },
+            ]
+        );
+        assert_eq!(violations[0].filter_type(), "PRICE_FILTER");
+        assert_eq!(violations[1].filter_type(), "LOT_SIZE");
+        assert_eq!(violations[2].filter_type(), "NOTIONAL");
+
+        let error = filters.validate(&order, "30000".parse::<Number>().unwrap()).unwrap_err();
+        assert_eq!(error.filter_violation(), Some(&violations[0]));
+
+        let market =
+            NewOrder::market("BTCUSDT", OrderSide::Sell, "200".parse::<Number>().unwrap());
+        let violations = filters.violations(&market, "30000".parse::<Number>().unwrap());
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].filter_type(), "MARKET_LOT_SIZE");
+    }
+
+    #[test]
+    fn percent_price_iceberg_and_trailing_delta_violations() {
+        let filters = btcusdt_filters();
+
+        let order = NewOrder::limit(
+            "BTCUSDT",
+            OrderSide::Sell,
+            "0.011".parse().unwrap(),
+            "200000".parse().unwrap(),
+        )
+        .set_iceberg_qty("0.001".parse::<Number>().unwrap());
+        let violations = filters.violations(&order, "30000".parse::<Number>().unwrap());
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].filter_type(), "PERCENT_PRICE_BY_SIDE");
+        match violations[0] {
+            FilterViolation::PercentPriceBySide { bound, limit, .. } => {
+                assert_eq!(bound, Bound::AboveMax);
+                assert_eq!(limit, "150000".parse::<Number>().unwrap());
+            }
+            _ => panic!("Unexpected violation"),
+        }
+        assert_eq!(
+            violations[1],
+            FilterViolation::IcebergParts {
+                parts: 11,
+                limit: 10
+            }
+        );
+
+        let stop = NewOrder::new("BTCUSDT", OrderSide::Buy, OrderType::StopLoss)
+            .set_quantity("0.01".parse::<Number>().unwrap())
+            .set_trailing_delta(5);
+        assert_eq!(
+            filters.violations(&stop, "30000".parse::<Number>().unwrap()),
+            vec![FilterViolation::TrailingDelta {
+                delta: 5,
+                bound: Bound::BelowMin,
+                limit: 10
+            }]
+        );
+    }
+
+    #[test]
+    fn rounding() {
+        let filters = btcusdt_filters();
+
+        assert_eq!(filters.tick_size().unwrap(), "0.01".parse::<Number>().unwrap());
+        assert_eq!(filters.step_size().unwrap(), "0.00001".parse::<Number>().unwrap());
+
+        assert_eq!(
+            filters.round_price("30000.006".parse::<Number>().unwrap()),
+            "30000.01".parse::<Number>().unwrap()
+        );
+        assert_eq!(
+            filters.round_price("0.3".parse::<Number>().unwrap()),
+            "0.3".parse::<Number>().unwrap()
+        );
+        assert_eq!(
+            filters.round_quantity("0.123459".parse::<Number>().unwrap()),
+            "0.12345".parse::<Number>().unwrap()
+        );
+        assert_eq!(
+            filters.round_quantity("0.00003".parse::<Number>().unwrap()),
+            "0.00003".parse::<Number>().unwrap()
+        );
+        assert_eq!(filters.format_price("30000".parse::<Number>().unwrap()), "30000.00");
+        assert_eq!(filters.format_quantity("1.5".parse::<Number>().unwrap()), "1.50000");
+
+        let order = filters.round_order(
+            NewOrder::limit(
+                "BTCUSDT",
+                OrderSide::Buy,
+                "0.0123456".parse().unwrap(),
+                "30000.004".parse().unwrap(),
+            )
+            .set_iceberg_qty("0.0025345".parse::<Number>().unwrap()),
+        );
+        assert_eq!(order.price, Some("30000".parse::<Number>().unwrap()));
+        assert_eq!(order.quantity, Some("0.01234".parse::<Number>().unwrap()));
+        assert_eq!(order.iceberg_qty, Some("0.00253".parse::<Number>().unwrap()));
+        assert!(filters.violations(&order, "30000".parse::<Number>().unwrap()).is_empty());
+    }
+}
@@ -8,7 +8,12 @@ mod tests {
     use mockito::{mock, Matcher};
     use float_cmp::*;
     use binance::account::OrderSide;
-    use binance::futures::model::Transaction;
+    use binance::futures::model::{
+        AmendRejectReason, BatchCancelResponse, BatchOrderResponse, ModifyOrderResponse, Transaction,
+    };
+    use binance::futures::heartbeat::CountdownHeartbeat;
+    use std::thread;
+    use std::time::Duration;
 
     #[test]
     fn change_initial_leverage() {
@@ -167,4 +172,472 @@ mod tests {
         assert_eq!(transaction.close_position, true);
         assert!(approx_eq!(f64, transaction.stop_price, 7.4, ulps = 2));
     }
+
+    #[test]
+    fn custom_batch_orders() {
+        let mock_batch_orders = mock("POST", "/fapi/v1/batchOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded(
+                    "batchOrders".into(),
+                    r#"[{"newClientOrderId":"bid-1","price":"30000","quantity":"0.001","side":"BUY","symbol":"BTCUSDT","timeInForce":"GTC","type":"LIMIT"},{"newClientOrderId":"bid-2","price":"29900","quantity":"0.001","side":"BUY","symbol":"BTCUSDT","timeInForce":"GTC","type":"LIMIT"}]"#.into(),
+                ),
+                Matcher::Regex("recvWindow=1234&timestamp=\\d+&signature=.*".into()),
+            ]))
+            .with_body_from_file("tests/mocks/futures/account/batch_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let _ = env_logger::try_init();
+        let bid = |price: f64, id: &str| CustomOrderRequest {
+            symbol: "BTCUSDT".into(),
+            side: OrderSide::Buy,
+            position_side: None,
+            order_type: OrderType::Limit,
+            time_in_force: Some(binance::account::TimeInForce::GTC),
+            qty: Some(0.001),
+            reduce_only: None,
+            price: Some(price),
+            stop_price: None,
+            close_position: None,
+            activation_price: None,
+            callback_rate: None,
+            working_type: None,
+            price_protect: None,
+            order_id: Some(id.into()),
+        };
+        let responses = account
+            .custom_batch_orders(vec![bid(30000.0, "bid-1"), bid(29900.0, "bid-2")])
+            .unwrap();
+
+        mock_batch_orders.assert();
+
+        assert_eq!(responses.len(), 2);
+        match &responses[0] {
+            BatchOrderResponse::Success(transaction) => {
+                assert_eq!(transaction.client_order_id, "bid-1");
+                assert!(approx_eq!(f64, transaction.price, 30000.0, ulps = 2));
+            }
+            BatchOrderResponse::Failure(error) => panic!("unexpected error {:?}", error),
+        }
+        match &responses[1] {
+            BatchOrderResponse::Failure(error) => assert_eq!(error.code, -2019),
+            BatchOrderResponse::Success(_) => panic!("the second order should have failed"),
+        }
+    }
+
+    #[test]
+    fn modify_batch_orders() {
+        let mock_batch_orders = mock("PUT", "/fapi/v1/batchOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::UrlEncoded(
+                "batchOrders".into(),
+                r#"[{"orderId":"2","price":"30100","quantity":"0.001","side":"BUY","symbol":"BTCUSDT"},{"origClientOrderId":"bid-2","price":"30000","quantity":"0.001","side":"BUY","symbol":"BTCUSDT"}]"#.into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/batch_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let modify = |price: f64, order_id: Option<u64>, client_id: Option<&str>| {
+            ModifyOrderRequest {
+                symbol: "BTCUSDT".into(),
+                side: OrderSide::Buy,
+                quantity: 0.001,
+                price,
+                price_match: None,
+                order_id,
+                orig_client_order_id: client_id.map(String::from),
+            }
+        };
+        let responses = account
+            .modify_batch_orders(vec![
+                modify(30100.0, Some(2), None),
+                modify(30000.0, None, Some("bid-2")),
+            ])
+            .unwrap();
+
+        mock_batch_orders.assert();
+        assert_eq!(responses.len(), 2);
+        assert!(matches!(responses[0], ModifyOrderResponse::Modified(_)));
+        match &responses[1] {
+            ModifyOrderResponse::Failed(error) => assert_eq!(error.code, -2019),
+            other => panic!("unexpected response {:?}", other),
+        }
+
+        assert!(account
+            .modify_batch_orders(vec![modify(30000.0, None, None)])
+            .is_err());
+    }
+
+    #[test]
+    fn cancel_batch_orders() {
+        let mock_cancel = mock("DELETE", "/fapi/v1/batchOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("orderIdList".into(), "[2,3]".into()),
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+            ]))
+            .with_body_from_file("tests/mocks/futures/account/batch_cancel_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let responses = account.cancel_batch_orders("BTCUSDT", vec![2, 3]).unwrap();
+
+        mock_cancel.assert();
+
+        match &responses[0] {
+            BatchCancelResponse::Success(order) => assert_eq!(order.status, "CANCELED"),
+            BatchCancelResponse::Failure(error) => panic!("unexpected error {:?}", error),
+        }
+        match &responses[1] {
+            BatchCancelResponse::Failure(error) => assert_eq!(error.code, -2011),
+            BatchCancelResponse::Success(_) => panic!("the second cancel should have failed"),
+        }
+    }
+
+    #[test]
+    fn batch_size_is_checked() {
+        let config = Config::default().set_futures_rest_api_endpoint("http://127.0.0.1:9");
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+
+        assert!(account.custom_batch_orders(vec![]).is_err());
+        assert!(account
+            .cancel_batch_orders("BTCUSDT", (0..11).collect())
+            .is_err());
+    }
+
+    #[test]
+    fn change_margin_type() {
+        let mock = mock("POST", "/fapi/v1/marginType")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "marginType=ISOLATED&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+&signature=.*"
+                    .into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/change_position_mode.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        account
+            .change_margin_type("BTCUSDT", MarginType::Isolated)
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[test]
+    fn add_position_margin() {
+        let mock = mock("POST", "/fapi/v1/positionMargin")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "amount=100&positionSide=LONG&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+&type=1"
+                    .into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/position_margin.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let response = account
+            .add_position_margin("BTCUSDT", 100.0, Some(PositionSide::Long))
+            .unwrap();
+
+        mock.assert();
+
+        assert_eq!(response.type_name, 1);
+        assert!(approx_eq!(f64, response.amount, 100.0, ulps = 2));
+    }
+
+    #[test]
+    fn leverage_brackets() {
+        let mock = mock("GET", "/fapi/v1/leverageBracket")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=ETHUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/leverage_brackets.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let brackets = account.leverage_brackets(Some("ETHUSDT".into())).unwrap();
+
+        mock.assert();
+
+        assert_eq!(brackets.len(), 1);
+        assert_eq!(brackets[0].notional_coef, Some(1.5));
+        assert_eq!(brackets[0].brackets[1].initial_leverage, 50);
+        assert!(approx_eq!(f64, brackets[0].brackets[1].cum, 35.0, ulps = 2));
+    }
+
+    #[test]
+    fn get_income() {
+        let mock = mock("GET", "/fapi/v1/income")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "incomeType=FUNDING_FEE&limit=100&recvWindow=1234&startTime=1570608000000&symbol=BTCUSDT&timestamp=\\d+"
+                    .into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/income.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let income = account
+            .get_income(
+                Some("BTCUSDT".into()),
+                IncomeType::FundingFee,
+                1570608000000,
+                None,
+                100,
+            )
+            .unwrap();
+
+        mock.assert();
+
+        assert_eq!(income.len(), 2);
+        assert_eq!(income[0].income_type, "FUNDING_FEE");
+        assert_eq!(income[0].tran_id, 9689322392);
+        assert!(approx_eq!(f64, income[0].income, -0.375, ulps = 2));
+        assert_eq!(income[1].trade_id, "2059192");
+    }
+
+    #[test]
+    fn adl_quantile() {
+        let mock = mock("GET", "/fapi/v1/adlQuantile")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/adl_quantile.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let quantiles = account.adl_quantile(None).unwrap();
+
+        mock.assert();
+
+        // Cross positions in hedge mode, HEDGE is only a flag
+        assert!(quantiles[0].adl_quantile.hedge.is_some());
+        assert_eq!(quantiles[0].adl_quantile.both, None);
+        assert_eq!(quantiles[0].adl_quantile.long, Some(3));
+        assert_eq!(quantiles[1].adl_quantile.both, Some(0));
+        assert_eq!(quantiles[1].adl_quantile.short, Some(2));
+    }
+
+    #[test]
+    fn get_force_orders() {
+        let mock = mock("GET", "/fapi/v1/forceOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "autoCloseType=LIQUIDATION&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/force_orders.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let orders = account
+            .get_force_orders(
+                Some("BTCUSDT".into()),
+                AutoCloseType::Liquidation,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        mock.assert();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].client_order_id, "autoclose-1596107620040000020");
+        assert!(!orders[0].price_protect);
+    }
+
+    #[test]
+    fn countdown_cancel_all() {
+        let mock = mock("POST", "/fapi/v1/countdownCancelAll")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "countdownTime=3000&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/countdown_cancel_all.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let countdown = account.countdown_cancel_all("BTCUSDT", 3000).unwrap();
+
+        mock.assert();
+
+        assert_eq!(countdown.symbol, "BTCUSDT");
+        assert_eq!(countdown.countdown_time, "3000");
+    }
+
+    #[test]
+    fn countdown_heartbeat() {
+        let mock_arm = mock("POST", "/fapi/v1/countdownCancelAll")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "countdownTime=1500&recvWindow=1234&symbol=ETHUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body(r#"{"symbol":"ETHUSDT","countdownTime":"1500"}"#)
+            .expect_at_least(3)
+            .create();
+        let mock_disarm = mock("POST", "/fapi/v1/countdownCancelAll")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "countdownTime=0&recvWindow=1234&symbol=ETHUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body(r#"{"symbol":"ETHUSDT","countdownTime":"0"}"#)
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let heartbeat =
+            CountdownHeartbeat::start(account, "ETHUSDT", Duration::from_millis(1500)).unwrap();
+
+        // Re-armed every 500ms
+        thread::sleep(Duration::from_millis(1200));
+        assert!(heartbeat.last_error().is_none());
+        heartbeat.disarm().unwrap();
+
+        mock_arm.assert();
+        mock_disarm.assert();
+    }
+
+    #[test]
+    fn modify_order_with_price_match() {
+        let mock_modify_order = mock("PUT", "/fapi/v1/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("orderId=20072994037&priceMatch=QUEUE&quantity=1&recvWindow=1234&side=BUY&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/modify_order.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let response = account
+            .modify_order(ModifyOrderRequest {
+                symbol: "BTCUSDT".into(),
+                side: OrderSide::Buy,
+                quantity: 1.0,
+                price: 0.0,
+                price_match: Some(PriceMatch::Queue),
+                order_id: Some(20072994037),
+                orig_client_order_id: None,
+            })
+            .unwrap();
+
+        mock_modify_order.assert();
+
+        match response {
+            ModifyOrderResponse::Modified(transaction) => {
+                assert_eq!(transaction.order_id, 20072994037);
+                assert_eq!(transaction.price_match.as_deref(), Some("QUEUE"));
+                assert!(approx_eq!(f64, transaction.price, 30005.0, ulps = 2));
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+    }
+
+    #[test]
+    fn modify_order_rejected() {
+        let mock_modify_order = mock("PUT", "/fapi/v1/order")
+            .with_status(400)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("origClientOrderId=bid-1&price=30000&quantity=0.001&recvWindow=1234&side=BUY&symbol=BTCUSDT&timestamp=\\d+".into()))
+            .with_body(r#"{"code":-5027,"msg":"No need to modify the order."}"#)
+            .create();
+        let mock_unauthorized = mock("PUT", "/fapi/v1/order")
+            .with_status(400)
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("origClientOrderId=bid-2".into()))
+            .with_body(r#"{"code":-2015,"msg":"Invalid API-key, IP, or permissions for action."}"#)
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let modify = |client_id: &str| ModifyOrderRequest {
+            symbol: "BTCUSDT".into(),
+            side: OrderSide::Buy,
+            quantity: 0.001,
+            price: 30000.0,
+            price_match: None,
+            order_id: None,
+            orig_client_order_id: Some(client_id.into()),
+        };
+        let response = account.modify_order(modify("bid-1")).unwrap();
+        let unauthorized = account.modify_order(modify("bid-2"));
+
+        mock_modify_order.assert();
+        mock_unauthorized.assert();
+
+        match response {
+            ModifyOrderResponse::Rejected { reason, error } => {
+                assert_eq!(reason, AmendRejectReason::NoChange);
+                assert_eq!(error.code, -5027);
+            }
+            other => panic!("unexpected response {:?}", other),
+        }
+        assert!(unauthorized.is_err());
+    }
+
+    #[test]
+    fn get_order_amendments() {
+        let mock_amendments = mock("GET", "/fapi/v1/orderAmendment")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "limit=2&orderId=20072994037&recvWindow=1234&symbol=BTCUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/order_amendment.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        let amendments = account
+            .get_order_amendments("BTCUSDT", 20072994037, None, None, None, 2)
+            .unwrap();
+
+        mock_amendments.assert();
+
+        assert_eq!(amendments.len(), 2);
+        assert_eq!(amendments[0].amendment_id, 5363);
+        assert_eq!(amendments[0].amendment.count, 3);
+        assert!(approx_eq!(f64, amendments[0].amendment.price.before, 30004.0, ulps = 2));
+        assert!(approx_eq!(f64, amendments[0].amendment.price.after, 30003.2, ulps = 2));
+        assert!(approx_eq!(f64, amendments[1].amendment.orig_qty.after, 1.0, ulps = 2));
+
+        assert!(account
+            .get_order_amendments("BTCUSDT", None, None, None, None, None)
+            .is_err());
+    }
 }
@@ -0,0 +1,114 @@
+use binance::futures::calculator::*;
+use binance::futures::model::{AccountInformation, LeverageBracket};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::*;
+    use std::convert::TryFrom;
+    use std::fs;
+
+    fn eth_brackets() -> LeverageBracket {
+        let brackets = fs::read_to_string("tests/mocks/futures/account/leverage_brackets.json")
+            .unwrap();
+        let mut brackets: Vec<LeverageBracket> = serde_json::from_str(&brackets).unwrap();
+        brackets.remove(0)
+    }
+
+    #[test]
+    fn margins() {
+        let brackets = eth_brackets();
+
+        let position = CalculatorPosition::new("ETHUSDT", 2.0, 2000.0, 10.0);
+        assert!(approx_eq!(f64, position.initial_margin(), 400.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.maint_margin(&brackets).unwrap(), 26.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.unrealized_pnl(2100.0), 200.0, epsilon = 1e-9));
+
+        // Second bracket, continuous with the first one at its cap
+        let position = CalculatorPosition::new("ETHUSDT", -10.0, 2000.0, 20.0);
+        assert!(approx_eq!(f64, position.maint_margin(&brackets).unwrap(), 165.0, epsilon = 1e-9));
+        assert_eq!(brackets.max_leverage(position.notional()), Some(50));
+        assert!(approx_eq!(f64, brackets.brackets[0].maint_margin(10000.0), 65.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, brackets.brackets[1].maint_margin(10000.0), 65.0, epsilon = 1e-9));
+
+        let position = CalculatorPosition::new("ETHUSDT", 30.0, 2000.0, 20.0);
+        assert!(position.maint_margin(&brackets).is_err());
+        let position = CalculatorPosition::new("BTCUSDT", 1.0, 30000.0, 20.0);
+        assert!(position.maint_margin(&brackets).is_err());
+    }
+
+    #[test]
+    fn isolated_liquidation_price() {
+        let brackets = eth_brackets();
+
+        let long = CalculatorPosition::new("ETHUSDT", 2.0, 2000.0, 10.0);
+        let wallet = MarginWallet::isolated(long.initial_margin());
+        let price = liquidation_price(&[long], &brackets, &wallet).unwrap().unwrap();
+        assert!(approx_eq!(f64, price, -3600.0 / (0.013 - 2.0), epsilon = 1e-9));
+
+        let short = CalculatorPosition::new("ETHUSDT", -2.0, 2000.0, 10.0);
+        let wallet = MarginWallet::isolated(short.initial_margin());
+        let price = liquidation_price(&[short], &brackets, &wallet).unwrap().unwrap();
+        assert!(approx_eq!(f64, price, 4400.0 / (0.013 + 2.0), epsilon = 1e-9));
+
+        // A fully collateralized long can't be liquidated
+        let long = CalculatorPosition::new("ETHUSDT", 2.0, 2000.0, 1.0);
+        let wallet = MarginWallet::isolated(long.initial_margin());
+        assert_eq!(liquidation_price(&[long], &brackets, &wallet).unwrap(), None);
+    }
+
+    #[test]
+    fn cross_liquidation_price() {
+        let brackets = eth_brackets();
+
+        // Hedge mode, both sides share the cross wallet
+        let long = CalculatorPosition::new("ETHUSDT", 2.0, 2000.0, 10.0);
+        let short = CalculatorPosition::new("ETHUSDT", -1.0, 2100.0, 10.0);
+        let wallet = MarginWallet::cross(1000.0, 10.0, -20.0);
+        let price = liquidation_price(&[long, short], &brackets, &wallet)
+            .unwrap()
+            .unwrap();
+        assert!(approx_eq!(f64, price, -930.0 / (0.0195 - 1.0), epsilon = 1e-9));
+
+        // One-way mode, with the BTCUSDT position of the account
+        let information = fs::read_to_string("tests/mocks/futures/account/account_information.json")
+            .unwrap();
+        let information: AccountInformation = serde_json::from_str(&information).unwrap();
+        let wallet = MarginWallet::cross_from_account(&information, "ETHUSDT");
+        assert!(approx_eq!(f64, wallet.other_maint_margin, 24.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, wallet.other_unrealized_pnl, 100.0, epsilon = 1e-9));
+
+        let position = CalculatorPosition::new("ETHUSDT", 10.0, 2000.0, 20.0);
+        let price = liquidation_price(&[position], &brackets, &wallet)
+            .unwrap()
+            .unwrap();
+        assert!(approx_eq!(f64, price, -9889.0 / (0.1 - 10.0), epsilon = 1e-9));
+
+        let btc = CalculatorPosition::try_from(&information.positions[0]).unwrap();
+        assert!(approx_eq!(f64, btc.leverage, 10.0, ulps = 2));
+        assert!(approx_eq!(f64, btc.initial_margin(), 600.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn break_even_and_sizing() {
+        let brackets = eth_brackets();
+
+        let long = CalculatorPosition::new("ETHUSDT", 2.0, 2000.0, 10.0);
+        let price = long.break_even_price(0.0004);
+        assert!(approx_eq!(
+            f64,
+            long.unrealized_pnl(price),
+            0.0004 * (long.notional() + 2.0 * price),
+            epsilon = 1e-9
+        ));
+        let short = CalculatorPosition::new("ETHUSDT", -2.0, 2000.0, 10.0);
+        assert!(short.break_even_price(0.0004) < 2000.0);
+
+        // Limited by the balance, then by the notional cap of the leverage
+        let quantity = max_quantity(&brackets, 100.0, 2000.0, 20.0).unwrap();
+        assert!(approx_eq!(f64, quantity, 1.0, epsilon = 1e-9));
+        let quantity = max_quantity(&brackets, 1000.0, 2000.0, 60.0).unwrap();
+        assert!(approx_eq!(f64, quantity, 5.0, epsilon = 1e-9));
+        assert!(max_quantity(&brackets, 1000.0, 2000.0, 100.0).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use binance::api::*;
 use binance::config::*;
 use binance::futures::market::FuturesMarket;
-use binance::futures::model::OpenInterestHist;
+use binance::futures::account::ContractType;
+use binance::futures::model::{KlineSummaries, OpenInterestHist};
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use mockito::{mock, Matcher};
+    use float_cmp::*;
 
     #[test]
     fn open_interest_statistics() {
@@ -41,4 +43,130 @@ mod tests {
 
         assert_eq!(open_interest_hists, expectation)
     }
+
+    #[test]
+    fn funding_rate() {
+        let mock_funding_rate = mock("GET", "/fapi/v1/fundingRate")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "endTime=1570636800000&limit=2&startTime=1570608000000&symbol=BTCUSDT".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/market/funding_rate.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let market: FuturesMarket = Binance::new_with_config(None, None, &config);
+
+        let funding_rates = market
+            .get_funding_rate(Some("BTCUSDT".into()), 2, 1570608000000, 1570636800000)
+            .unwrap();
+        mock_funding_rate.assert();
+
+        assert_eq!(funding_rates.len(), 2);
+        assert_eq!(funding_rates[1].funding_time, 1570636800000);
+        assert!(approx_eq!(f64, funding_rates[0].funding_rate, -0.0375, ulps = 2));
+    }
+
+    #[test]
+    fn long_short_ratios() {
+        let mock_position_ratio = mock("GET", "/futures/data/topLongShortPositionRatio")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("limit=2&period=5m&symbol=BTCUSDT".into()))
+            .with_body_from_file("tests/mocks/futures/market/top_long_short_position_ratio.json")
+            .create();
+        let mock_taker_volume = mock("GET", "/futures/data/takerlongshortRatio")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("period=5m&symbol=BTCUSDT".into()))
+            .with_body_from_file("tests/mocks/futures/market/taker_buy_sell_volume.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let market: FuturesMarket = Binance::new_with_config(None, None, &config);
+
+        let ratios = market
+            .top_long_short_position_ratio("BTCUSDT", "5m", 2, None, None)
+            .unwrap();
+        let volumes = market
+            .taker_buy_sell_volume("BTCUSDT", "5m", None, None, None)
+            .unwrap();
+        mock_position_ratio.assert();
+        mock_taker_volume.assert();
+
+        assert_eq!(ratios.len(), 2);
+        assert!(approx_eq!(f64, ratios[0].long_short_ratio, 1.4342, ulps = 2));
+        assert!(approx_eq!(f64, ratios[1].short_account, 0.6417, ulps = 2));
+        assert!(approx_eq!(f64, volumes[0].buy_volume, 387.33, ulps = 2));
+        assert!(approx_eq!(f64, volumes[0].sell_volume, 248.503, ulps = 2));
+    }
+
+    #[test]
+    fn lvt_klines() {
+        let mock_lvt_klines = mock("GET", "/fapi/v1/lvtKlines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("interval=1h&limit=1&symbol=BTCDOWN".into()))
+            .with_body_from_file("tests/mocks/futures/market/lvt_klines.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let market: FuturesMarket = Binance::new_with_config(None, None, &config);
+
+        let klines = market
+            .get_lvt_klines("BTCDOWN", "1h", 1, None, None)
+            .unwrap();
+        mock_lvt_klines.assert();
+
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].open_time, 1598371200000);
+        assert_eq!(klines[0].close, "5.99403551");
+        assert_eq!(klines[0].real_leverage, "2.28602984");
+        assert_eq!(klines[0].number_of_nav_updates, 6209);
+    }
+
+    #[test]
+    fn continuous_klines() {
+        let mock_continuous_klines = mock("GET", "/fapi/v1/continuousKlines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "contractType=PERPETUAL&interval=1m&pair=BTCUSDT".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/market/continuous_klines.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let market: FuturesMarket = Binance::new_with_config(None, None, &config);
+
+        let KlineSummaries::AllKlineSummaries(klines) = market
+            .get_continuous_klines("BTCUSDT", ContractType::Perpetual, "1m", None, None, None)
+            .unwrap();
+        mock_continuous_klines.assert();
+
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].close, "18896.13");
+        assert_eq!(klines[0].number_of_trades, 1874);
+    }
+
+    #[test]
+    fn index_info() {
+        let mock_index_info = mock("GET", "/fapi/v1/indexInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=DEFIUSDT".into()))
+            .with_body_from_file("tests/mocks/futures/market/index_info.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let market: FuturesMarket = Binance::new_with_config(None, None, &config);
+
+        let index_info = market.get_index_info(Some("DEFIUSDT".into())).unwrap();
+        mock_index_info.assert();
+
+        assert_eq!(index_info.len(), 1);
+        assert_eq!(index_info[0].component, "baseAsset");
+        assert_eq!(index_info[0].base_asset_list[1].base_asset, "BAND");
+        assert!(approx_eq!(
+            f64,
+            index_info[0].base_asset_list[0].weight_in_percentage,
+            0.027839,
+            ulps = 2
+        ));
+    }
 }
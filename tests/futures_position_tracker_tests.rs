@@ -0,0 +1,188 @@
+use binance::api::*;
+use binance::config::*;
+use binance::futures::account::*;
+use binance::futures::position_tracker::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+    use binance::futures::websockets::FuturesWebsocketEvent;
+
+    fn tracker() -> PositionTracker {
+        let _mock_account = mock("GET", "/fapi/v2/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/account_information.json")
+            .create();
+        let _mock_positions = mock("GET", "/fapi/v2/positionRisk")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/position_risk.json")
+            .create();
+        let _mock_brackets = mock("GET", "/fapi/v1/leverageBracket")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/futures/account/leverage_brackets_btcusdt.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+        PositionTracker::from_account(&account).unwrap()
+    }
+
+    fn apply(tracker: &mut PositionTracker, events: &[&str]) {
+        for event in events {
+            let event: serde_json::Value = serde_json::from_str(event).unwrap();
+            let event = match event["e"].as_str() {
+                Some("ACCOUNT_UPDATE") => {
+                    FuturesWebsocketEvent::AccountUpdate(serde_json::from_value(event).unwrap())
+                }
+                Some("ORDER_TRADE_UPDATE") => {
+                    FuturesWebsocketEvent::OrderTrade(serde_json::from_value(event).unwrap())
+                }
+                _ => FuturesWebsocketEvent::MarkPrice(serde_json::from_value(event).unwrap()),
+            };
+            tracker.handle_event(&event).unwrap();
+        }
+    }
+
+    fn fill(side: &str, qty: &str, price: &str, time: u64) -> String {
+        format!(
+            r#"{{"e":"ORDER_TRADE_UPDATE","E":{time},"T":{time},"o":{{"s":"BTCUSDT","c":"TEST","S":"{side}","o":"MARKET","f":"GTC","q":"{qty}","p":"0","ap":"{price}","sp":"0","x":"TRADE","X":"FILLED","i":8886774,"l":"{qty}","z":"{qty}","L":"{price}","N":"USDT","n":"0.1","T":{time},"t":1,"b":"0","a":"0","m":false,"R":false,"wt":"CONTRACT_PRICE","ot":"MARKET","ps":"BOTH","cp":false,"pP":false,"si":0,"ss":0,"rp":"0"}}}}"#,
+            side = side,
+            qty = qty,
+            price = price,
+            time = time
+        )
+    }
+
+    // Cross liquidation price of a lone BTCUSDT position in the first bracket
+    fn cross_liquidation_price(wallet_balance: f64, amount: f64, entry_price: f64) -> f64 {
+        (wallet_balance - amount * entry_price) / (amount.abs() * 0.004 - amount)
+    }
+
+    #[test]
+    fn seeded_from_rest() {
+        let tracker = tracker();
+
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.position_amount, 0.2, ulps = 2));
+        assert!(approx_eq!(f64, position.leverage, 10.0, ulps = 2));
+        assert!(approx_eq!(f64, position.unrealized_pnl().unwrap(), 100.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.initial_margin(), 600.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.roe().unwrap(), 1.0 / 6.0, epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            position.liquidation_distance().unwrap(),
+            (30500.0 - 27150.0) / 30500.0,
+            epsilon = 1e-9
+        ));
+
+        let balance = tracker.balance("USDT").unwrap();
+        assert!(approx_eq!(f64, balance.wallet_balance, 10000.0, ulps = 2));
+        assert!(approx_eq!(f64, balance.maint_margin, 24.0, ulps = 2));
+    }
+
+    #[test]
+    fn marked_to_market() {
+        let mut tracker = tracker();
+        apply(
+            &mut tracker,
+            &[r#"{"e":"markPriceUpdate","E":1562305380000,"s":"BTCUSDT","p":"31000.00000000","i":"30990.00000000","P":"30995.00000000","r":"0.00038167","T":1562306400000}"#],
+        );
+
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.unrealized_pnl().unwrap(), 200.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.notional().unwrap(), 6200.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, tracker.total_unrealized_pnl(), 200.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn fills_then_account_update() {
+        let mut tracker = tracker();
+
+        // Increase at a higher price, the entry price is averaged
+        apply(&mut tracker, &[&fill("BUY", "0.200", "31000", 1625474305000)]);
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.position_amount, 0.4, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.entry_price, 30500.0, epsilon = 1e-9));
+        // Recomputed from the tracked position and wallet
+        let liquidation_price = cross_liquidation_price(10000.0, 0.4, 30500.0);
+        assert!(approx_eq!(f64, position.liquidation_price.unwrap(), liquidation_price, epsilon = 1e-6));
+        assert!(approx_eq!(
+            f64,
+            position.liquidation_distance().unwrap(),
+            (30500.0 - liquidation_price) / 30500.0,
+            epsilon = 1e-9
+        ));
+
+        // Flip to short, the remaining quantity is entered at the fill price
+        apply(&mut tracker, &[&fill("SELL", "0.500", "32000", 1625474305001)]);
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.position_amount, -0.1, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.entry_price, 32000.0, epsilon = 1e-9));
+
+        // The exchange view overrides the tracked fills
+        apply(
+            &mut tracker,
+            &[r#"{"e":"ACCOUNT_UPDATE","E":1625474305002,"T":1625474305001,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"10250.00000000","cw":"10250.00000000","bc":"0"}],"P":[{"s":"BTCUSDT","pa":"-0.100","ep":"31990.00000","bep":"31990","cr":"250","up":"0","mt":"cross","iw":"0","ps":"BOTH"}]}}"#],
+        );
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.entry_price, 31990.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.accumulated_realized, 250.0, epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            tracker.balance("USDT").unwrap().wallet_balance,
+            10250.0,
+            epsilon = 1e-9
+        ));
+
+        // Short PnL with the seeded mark price
+        assert!(approx_eq!(
+            f64,
+            position.unrealized_pnl().unwrap(),
+            -0.1 * (30500.0 - 31990.0),
+            epsilon = 1e-9
+        ));
+        assert_eq!(tracker.open_positions().len(), 1);
+    }
+
+    #[test]
+    fn account_update_then_fill() {
+        let mut tracker = tracker();
+
+        // The ACCOUNT_UPDATE of the fill arrives first
+        apply(
+            &mut tracker,
+            &[
+                r#"{"e":"ACCOUNT_UPDATE","E":1625474305001,"T":1625474305000,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"9999.90000000","cw":"9999.90000000","bc":"0"}],"P":[{"s":"BTCUSDT","pa":"0.400","ep":"30500.00000","bep":"30500","cr":"0","up":"0","mt":"cross","iw":"0","ps":"BOTH"}]}}"#,
+                &fill("BUY", "0.200", "31000", 1625474305000),
+            ],
+        );
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.position_amount, 0.4, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.entry_price, 30500.0, epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            position.liquidation_price.unwrap(),
+            cross_liquidation_price(9999.9, 0.4, 30500.0),
+            epsilon = 1e-6
+        ));
+
+        // A later fill is applied
+        apply(&mut tracker, &[&fill("BUY", "0.100", "31000", 1625474306000)]);
+        let position = tracker.position("BTCUSDT", "BOTH").unwrap();
+        assert!(approx_eq!(f64, position.position_amount, 0.5, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.entry_price, 30600.0, epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            position.liquidation_price.unwrap(),
+            cross_liquidation_price(9999.9, 0.5, 30600.0),
+            epsilon = 1e-6
+        ));
+    }
+}
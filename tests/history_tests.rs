@@ -0,0 +1,214 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::futures::account::*;
+use binance::history::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    fn trades(ids: &[(u64, u64)]) -> String {
+        let trades: Vec<String> = ids
+            .iter()
+            .map(|(id, time)| {
+                format!(
+                    r#"{{"symbol":"BNBBTC","id":{},"orderId":100234,"orderListId":-1,"price":"4.00000100","qty":"12.00000000","quoteQty":"48.000012","commission":"10.10000000","commissionAsset":"BNB","time":{},"isBuyer":true,"isMaker":false,"isBestMatch":true}}"#,
+                    id, time
+                )
+            })
+            .collect();
+        format!("[{}]", trades.join(","))
+    }
+
+    fn orders(ids: &[(u64, u64)]) -> String {
+        let orders: Vec<String> = ids
+            .iter()
+            .map(|(id, time)| {
+                format!(
+                    r#"{{"symbol":"BNBBTC","orderId":{},"orderListId":-1,"clientOrderId":"order{}","price":"0.1","origQty":"1.0","executedQty":"1.0","cummulativeQuoteQty":"0.1","status":"FILLED","timeInForce":"GTC","type":"LIMIT","side":"BUY","stopPrice":"0.0","icebergQty":"0.0","time":{},"updateTime":{},"isWorking":true,"selfTradePreventionMode":"NONE"}}"#,
+                    id, id, time, time
+                )
+            })
+            .collect();
+        format!("[{}]", orders.join(","))
+    }
+
+    fn account() -> Account {
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        Binance::new_with_config(None, None, &config)
+    }
+
+    #[test]
+    fn trade_history_query() {
+        let mock_trade_history = mock("GET", "/api/v3/myTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "^endTime=1499951949590&limit=500&orderId=100234&recvWindow=1234&startTime=1499865549590&symbol=BNBBTC&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/account/trade_history.json")
+            .create();
+
+        let query = HistoryQuery::new("BNBBTC")
+            .set_order_id(100234)
+            .set_start_time(1499865549590)
+            .set_end_time(1499865549590 + DAY)
+            .set_limit(500);
+        let trades = account().trade_history_query(&query).unwrap();
+
+        mock_trade_history.assert();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].id, 28457);
+    }
+
+    #[test]
+    fn trade_history_by_id() {
+        let mock_first_page = mock("GET", "/api/v3/myTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "^fromId=0&limit=2&recvWindow=1234&symbol=BNBBTC&timestamp=\\d+".into(),
+            ))
+            .with_body(trades(&[(1, 1000), (2, 2000)]))
+            .create();
+        let mock_last_page = mock("GET", "/api/v3/myTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "^fromId=3&limit=2&recvWindow=1234&symbol=BNBBTC&timestamp=\\d+".into(),
+            ))
+            .with_body(trades(&[(3, 3000)]))
+            .create();
+
+        let mut iter = account().trade_history_iter(HistoryQuery::new("BNBBTC").set_limit(2));
+        let ids: Vec<u64> = iter.by_ref().map(|trade| trade.unwrap().id).collect();
+
+        mock_first_page.assert();
+        mock_last_page.assert();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(iter.requests(), 2);
+    }
+
+    #[test]
+    fn all_orders_by_time() {
+        let start = 1_000;
+        let end = start + DAY + 500;
+
+        // A full page is continued from its last timestamp, then the next window starts
+        let pages = vec![
+            (start, start + DAY - 1, orders(&[(1, 2000), (2, 3000)])),
+            (3000, start + DAY - 1, orders(&[(2, 3000), (3, 5000)])),
+            (5000, start + DAY - 1, orders(&[(3, 5000)])),
+            (start + DAY, end, orders(&[(4, start + DAY + 100)])),
+        ];
+        let mocks: Vec<_> = pages
+            .into_iter()
+            .map(|(start_time, end_time, body)| {
+                mock("GET", "/api/v3/allOrders")
+                    .with_header("content-type", "application/json;charset=UTF-8")
+                    .match_query(Matcher::Regex(format!(
+                        "^endTime={}&limit=2&recvWindow=1234&startTime={}&symbol=BNBBTC&timestamp=\\d+",
+                        end_time, start_time
+                    )))
+                    .with_body(body)
+                    .create()
+            })
+            .collect();
+
+        let query = HistoryQuery::new("BNBBTC")
+            .set_start_time(start)
+            .set_end_time(end)
+            .set_limit(2);
+        let orders = account()
+            .all_orders_iter(query)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        for mock in mocks {
+            mock.assert();
+        }
+        let ids: Vec<u64> = orders.iter().map(|order| order.order_id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn trade_history_same_millisecond() {
+        let time_page = |start_time: u64, body: String| {
+            mock("GET", "/api/v3/myTrades")
+                .with_header("content-type", "application/json;charset=UTF-8")
+                .match_query(Matcher::Regex(format!(
+                    "^endTime=10000&limit=2&recvWindow=1234&startTime={}&symbol=BNBBTC&timestamp=\\d+",
+                    start_time
+                )))
+                .with_body(body)
+                .create()
+        };
+        let id_page = |from_id: u64, body: String| {
+            mock("GET", "/api/v3/myTrades")
+                .with_header("content-type", "application/json;charset=UTF-8")
+                .match_query(Matcher::Regex(format!(
+                    "^fromId={}&limit=2&recvWindow=1234&symbol=BNBBTC&timestamp=\\d+",
+                    from_id
+                )))
+                .with_body(body)
+                .create()
+        };
+
+        // Full pages within 2000 are walked by id, then by time after it
+        let mocks = vec![
+            time_page(1000, trades(&[(1, 2000), (2, 2000)])),
+            time_page(2000, trades(&[(1, 2000), (2, 2000)])),
+            id_page(3, trades(&[(3, 2000), (4, 2000)])),
+            id_page(5, trades(&[(5, 2000), (6, 3000)])),
+            time_page(2001, trades(&[(6, 3000)])),
+        ];
+
+        let query = HistoryQuery::new("BNBBTC")
+            .set_start_time(1000)
+            .set_end_time(10000)
+            .set_limit(2);
+        let mut iter = account().trade_history_iter(query);
+        let ids: Vec<u64> = iter.by_ref().map(|trade| trade.unwrap().id).collect();
+
+        for mock in mocks {
+            mock.assert();
+        }
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(iter.requests(), 5);
+    }
+
+    #[test]
+    fn futures_trade_history() {
+        let mock_user_trades = mock("GET", "/fapi/v1/userTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "^endTime=600000000&limit=1000&recvWindow=1234&startTime=0&symbol=BTCUSDT&timestamp=\\d+".into(),
+            ))
+            .with_body_from_file("tests/mocks/futures/account/user_trades.json")
+            .create();
+
+        let config = Config::default()
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: FuturesAccount = Binance::new_with_config(None, None, &config);
+
+        // 7 days fit in one window
+        let query = HistoryQuery::new("BTCUSDT")
+            .set_start_time(0)
+            .set_end_time(600_000_000);
+        let trades = account
+            .trade_history_iter(query)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        mock_user_trades.assert();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].id, 698759);
+        assert_eq!(trades[0].position_side, "SHORT");
+        assert!(approx_eq!(f64, trades[0].realized_pnl, -0.91539999, ulps = 2));
+    }
+}
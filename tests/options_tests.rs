@@ -0,0 +1,177 @@
+use binance::api::*;
+use binance::config::*;
+use binance::options::account::*;
+use binance::options::general::*;
+use binance::options::market::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+    use binance::account::TimeInForce;
+    use binance::model::Number;
+
+    #[test]
+    fn exchange_info() {
+        let mock_exchange_info = mock("GET", "/eapi/v1/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/options/general/exchange_info.json")
+            .create();
+
+        let config = Config::default().set_options_rest_api_endpoint(mockito::server_url());
+        let general: OptionsGeneral = Binance::new_with_config(None, None, &config);
+
+        let symbol = general.get_symbol_info("btc-220815-50000-c").unwrap();
+
+        mock_exchange_info.assert();
+
+        assert_eq!(symbol.side, "CALL");
+        assert_eq!(symbol.underlying, "BTCUSDT");
+        assert_eq!(symbol.expiry_date, 1660521600000);
+        assert_eq!(symbol.filters.len(), 2);
+        assert!(approx_eq!(f64, symbol.strike_price, 50000.0, ulps = 2));
+        assert!(approx_eq!(f64, symbol.min_qty, 0.01, ulps = 2));
+    }
+
+    #[test]
+    fn mark_prices_and_depth() {
+        let mock_mark = mock("GET", "/eapi/v1/mark")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("symbol=BTC-200730-9000-C".into()))
+            .with_body_from_file("tests/mocks/options/market/mark.json")
+            .create();
+        let mock_depth = mock("GET", "/eapi/v1/depth")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("limit=10&symbol=BTC-200730-9000-C".into()))
+            .with_body_from_file("tests/mocks/options/market/depth.json")
+            .create();
+
+        let config = Config::default().set_options_rest_api_endpoint(mockito::server_url());
+        let market: OptionsMarket = Binance::new_with_config(None, None, &config);
+
+        let mark_prices = market
+            .get_mark_prices(Some("BTC-200730-9000-C".into()))
+            .unwrap();
+        let depth = market.get_custom_depth("BTC-200730-9000-C", 10).unwrap();
+
+        mock_mark.assert();
+        mock_depth.assert();
+
+        assert_eq!(mark_prices.len(), 1);
+        assert!(approx_eq!(f64, mark_prices[0].mark_price, 1343.2883, ulps = 2));
+        assert!(approx_eq!(f64, mark_prices[0].mark_iv, 1.45, ulps = 2));
+        assert!(approx_eq!(f64, mark_prices[0].delta, 0.55937056, ulps = 2));
+        assert!(approx_eq!(f64, mark_prices[0].vega, 978.58874732, ulps = 2));
+
+        assert_eq!(depth.update_id, 37461);
+        assert_eq!(depth.bids[0].price, "1000".parse::<Number>().unwrap());
+        assert_eq!(depth.asks[0].qty, "0.1".parse::<Number>().unwrap());
+    }
+
+    #[test]
+    fn klines() {
+        let mock_klines = mock("GET", "/eapi/v1/klines")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "interval=5m&limit=10&symbol=BTC-200730-9000-C".into(),
+            ))
+            .with_body_from_file("tests/mocks/options/market/klines.json")
+            .create();
+
+        let config = Config::default().set_options_rest_api_endpoint(mockito::server_url());
+        let market: OptionsMarket = Binance::new_with_config(None, None, &config);
+
+        let klines = market
+            .get_klines("BTC-200730-9000-C", "5m", 10, None, None)
+            .unwrap();
+
+        mock_klines.assert();
+
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].trade_count, 10);
+        assert!(approx_eq!(f64, klines[0].close, 1100.0, ulps = 2));
+        assert!(approx_eq!(f64, klines[0].taker_amount, 2.0, ulps = 2));
+    }
+
+    #[test]
+    fn account_and_positions() {
+        let mock_account = mock("GET", "/eapi/v1/marginAccount")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "recvWindow=1234&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/options/account/margin_account.json")
+            .create();
+        let mock_position = mock("GET", "/eapi/v1/position")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "recvWindow=1234&symbol=BTC-200730-9000-C&timestamp=\\d+&signature=.*".into(),
+            ))
+            .with_body_from_file("tests/mocks/options/account/position.json")
+            .create();
+
+        let config = Config::default()
+            .set_options_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: OptionsAccount = Binance::new_with_config(None, None, &config);
+
+        let information = account.account_information().unwrap();
+        let positions = account
+            .position_information(Some("BTC-200730-9000-C".into()))
+            .unwrap();
+
+        mock_account.assert();
+        mock_position.assert();
+
+        assert_eq!(information.asset[0].asset, "USDT");
+        assert!(approx_eq!(f64, information.asset[0].unrealized_pnl, -5.00138, ulps = 2));
+        assert_eq!(information.greek[0].underlying, "BTCUSDT");
+        assert!(approx_eq!(f64, information.greek[0].delta, -0.05, ulps = 2));
+        assert!(!information.reduce_only);
+
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].side, "SHORT");
+        assert_eq!(positions[0].option_side, "CALL");
+        assert!(approx_eq!(f64, positions[0].quantity, -0.1, ulps = 2));
+        assert!(approx_eq!(f64, positions[0].mark_price, 1050.0138, ulps = 2));
+    }
+
+    #[test]
+    fn limit_buy_and_cancel() {
+        let mock_limit_buy = mock("POST", "/eapi/v1/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("price=100&quantity=1&recvWindow=1234&side=BUY&symbol=BTC-200730-9000-C&timeInForce=GTC&timestamp=\\d+&type=LIMIT".into()))
+            .with_body_from_file("tests/mocks/options/account/limit_buy.json")
+            .create();
+        let mock_cancel = mock("DELETE", "/eapi/v1/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex(
+                "orderId=4611875134427365377&recvWindow=1234&symbol=BTC-200730-9000-C&timestamp=\\d+"
+                    .into(),
+            ))
+            .with_body_from_file("tests/mocks/options/account/cancel_order.json")
+            .create();
+
+        let config = Config::default()
+            .set_options_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: OptionsAccount = Binance::new_with_config(None, None, &config);
+
+        let order = account
+            .limit_buy("BTC-200730-9000-C", 1, 100.0, TimeInForce::GTC)
+            .unwrap();
+        let cancelled = account
+            .cancel_order("BTC-200730-9000-C", order.order_id)
+            .unwrap();
+
+        mock_limit_buy.assert();
+        mock_cancel.assert();
+
+        assert_eq!(order.status, "ACCEPTED");
+        assert_eq!(order.option_side, "CALL");
+        assert!(approx_eq!(f64, order.quantity, 1.0, ulps = 2));
+        assert_eq!(cancelled.order_id, 4611875134427365377);
+        assert_eq!(cancelled.status, "CANCELLED");
+    }
+}
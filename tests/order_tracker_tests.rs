@@ -0,0 +1,180 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::model::Transaction;
+use binance::order_tracker::*;
+use binance::symbol_registry::SymbolMarket;
+use binance::websockets::WebsocketEvent;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+    use binance::futures::websockets::FuturesWebsocketEvent;
+
+    fn execution_report(
+        order_id: u64, execution: &str, status: &str, last_qty: &str, total_qty: &str,
+        last_price: &str, trade_id: i64,
+    ) -> WebsocketEvent {
+        let event = format!(
+            r#"{{"e":"executionReport","E":1499405658658,"s":"LTCBTC","c":"myOrder{order_id}","S":"BUY","o":"LIMIT","f":"GTC","q":"2.00000000","p":"0.10000000","P":"0.00000000","F":"0.00000000","g":-1,"C":"","x":"{execution}","X":"{status}","r":"NONE","i":{order_id},"l":"{last_qty}","z":"{total_qty}","L":"{last_price}","n":"0.00100000","N":"BNB","T":1499405658657,"t":{trade_id},"I":8641984,"w":true,"m":false,"M":false,"O":1499405658657,"Z":"0.00000000","Y":"0.00000000","Q":"0.00000000"}}"#,
+            order_id = order_id,
+            execution = execution,
+            status = status,
+            last_qty = last_qty,
+            total_qty = total_qty,
+            last_price = last_price,
+            trade_id = trade_id
+        );
+        WebsocketEvent::OrderTrade(serde_json::from_str(&event).unwrap())
+    }
+
+    #[test]
+    fn spot_lifecycle() {
+        let mut tracker = OrderTracker::new();
+        let events = vec![
+            execution_report(1, "NEW", "NEW", "0", "0", "0", -1),
+            execution_report(1, "TRADE", "PARTIALLY_FILLED", "0.5", "0.5", "0.09", 10),
+            // Delivered twice
+            execution_report(1, "TRADE", "PARTIALLY_FILLED", "0.5", "0.5", "0.09", 10),
+            execution_report(1, "TRADE", "FILLED", "1.5", "2", "0.1", 11),
+            // Late event
+            execution_report(1, "NEW", "NEW", "0", "0", "0", -1),
+        ];
+        for event in &events {
+            tracker.handle_event(event).unwrap();
+        }
+
+        let order = tracker.order(SymbolMarket::Spot, "LTCBTC", 1).unwrap();
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.client_order_id, "myOrder1");
+        assert_eq!(order.side, "BUY");
+        assert_eq!(order.fills.len(), 2);
+        assert!(approx_eq!(f64, order.executed_qty, 2.0, ulps = 2));
+        assert!(approx_eq!(f64, order.remaining_qty(), 0.0, ulps = 2));
+        assert!(approx_eq!(f64, order.fees["BNB"], 0.002, ulps = 2));
+        assert!(approx_eq!(
+            f64,
+            order.average_price().unwrap(),
+            (0.5 * 0.09 + 1.5 * 0.1) / 2.0,
+            epsilon = 1e-12
+        ));
+        assert!(tracker.open_orders(SymbolMarket::Spot).is_empty());
+    }
+
+    #[test]
+    fn spot_order_response() {
+        let body = std::fs::read_to_string("tests/mocks/account/new_order_full.json").unwrap();
+        let transaction: Transaction = serde_json::from_str(&body).unwrap();
+
+        let mut tracker = OrderTracker::new();
+        tracker.track_transaction(&transaction);
+
+        let order = tracker
+            .order_by_client_id(SymbolMarket::Spot, "6gCrw2kRUAF9CvJDGP16IP")
+            .unwrap();
+        assert_eq!(order.order_id, 28);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.fills.len(), 2);
+        assert!(approx_eq!(f64, order.fees["BTC"], 0.001, ulps = 2));
+        assert!(approx_eq!(f64, order.average_price().unwrap(), 30000.5, epsilon = 1e-9));
+
+        // The response already carried the fills
+        tracker.track_transaction(&transaction);
+        let order = tracker.order(SymbolMarket::Spot, "BTCUSDT", 28).unwrap();
+        assert_eq!(order.fills.len(), 2);
+        assert!(approx_eq!(f64, order.fees["BTC"], 0.001, ulps = 2));
+    }
+
+    #[test]
+    fn spot_reconcile() {
+        let mock_open_orders = mock("GET", "/api/v3/openOrders")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("^recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/get_open_orders.json")
+            .create();
+        let mock_order_status = mock("GET", "/api/v3/order")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("orderId=2&recvWindow=1234&symbol=LTCBTC&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/order_status_filled.json")
+            .create();
+        let mock_trades = mock("GET", "/api/v3/myTrades")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("limit=1000&orderId=2&recvWindow=1234&symbol=LTCBTC&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/account/order_trades.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234);
+        let account: Account = Binance::new_with_config(None, None, &config);
+
+        // Order 1 was missed, order 2 filled while the stream was down
+        let mut tracker = OrderTracker::new();
+        tracker
+            .handle_event(&execution_report(2, "NEW", "NEW", "0", "0", "0", -1))
+            .unwrap();
+        tracker
+            .handle_event(&execution_report(2, "TRADE", "PARTIALLY_FILLED", "0.5", "0.5", "0.1", 10))
+            .unwrap();
+
+        let mut changed = tracker.reconcile(&account).unwrap();
+        changed.sort_by_key(|order| order.order_id);
+
+        mock_open_orders.assert();
+        mock_order_status.assert();
+        mock_trades.assert();
+
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0].order_id, 1);
+        assert_eq!(changed[0].status, OrderStatus::New);
+        assert_eq!(changed[1].status, OrderStatus::Filled);
+        assert!(approx_eq!(f64, changed[1].average_price().unwrap(), 0.1, epsilon = 1e-12));
+
+        // The missed fill is restored once, with its fee
+        assert_eq!(changed[1].fills.len(), 2);
+        assert!(approx_eq!(f64, changed[1].filled_qty(), 2.0, epsilon = 1e-12));
+        assert!(approx_eq!(f64, changed[1].fees["BNB"], 0.004, epsilon = 1e-12));
+
+        let open = tracker.open_orders(SymbolMarket::Spot);
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].client_order_id, "myOrder1");
+    }
+
+    #[test]
+    fn futures_lifecycle() {
+        let fill = |status: &str, execution: &str, last_qty: &str, total_qty: &str, trade_id: i64| {
+            let event = format!(
+                r#"{{"e":"ORDER_TRADE_UPDATE","E":1568879465651,"T":1568879465650,"o":{{"s":"BTCUSDT","c":"TEST","S":"SELL","o":"LIMIT","f":"GTC","q":"0.2","p":"30000","ap":"30000","sp":"0","x":"{execution}","X":"{status}","i":8886774,"l":"{last_qty}","z":"{total_qty}","L":"30000","N":"USDT","n":"0.6","T":1568879465650,"t":{trade_id},"b":"0","a":"0","m":true,"R":false,"wt":"CONTRACT_PRICE","ot":"LIMIT","ps":"BOTH","cp":false,"pP":false,"si":0,"ss":0,"rp":"0"}}}}"#,
+                status = status,
+                execution = execution,
+                last_qty = last_qty,
+                total_qty = total_qty,
+                trade_id = trade_id
+            );
+            FuturesWebsocketEvent::OrderTrade(serde_json::from_str(&event).unwrap())
+        };
+
+        let mut tracker = OrderTracker::new();
+        tracker
+            .handle_futures_event(&fill("PARTIALLY_FILLED", "TRADE", "0.1", "0.1", 1))
+            .unwrap();
+        tracker
+            .handle_futures_event(&fill("CANCELED", "CANCELED", "0", "0.1", 0))
+            .unwrap();
+
+        let order = tracker
+            .order_by_client_id(SymbolMarket::Futures, "TEST")
+            .unwrap();
+        assert_eq!(order.order_id, 8886774);
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert_eq!(order.fills.len(), 1);
+        assert!(approx_eq!(f64, order.remaining_qty(), 0.1, epsilon = 1e-12));
+        assert!(approx_eq!(f64, order.fees["USDT"], 0.6, ulps = 2));
+        assert!(tracker.order(SymbolMarket::Spot, "BTCUSDT", 8886774).is_none());
+
+        tracker.remove_closed();
+        assert_eq!(tracker.orders().count(), 0);
+    }
+}
@@ -0,0 +1,135 @@
+use binance::account::*;
+use binance::api::*;
+use binance::config::*;
+use binance::futures::account::*;
+use binance::margin::account::*;
+use binance::market::*;
+use binance::portfolio::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{mock, Matcher};
+    use float_cmp::*;
+    use serde::de::DeserializeOwned;
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> T {
+        let body = std::fs::read_to_string(format!("tests/mocks/portfolio/{}.json", name)).unwrap();
+        serde_json::from_str(&body).unwrap()
+    }
+
+    fn snapshot() -> PortfolioSnapshot {
+        PortfolioSnapshot::new()
+            .set_prices(fixture("prices"))
+            .set_spot(fixture("spot_account"))
+            .set_margin(fixture("margin_account"))
+            .set_futures(fixture("futures_balance"))
+    }
+
+    #[test]
+    fn routed_prices() {
+        let snapshot = snapshot();
+
+        assert!(approx_eq!(f64, snapshot.price("BTC", "USDT").unwrap(), 30000.0, ulps = 2));
+        // Through BTC
+        assert!(approx_eq!(f64, snapshot.price("ETH", "USDT").unwrap(), 1500.0, epsilon = 1e-9));
+        // Inverse pair through USDT
+        assert!(approx_eq!(
+            f64,
+            snapshot.price("BTC", "EUR").unwrap(),
+            30000.0 / 1.1,
+            epsilon = 1e-9
+        ));
+        assert!(snapshot.price("XYZ", "USDT").is_none());
+        assert!(snapshot
+            .clone()
+            .set_intermediates(vec!["USDT".into()])
+            .price("ETH", "USDT")
+            .is_none());
+    }
+
+    #[test]
+    fn valuation_from_snapshot() {
+        let valuation = snapshot().valuation("USDT").unwrap();
+
+        // Empty balances are left out
+        assert_eq!(valuation.holdings.len(), 7);
+        assert_eq!(valuation.unpriced(), vec!["XYZ"]);
+
+        let wallets = valuation.wallets();
+        assert!(approx_eq!(f64, wallets[&Wallet::Spot], 18000.0 + 3000.0 + 1100.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, wallets[&Wallet::CrossMargin], 3999.0 + 6000.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, wallets[&Wallet::Futures], 1050.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, valuation.total(), 33149.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, valuation.liabilities(), 1001.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, valuation.gross_assets(), 34150.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, valuation.net_exposure(), 28100.0, epsilon = 1e-6));
+
+        let assets = valuation.assets();
+        let btc = assets.iter().find(|asset| asset.asset == "BTC").unwrap();
+        assert!(approx_eq!(f64, btc.net_quantity(), 0.8, epsilon = 1e-9));
+        assert!(approx_eq!(f64, btc.value().unwrap(), 24000.0, epsilon = 1e-6));
+        let usdt = assets.iter().find(|asset| asset.asset == "USDT").unwrap();
+        assert!(approx_eq!(f64, usdt.net_quantity(), 5049.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn fetch_isolated_margin() {
+        let mock_prices = mock("GET", "/api/v3/ticker/price")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/portfolio/prices.json")
+            .create();
+        let mock_spot = mock("GET", "/api/v3/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/portfolio/spot_account.json")
+            .create();
+        let mock_isolated = mock("GET", "/sapi/v1/margin/isolated/account")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/portfolio/isolated_margin_account.json")
+            .create();
+        let mock_futures = mock("GET", "/fapi/v2/balance")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .match_query(Matcher::Regex("recvWindow=1234&timestamp=\\d+".into()))
+            .with_body_from_file("tests/mocks/portfolio/futures_balance.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_futures_rest_api_endpoint(mockito::server_url())
+            .set_recv_window(1234)
+            .set_isolated(true);
+        let market: Market = Binance::new_with_config(None, None, &config);
+        let account: Account = Binance::new_with_config(None, None, &config);
+        let margin: MarginAccount = Binance::new_with_config(None, None, &config);
+        let futures: FuturesAccount = Binance::new_with_config(None, None, &config);
+
+        let snapshot =
+            PortfolioSnapshot::fetch(&market, Some(&account), Some(&margin), Some(&futures))
+                .unwrap();
+
+        mock_prices.assert();
+        mock_spot.assert();
+        mock_isolated.assert();
+        mock_futures.assert();
+
+        let valuation = snapshot.valuation("USDT").unwrap();
+        let isolated: Vec<&Holding> = valuation
+            .holdings
+            .iter()
+            .filter(|holding| holding.wallet == Wallet::IsolatedMargin)
+            .collect();
+        assert_eq!(isolated.len(), 2);
+        assert_eq!(isolated[0].symbol.as_deref(), Some("ETHBTC"));
+        assert!(approx_eq!(f64, isolated[0].net_quantity(), 2.0, ulps = 2));
+        assert!(approx_eq!(
+            f64,
+            valuation.wallets()[&Wallet::IsolatedMargin],
+            6000.0,
+            epsilon = 1e-6
+        ));
+        assert!(!valuation.wallets().contains_key(&Wallet::CrossMargin));
+        assert!(approx_eq!(f64, valuation.total(), 29150.0, epsilon = 1e-6));
+    }
+}
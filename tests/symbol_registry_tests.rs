@@ -0,0 +1,173 @@
+use binance::api::*;
+use binance::config::*;
+use binance::futures::general::*;
+use binance::general::*;
+use binance::model::ExchangeInformation;
+use binance::symbol_registry::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::mock;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    fn spot_exchange_info() -> ExchangeInformation {
+        let body = std::fs::read_to_string("tests/mocks/general/exchange_info.json").unwrap();
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn load_spot_and_futures() {
+        let mock_spot = mock("GET", "/api/v3/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/general/exchange_info.json")
+            .create();
+        let mock_futures = mock("GET", "/fapi/v1/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/futures/general/exchange_info.json")
+            .create();
+
+        let config = Config::default()
+            .set_rest_api_endpoint(mockito::server_url())
+            .set_futures_rest_api_endpoint(mockito::server_url());
+        let general: General = Binance::new_with_config(None, None, &config);
+        let futures_general: FuturesGeneral = Binance::new_with_config(None, None, &config);
+
+        let registry = SymbolRegistry::load(Some(general), Some(futures_general)).unwrap();
+
+        mock_spot.assert();
+        mock_futures.assert();
+
+        assert!(registry.is_loaded(SymbolMarket::Spot));
+        assert!(registry.is_loaded(SymbolMarket::Futures));
+        assert_eq!(registry.symbols(SymbolMarket::Spot).len(), 3);
+
+        let symbol = registry.spot_symbol("bnbbtc").unwrap();
+        assert_eq!(symbol.base_asset, "BNB");
+        assert_eq!(
+            registry.spot_symbol_by_assets("ETH", "BTC").unwrap().symbol,
+            "ETHBTC"
+        );
+        assert_eq!(registry.symbols_by_quote(SymbolMarket::Spot, "BTC").len(), 3);
+        assert!(registry.symbols_by_base(SymbolMarket::Spot, "XRP").is_empty());
+        assert!(registry.is_trading(SymbolMarket::Spot, "ETHBTC"));
+        assert_eq!(registry.precision(SymbolMarket::Spot, "ETHBTC"), Some((6, 3)));
+        assert!(registry.spot_filters("ETHBTC").unwrap().tick_size().is_some());
+
+        let mut futures = registry.symbols_by_assets(SymbolMarket::Futures, "BTC", "USDT");
+        futures.sort();
+        assert_eq!(futures, vec!["BTCUSDT", "BTCUSDT_240329"]);
+        assert_eq!(registry.futures_symbol("BTCUSDT").unwrap().price_precision, 2);
+        assert_eq!(registry.precision(SymbolMarket::Futures, "BTCUSDT_240329"), Some((1, 3)));
+        assert_eq!(
+            registry.status(SymbolMarket::Futures, "ETHUSDT").as_deref(),
+            Some("SETTLING")
+        );
+        assert!(!registry.is_trading(SymbolMarket::Futures, "ETHUSDT"));
+        assert!(!registry.contains(SymbolMarket::Futures, "ETHBTC"));
+    }
+
+    #[test]
+    fn change_feed() {
+        let registry = SymbolRegistry::new(None, None);
+        let changes = registry.subscribe();
+
+        let initial = registry.apply_spot_exchange_info(spot_exchange_info()).unwrap();
+        assert!(initial.is_empty());
+        assert!(changes.try_recv().is_err());
+
+        let mut info = spot_exchange_info();
+        info.symbols.retain(|symbol| symbol.symbol != "LTCBTC");
+        info.symbols[0].status = "BREAK".into();
+        let mut listed = info.symbols[1].clone();
+        listed.symbol = "BNBETH".into();
+        listed.quote_asset = "ETH".into();
+        info.symbols.push(listed);
+
+        let expected = vec![
+            SymbolChange::Added {
+                market: SymbolMarket::Spot,
+                symbol: "BNBETH".into(),
+                status: "TRADING".into(),
+            },
+            SymbolChange::StatusChanged {
+                market: SymbolMarket::Spot,
+                symbol: "ETHBTC".into(),
+                previous: "TRADING".into(),
+                status: "BREAK".into(),
+            },
+            SymbolChange::Removed {
+                market: SymbolMarket::Spot,
+                symbol: "LTCBTC".into(),
+            },
+        ];
+        assert_eq!(registry.apply_spot_exchange_info(info).unwrap(), expected);
+        assert_eq!(changes.try_iter().collect::<Vec<_>>(), expected);
+
+        assert!(!registry.contains(SymbolMarket::Spot, "LTCBTC"));
+        assert!(!registry.is_trading(SymbolMarket::Spot, "ETHBTC"));
+        assert_eq!(registry.symbols_by_quote(SymbolMarket::Spot, "ETH"), vec!["BNBETH"]);
+    }
+
+    #[test]
+    fn background_refresh() {
+        let mock_futures = mock("GET", "/fapi/v1/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body_from_file("tests/mocks/futures/general/exchange_info.json")
+            .create();
+
+        let config = Config::default().set_futures_rest_api_endpoint(mockito::server_url());
+        let futures_general: FuturesGeneral = Binance::new_with_config(None, None, &config);
+        let registry = SymbolRegistry::load(None, Some(futures_general)).unwrap();
+        mock_futures.assert();
+        drop(mock_futures);
+
+        // ETHUSDT is delisted by the next reloads
+        let body =
+            std::fs::read_to_string("tests/mocks/futures/general/exchange_info.json").unwrap();
+        let mut info: serde_json::Value = serde_json::from_str(&body).unwrap();
+        info["symbols"]
+            .as_array_mut()
+            .unwrap()
+            .retain(|symbol| symbol["symbol"] != "ETHUSDT");
+        let mock_delisted = mock("GET", "/fapi/v1/exchangeInfo")
+            .with_header("content-type", "application/json;charset=UTF-8")
+            .with_body(info.to_string())
+            .create();
+
+        let changes = registry.subscribe();
+        let refresh = registry.spawn_refresh(Duration::from_millis(50));
+        assert_eq!(
+            changes.recv_timeout(Duration::from_secs(5)).unwrap(),
+            SymbolChange::Removed {
+                market: SymbolMarket::Futures,
+                symbol: "ETHUSDT".into(),
+            }
+        );
+        assert!(!registry.contains(SymbolMarket::Futures, "ETHUSDT"));
+        assert!(refresh.last_error().is_none());
+        drop(mock_delisted);
+
+        let mock_failure = mock("GET", "/fapi/v1/exchangeInfo")
+            .with_status(500)
+            .create();
+        let started = Instant::now();
+        while refresh.last_error().is_none() {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(refresh.last_error().as_deref(), Some("Internal Server Error"));
+        assert!(registry.contains(SymbolMarket::Futures, "BTCUSDT"));
+
+        // Once stopped the thread is joined and no reload follows
+        refresh.stop();
+        drop(mock_failure);
+        let mock_stopped = mock("GET", "/fapi/v1/exchangeInfo")
+            .with_status(500)
+            .expect(0)
+            .create();
+        thread::sleep(Duration::from_millis(200));
+        mock_stopped.assert();
+    }
+}
@@ -0,0 +1,314 @@
+use binance::config::*;
+use binance::websockets::*;
+use binance::futures::websockets::*;
+use binance::options::websockets::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use binance::model::Number;
+    use binance::stream_stats::StaleAction;
+    use std::net::TcpListener;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use tungstenite::{accept, Message};
+
+    fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+
+    #[test]
+    fn stream_stats_for_combined_streams() {
+        let mut events = 0;
+        let mut web_socket = WebSockets::new(|_event: WebsocketEvent| {
+            events += 1;
+            Ok(())
+        });
+        let monitor = web_socket.monitor();
+
+        let event_time = now_ms() - 250;
+        for _ in 0..3 {
+            let msg = format!(
+                r#"{{"stream":"bnbbtc@aggTrade","data":{{"e":"aggTrade","E":{},"s":"BNBBTC","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":{},"m":true,"M":true}}}}"#,
+                event_time, event_time
+            );
+            web_socket.test_handle_msg(&msg).unwrap();
+        }
+        drop(web_socket);
+
+        assert_eq!(events, 3);
+        let stats = monitor.stats("bnbbtc@aggTrade").unwrap();
+        assert_eq!(stats.messages, 3);
+        assert_eq!(stats.last_event_time(), Some(event_time));
+
+        let latency = stats.latency_summary().unwrap();
+        assert!(latency.p50 >= 250);
+        assert!(latency.max >= latency.p99);
+        assert!(stats.last_message_age().as_secs() < 1);
+    }
+
+    #[test]
+    fn stream_stats_without_event_time() {
+        let mut web_socket = FuturesWebSockets::new(|_event: FuturesWebsocketEvent| Ok(()));
+
+        let msg = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        web_socket.test_handle_msg(msg).unwrap();
+
+        let stats = web_socket.monitor().stats("bnbusdt@unknown").unwrap();
+        assert_eq!(stats.messages, 1);
+        assert!(stats.latency_summary().is_none());
+    }
+
+    #[test]
+    fn stop_handle_interrupts_blocked_read() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}/ws", listener.local_addr().unwrap());
+
+        // Quiet server which only reports the close frame it receives
+        let (closed_tx, closed_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = accept(stream).unwrap();
+            loop {
+                match socket.read_message() {
+                    Ok(Message::Close(frame)) => {
+                        closed_tx.send(frame.is_some()).unwrap();
+                        break;
+                    }
+                    Ok(_) => (),
+                    Err(_) => break,
+                }
+            }
+        });
+
+        let (handle_tx, handle_rx) = mpsc::channel();
+        let event_loop = thread::spawn(move || {
+            let config = Config::default().set_ws_endpoint(endpoint);
+            let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()));
+            web_socket.connect_with_config("bnbbtc@trade", &config).unwrap();
+            handle_tx.send(web_socket.stop_handle()).unwrap();
+
+            let keep_running = AtomicBool::new(false);
+            let started = Instant::now();
+            let result = web_socket.event_loop(&keep_running);
+            (result, started.elapsed())
+        });
+
+        let stop_handle = handle_rx.recv().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        stop_handle.stop();
+
+        let (result, elapsed) = event_loop.join().unwrap();
+        assert!(result.is_ok());
+        assert!(elapsed < Duration::from_secs(5));
+        assert!(stop_handle.is_stopped());
+        assert!(closed_rx.recv_timeout(Duration::from_secs(5)).unwrap());
+    }
+
+    #[test]
+    fn stale_stream_reconnects_then_disconnects() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("ws://{}/ws", listener.local_addr().unwrap());
+
+        // Quiet server which reports every websocket it accepts
+        let (accepted_tx, accepted_rx) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut socket = accept(stream.unwrap()).unwrap();
+                accepted_tx.send(()).unwrap();
+                thread::spawn(move || while socket.read_message().is_ok() {});
+            }
+        });
+
+        let (stale_tx, stale_rx) = mpsc::channel();
+        let event_loop = thread::spawn(move || {
+            let config = Config::default().set_ws_endpoint(endpoint);
+            let mut stale_count = 0;
+            let mut web_socket = WebSockets::new(|_event: WebsocketEvent| Ok(()))
+                .set_stale_threshold(Duration::from_millis(200))
+                .on_stale(move |stream, age| {
+                    stale_tx.send((stream.to_owned(), age)).unwrap();
+                    stale_count += 1;
+                    if stale_count == 1 {
+                        StaleAction::Reconnect
+                    } else {
+                        StaleAction::Disconnect
+                    }
+                });
+            web_socket.connect_with_config("bnbbtc@trade", &config).unwrap();
+
+            let keep_running = AtomicBool::new(false);
+            web_socket.event_loop(&keep_running).is_err()
+        });
+
+        let timeout = Duration::from_secs(5);
+        accepted_rx.recv_timeout(timeout).unwrap();
+
+        let (stream, age) = stale_rx.recv_timeout(timeout).unwrap();
+        assert_eq!(stream, "bnbbtc@trade");
+        assert!(age > Duration::from_millis(200));
+        accepted_rx.recv_timeout(timeout).unwrap();
+
+        let (stream, _) = stale_rx.recv_timeout(timeout).unwrap();
+        assert_eq!(stream, "bnbbtc@trade");
+        assert!(event_loop.join().unwrap());
+        assert!(accepted_rx.try_recv().is_err());
+    }
+
+    fn futures_events(messages: &[&str]) -> Vec<FuturesWebsocketEvent> {
+        let mut events = Vec::new();
+        let mut web_socket = FuturesWebSockets::new(|event: FuturesWebsocketEvent| {
+            events.push(event);
+            Ok(())
+        });
+        for msg in messages {
+            web_socket.test_handle_msg(msg).unwrap();
+        }
+        drop(web_socket);
+        events
+    }
+
+    #[test]
+    fn futures_account_update() {
+        let events = futures_events(&[
+            r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{"m":"ORDER","B":[{"a":"USDT","wb":"122624.12345678","cw":"100.12345678","bc":"50.12345678"}],"P":[{"s":"BTCUSDT","pa":"20","ep":"6563.66500","bep":"6563.6","cr":"0","up":"2850.21200","mt":"isolated","iw":"13200.70726908","ps":"LONG"}]}}"#,
+        ]);
+
+        match &events[..] {
+            [FuturesWebsocketEvent::AccountUpdate(event)] => {
+                assert_eq!(event.transaction_time, 1564745798938);
+                assert_eq!(event.data.reason, "ORDER");
+                assert_eq!(event.data.balances[0].balance_change, "50.12345678");
+                assert_eq!(event.data.positions[0].breakeven_price, "6563.6");
+                assert_eq!(event.data.positions[0].position_side, "LONG");
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    #[test]
+    fn futures_malformed_user_data_is_skipped() {
+        let events = futures_events(&[
+            r#"{"e":"ACCOUNT_UPDATE","E":1564745798939,"T":1564745798938,"a":{"m":"ORDER","B":"not a list"}}"#,
+            r#"{"e":"MARGIN_CALL","E":1587727187525,"cw":"3.16812045"}"#,
+            r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSDT","l":25}}"#,
+        ]);
+
+        match &events[..] {
+            [FuturesWebsocketEvent::AccountConfigUpdate(event)] => {
+                assert_eq!(event.transaction_time, 1611646737476);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    #[test]
+    fn futures_margin_call_and_config_update() {
+        let events = futures_events(&[
+            r#"{"e":"MARGIN_CALL","E":1587727187525,"cw":"3.16812045","p":[{"s":"ETHUSDT","ps":"LONG","pa":"1.327","mt":"CROSSED","iw":"0","mp":"187.17127","up":"-1.166074","mm":"1.614445"}]}"#,
+            r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ac":{"s":"BTCUSDT","l":25}}"#,
+            r#"{"e":"ACCOUNT_CONFIG_UPDATE","E":1611646737479,"T":1611646737476,"ai":{"j":true}}"#,
+        ]);
+
+        match &events[..] {
+            [FuturesWebsocketEvent::MarginCall(margin_call), FuturesWebsocketEvent::AccountConfigUpdate(leverage), FuturesWebsocketEvent::AccountConfigUpdate(multi_assets)] =>
+            {
+                assert_eq!(margin_call.cross_wallet_balance.as_deref(), Some("3.16812045"));
+                assert_eq!(margin_call.positions[0].maintenance_margin_required, "1.614445");
+                assert_eq!(leverage.leverage.as_ref().unwrap().leverage, 25);
+                assert!(leverage.multi_assets.is_none());
+                assert!(multi_assets.multi_assets.as_ref().unwrap().multi_assets_margin);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    #[test]
+    fn futures_trade_lite_and_strategy_updates() {
+        let events = futures_events(&[
+            r#"{"e":"TRADE_LITE","E":1721895408092,"T":1721895408214,"s":"BTCUSDT","q":"0.001","p":"0","m":false,"c":"z8hcUoOsqEdKMeKPSABslD","S":"BUY","L":"64089.20","l":"0.040","t":109100866,"i":8886774}"#,
+            r#"{"e":"STRATEGY_UPDATE","T":1669262908216,"E":1669262908218,"su":{"si":176054594,"st":"GRID","ss":"NEW","s":"BTCUSDT","ut":1669262908216,"c":8007}}"#,
+            r#"{"e":"GRID_UPDATE","T":1669262908216,"E":1669262908218,"gu":{"si":176057039,"st":"GRID","ss":"WORKING","s":"BTCUSDT","r":"-0.00300716","up":"16720","uq":"-0.001","uf":"-0.00300716","mp":"0.0","ut":1669262908197}}"#,
+        ]);
+
+        match &events[..] {
+            [FuturesWebsocketEvent::TradeLite(trade), FuturesWebsocketEvent::StrategyUpdate(strategy), FuturesWebsocketEvent::GridUpdate(grid)] =>
+            {
+                assert_eq!(trade.last_filled_price, "64089.20");
+                assert_eq!(trade.order_id, 8886774);
+                assert!(!trade.is_maker);
+                assert_eq!(strategy.strategy.strategy_status, "NEW");
+                assert_eq!(strategy.strategy.op_code, 8007);
+                assert_eq!(grid.grid.unmatched_qty, "-0.001");
+                assert_eq!(grid.grid.update_time, 1669262908197);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    fn options_events(messages: &[&str]) -> Vec<OptionsWebsocketEvent> {
+        let mut events = Vec::new();
+        let mut web_socket = OptionsWebSockets::new(|event: OptionsWebsocketEvent| {
+            events.push(event);
+            Ok(())
+        });
+        for msg in messages {
+            web_socket.test_handle_msg(msg).unwrap();
+        }
+        drop(web_socket);
+        events
+    }
+
+    #[test]
+    fn options_trade_index_and_mark_price() {
+        let events = options_events(&[
+            r#"{"e":"trade","E":1591677941092,"s":"BTC-200630-9000-P","t":"1","p":"1000.0","q":"-2.0","b":4611781675939004417,"a":4611781675939004418,"T":1591677567872,"S":"-1"}"#,
+            r#"{"stream":"BTCUSDT@index","data":{"e":"index","E":1661415480351,"s":"BTCUSDT","p":"21531.54"}}"#,
+            r#"[{"e":"markPrice","E":1663684594227,"s":"ETH-220930-1500-C","mp":"30.3"},{"e":"markPrice","E":1663684594228,"s":"ETH-220930-1600-C","mp":"11.6"}]"#,
+            r#"{"e":"unknown","E":1663684594227}"#,
+        ]);
+
+        match &events[..] {
+            [OptionsWebsocketEvent::Trade(trade), OptionsWebsocketEvent::IndexPrice(index), OptionsWebsocketEvent::MarkPriceAll(marks)] =>
+            {
+                assert_eq!(trade.trade_id, "1");
+                assert_eq!(trade.side, "-1");
+                assert_eq!(trade.qty, -2.0);
+                assert_eq!(index.symbol, "BTCUSDT");
+                assert_eq!(index.price, 21531.54);
+                assert_eq!(marks.len(), 2);
+                assert_eq!(marks[1].mark_price, 11.6);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+
+    #[test]
+    fn options_kline_depth_and_open_interest() {
+        let events = options_events(&[
+            r#"{"e":"kline","E":1638747660000,"s":"BTC-200630-9000-P","k":{"t":1638747660000,"T":1638747719999,"s":"BTC-200630-9000-P","i":"1m","F":0,"L":4,"o":"1000","c":"1100","h":"1100","l":"900","v":"2","n":5,"x":false,"q":"2000","V":"1","Q":"1100"}}"#,
+            r#"{"e":"depth","E":1591695934010,"T":1591695934000,"s":"BTC-200630-9000-P","u":162,"pu":161,"b":[["200","3"],["101","1"]],"a":[["1000","89"]]}"#,
+            r#"[{"e":"openInterest","E":1668759300045,"s":"ETH-221125-2700-C","o":"1580.87","h":"1912992.178168204"}]"#,
+        ]);
+
+        match &events[..] {
+            [OptionsWebsocketEvent::Kline(kline), OptionsWebsocketEvent::DepthOrderBook(depth), OptionsWebsocketEvent::OpenInterestAll(open_interest)] =>
+            {
+                assert_eq!(kline.kline.interval, "1m");
+                assert_eq!(kline.kline.trade_count, 5);
+                assert!(!kline.kline.is_final_bar);
+                assert_eq!(depth.update_id, 162);
+                assert_eq!(depth.previous_update_id, 161);
+                assert_eq!(depth.bids[1].price, "101".parse::<Number>().unwrap());
+                assert_eq!(depth.asks[0].qty, "89".parse::<Number>().unwrap());
+                assert_eq!(open_interest[0].open_interest, 1580.87);
+            }
+            other => panic!("unexpected events {:?}", other),
+        }
+    }
+}